@@ -0,0 +1,497 @@
+//! [`Arbitrary`] implementations for generating valid expression trees, for
+//! property-based testing and fuzzing. Requires the `arbitrary` feature.
+//!
+//! Generated trees are kept small: nesting stops at [`MAX_DEPTH`], and lists,
+//! maps, sets, and `IN` operands have at most [`MAX_LEN`] items.
+
+use ::arbitrary::{Arbitrary, Result, Unstructured};
+
+use crate::{
+    condition::{attribute_type::Type, Between, Comparator, Comparison, Condition, In},
+    key::KeyCondition,
+    operand::{Operand, OperandType, Size},
+    path::{Element, Path},
+    update::{AddValue, Update},
+    value::{List, Map, Num, Scalar, Set, Value},
+};
+
+/// How deeply conditions, lists, and maps are nested.
+const MAX_DEPTH: usize = 3;
+
+/// The most items in a list, map, set, or `IN` condition.
+const MAX_LEN: usize = 4;
+
+/// The most elements in a path, like `foo[3].bar`.
+const MAX_ELEMENTS: usize = 3;
+
+/// The most indexes on a path element, like `foo[3][7]`.
+const MAX_INDEXES: usize = 2;
+
+/// The most actions in an update expression.
+const MAX_ACTIONS: usize = 4;
+
+/// The longest attribute name, string, or binary value.
+const MAX_STRING: usize = 8;
+
+/// Attribute names are generated from these, so they survive a round trip
+/// through [`Path`]'s `Display` and `FromStr`.
+const NAME_CHARS: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789_-";
+
+const COMPARATORS: [Comparator; 6] = [
+    Comparator::Eq,
+    Comparator::Ne,
+    Comparator::Lt,
+    Comparator::Le,
+    Comparator::Gt,
+    Comparator::Ge,
+];
+
+const TYPES: [Type; 10] = [
+    Type::String,
+    Type::StringSet,
+    Type::Number,
+    Type::NumberSet,
+    Type::Binary,
+    Type::BinarySet,
+    Type::Boolean,
+    Type::Null,
+    Type::List,
+    Type::Map,
+];
+
+impl<'a> Arbitrary<'a> for Path {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Self {
+            elements: items(u, 1, MAX_ELEMENTS, element)?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for Num {
+    /// Integers, or decimals with up to two places. Never `NaN` or infinite.
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(if u.arbitrary()? {
+            Self::new(u.arbitrary::<i64>()?)
+        } else {
+            Self::new(f64::from(u.arbitrary::<i32>()?) / 100.0)
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for Value {
+    /// Any type of value. Sets are never empty.
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        value(u, MAX_DEPTH)
+    }
+}
+
+impl<'a> Arbitrary<'a> for Condition {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        condition(u, MAX_DEPTH)
+    }
+}
+
+impl<'a> Arbitrary<'a> for KeyCondition {
+    /// An equality condition on a partition key, with or without a condition on
+    /// a sort key. Values are never empty, and the bounds of a `BETWEEN` are the
+    /// same type, in order.
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let partition = Path::new_name(name(u)?);
+        let key_condition = partition.clone().key().equal(key_value(u)?);
+
+        let sort = Path::new_name(name(u)?);
+        if sort == partition || !u.arbitrary::<bool>()? {
+            return Ok(key_condition);
+        }
+
+        let key = sort.key();
+        let sort_condition = match u.choose_index(7)? {
+            0 => key.equal(key_value(u)?),
+            1 => key.less_than(key_value(u)?),
+            2 => key.less_than_or_equal(key_value(u)?),
+            3 => key.greater_than(key_value(u)?),
+            4 => key.greater_than_or_equal(key_value(u)?),
+            5 => {
+                let (lower, upper) = key_bounds(u)?;
+                key.between(lower, upper)
+            }
+            _ => key.begins_with(key_string(u)?),
+        };
+
+        Ok(key_condition.and(sort_condition))
+    }
+}
+
+impl<'a> Arbitrary<'a> for Update {
+    /// Between one and four actions. No two actions have overlapping paths,
+    /// which DynamoDB would reject.
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let path = Path::arbitrary(u)?;
+        let mut paths = vec![path.clone()];
+        let mut update = action(u, path)?;
+
+        for _ in 1..u.int_in_range(1..=MAX_ACTIONS)? {
+            let path = Path::arbitrary(u)?;
+            if paths
+                .iter()
+                .any(|other| other.covers(&path) || path.covers(other))
+            {
+                continue;
+            }
+
+            paths.push(path.clone());
+            update = update.and(action(u, path)?);
+        }
+
+        Ok(update)
+    }
+}
+
+/// Between `min` and `max` items made by `f`.
+fn items<'a, T, F>(u: &mut Unstructured<'a>, min: usize, max: usize, mut f: F) -> Result<Vec<T>>
+where
+    F: FnMut(&mut Unstructured<'a>) -> Result<T>,
+{
+    let len = u.int_in_range(min..=max)?;
+
+    (0..len).map(|_| f(u)).collect()
+}
+
+fn name(u: &mut Unstructured<'_>) -> Result<String> {
+    items(u, 1, MAX_STRING, |u| u.choose(NAME_CHARS).copied())
+        .map(|name| name.into_iter().map(char::from).collect())
+}
+
+fn element(u: &mut Unstructured<'_>) -> Result<Element> {
+    let name = name(u)?;
+    let indexes = items(u, 0, MAX_INDEXES, |u| u.int_in_range(0..=99))?;
+
+    Ok(Element::new_indexed_field(name, indexes))
+}
+
+fn string(u: &mut Unstructured<'_>) -> Result<String> {
+    let string: &str = u.arbitrary()?;
+
+    Ok(string.chars().take(MAX_STRING).collect())
+}
+
+fn binary(u: &mut Unstructured<'_>) -> Result<Vec<u8>> {
+    let len = u.int_in_range(0..=MAX_STRING)?;
+
+    Ok(u.bytes(len)?.to_vec())
+}
+
+fn scalar(u: &mut Unstructured<'_>) -> Result<Scalar> {
+    Ok(match u.choose_index(5)? {
+        0 => Scalar::new_string(string(u)?),
+        1 => Num::arbitrary(u)?.into(),
+        2 => Scalar::new_bool(u.arbitrary()?),
+        3 => Scalar::new_binary(binary(u)?),
+        _ => Scalar::new_null(),
+    })
+}
+
+/// A non-empty string, as DynamoDB requires for key attributes.
+fn key_string(u: &mut Unstructured<'_>) -> Result<String> {
+    items(u, 1, MAX_STRING, char::arbitrary).map(String::from_iter)
+}
+
+/// A non-empty binary value, as DynamoDB requires for key attributes.
+fn key_binary(u: &mut Unstructured<'_>) -> Result<Vec<u8>> {
+    let len = u.int_in_range(1..=MAX_STRING)?;
+
+    Ok(u.bytes(len)?.to_vec())
+}
+
+/// A string, number, or binary value, as allowed for key attributes.
+fn key_value(u: &mut Unstructured<'_>) -> Result<Scalar> {
+    Ok(match u.choose_index(3)? {
+        0 => Scalar::new_string(key_string(u)?),
+        1 => Num::arbitrary(u)?.into(),
+        _ => Scalar::new_binary(key_binary(u)?),
+    })
+}
+
+/// Two key values of the same type, lowest first, for a `BETWEEN`.
+fn key_bounds(u: &mut Unstructured<'_>) -> Result<(Scalar, Scalar)> {
+    // DynamoDB orders strings and binary values by their bytes, the same as
+    // `Ord` for `String` and `Vec<u8>`.
+    fn ordered<T: Ord>(a: T, b: T) -> (T, T) {
+        if a <= b {
+            (a, b)
+        } else {
+            (b, a)
+        }
+    }
+
+    Ok(match u.choose_index(3)? {
+        0 => {
+            let (lower, upper) = ordered(key_string(u)?, key_string(u)?);
+            (Scalar::new_string(lower), Scalar::new_string(upper))
+        }
+        1 => {
+            let (lower, upper) = ordered(u.arbitrary::<i64>()?, u.arbitrary::<i64>()?);
+            (Num::new(lower).into(), Num::new(upper).into())
+        }
+        _ => {
+            let (lower, upper) = ordered(key_binary(u)?, key_binary(u)?);
+            (Scalar::new_binary(lower), Scalar::new_binary(upper))
+        }
+    })
+}
+
+fn set(u: &mut Unstructured<'_>) -> Result<Set> {
+    Ok(match u.choose_index(3)? {
+        0 => Set::new_string_set(items(u, 1, MAX_LEN, string)?),
+        1 => Set::new_num_set(items(u, 1, MAX_LEN, Num::arbitrary)?),
+        _ => Set::new_binary_set(items(u, 1, MAX_LEN, binary)?),
+    })
+}
+
+fn value(u: &mut Unstructured<'_>, depth: usize) -> Result<Value> {
+    // Lists and maps only while there's depth left to nest them.
+    let kinds = if depth == 0 { 2 } else { 4 };
+
+    Ok(match u.choose_index(kinds)? {
+        0 => scalar(u)?.into(),
+        1 => set(u)?.into(),
+        2 => List::from(items(u, 0, MAX_LEN, |u| value(u, depth - 1))?).into(),
+        _ => Map::from(items(u, 0, MAX_LEN, |u| {
+            Ok((name(u)?, value(u, depth - 1)?))
+        })?)
+        .into(),
+    })
+}
+
+fn operand(u: &mut Unstructured<'_>) -> Result<Operand> {
+    Ok(match u.choose_index(3)? {
+        0 => Path::arbitrary(u)?.into(),
+        1 => scalar(u)?.into(),
+        _ => Operand {
+            op: OperandType::Size(Size::from(Path::arbitrary(u)?)),
+        },
+    })
+}
+
+fn condition(u: &mut Unstructured<'_>, depth: usize) -> Result<Condition> {
+    // `AND`, `OR`, `NOT`, and parentheses only while there's depth left.
+    let kinds = if depth == 0 { 8 } else { 12 };
+
+    Ok(match u.choose_index(kinds)? {
+        0 => Comparison {
+            left: operand(u)?,
+            cmp: *u.choose(&COMPARATORS)?,
+            right: operand(u)?,
+        }
+        .into(),
+        1 => Between {
+            op: operand(u)?,
+            lower: operand(u)?,
+            upper: operand(u)?,
+        }
+        .into(),
+        2 => In {
+            op: operand(u)?,
+            items: items(u, 1, MAX_LEN, operand)?,
+        }
+        .into(),
+        3 => Path::arbitrary(u)?.attribute_exists(),
+        4 => Path::arbitrary(u)?.attribute_not_exists(),
+        5 => Path::arbitrary(u)?.attribute_type(*u.choose(&TYPES)?),
+        6 => Path::arbitrary(u)?.begins_with(string(u)?),
+        7 => Path::arbitrary(u)?.contains(scalar(u)?),
+        8 => condition(u, depth - 1)?.and(condition(u, depth - 1)?),
+        9 => condition(u, depth - 1)?.or(condition(u, depth - 1)?),
+        10 => condition(u, depth - 1)?.not(),
+        _ => condition(u, depth - 1)?.parenthesize(),
+    })
+}
+
+fn action(u: &mut Unstructured<'_>, path: Path) -> Result<Update> {
+    Ok(match u.choose_index(7)? {
+        0 => path.set(value(u, MAX_DEPTH)?).into(),
+        1 => {
+            let mut math = path.math();
+            if u.arbitrary()? {
+                math = math.if_not_exists(Num::arbitrary(u)?);
+            }
+
+            if u.arbitrary()? {
+                math.add(Num::arbitrary(u)?)
+            } else {
+                math.sub(Num::arbitrary(u)?)
+            }
+            .into()
+        }
+        2 => path
+            .list_append()
+            .list(items(u, 0, MAX_LEN, |u| value(u, MAX_DEPTH - 1))?)
+            .into(),
+        3 => path.if_not_exists().set(value(u, MAX_DEPTH)?).into(),
+        4 => path.remove().into(),
+        5 => path
+            .add(if u.arbitrary()? {
+                AddValue::from(Num::arbitrary(u)?)
+            } else {
+                AddValue::from(set(u)?)
+            })
+            .into(),
+        _ => path.delete(set(u)?).into(),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::{BTreeSet, HashSet};
+
+    use ::arbitrary::{Arbitrary, Unstructured};
+    use pretty_assertions::assert_eq;
+
+    use aws_sdk_dynamodb::types::AttributeValue;
+
+    use crate::{
+        condition::Condition,
+        key::KeyCondition,
+        operand::{Operand, OperandType},
+        redact::Redact,
+        redact::Redaction,
+        update::Update,
+        value::{Scalar, Value, ValueOrRef},
+        Expression, Path,
+    };
+
+    /// Deterministic input for each generated case, so failures reproduce.
+    fn cases() -> impl Iterator<Item = Vec<u8>> {
+        // xorshift64
+        let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+
+        (0..256).map(move |_| {
+            (0..1024)
+                .map(|_| {
+                    state ^= state << 13;
+                    state ^= state >> 7;
+                    state ^= state << 17;
+                    state.to_le_bytes()[0]
+                })
+                .collect()
+        })
+    }
+
+    /// The `#name` and `:value` placeholders used in an expression string.
+    fn placeholders(expression: &str) -> impl Iterator<Item = String> + '_ {
+        expression.match_indices(['#', ':']).map(|(start, _)| {
+            let end = expression[start + 1..]
+                .find(|c: char| !c.is_ascii_digit())
+                .map_or(expression.len(), |end| start + 1 + end);
+
+            expression[start..end].to_string()
+        })
+    }
+
+    #[test]
+    fn path_round_trip() {
+        for data in cases() {
+            let path = Path::arbitrary(&mut Unstructured::new(&data)).unwrap();
+
+            assert_eq!(path, path.to_string().parse::<Path>().unwrap());
+        }
+    }
+
+    #[test]
+    fn build_placeholders() {
+        for data in cases() {
+            let u = &mut Unstructured::new(&data);
+            let condition = Condition::arbitrary(u).unwrap();
+            let key_condition = KeyCondition::arbitrary(u).unwrap();
+            let update = Update::arbitrary(u).unwrap();
+            let filter = Condition::arbitrary(u).unwrap();
+            let projection: Vec<Path> = u.arbitrary().unwrap();
+
+            let expression = Expression::builder()
+                .with_condition(condition)
+                .with_key_condition(key_condition)
+                .with_update(update)
+                .with_filter(filter)
+                .with_projection_paths(projection)
+                .build();
+
+            let used: BTreeSet<_> = [
+                &expression.condition_expression,
+                &expression.key_condition_expression,
+                &expression.update_expression,
+                &expression.filter_expression,
+                &expression.projection_expression,
+            ]
+            .into_iter()
+            .flatten()
+            .flat_map(|expression| placeholders(expression))
+            .collect();
+
+            let names = expression.expression_attribute_names.unwrap_or_default();
+            let values = expression.expression_attribute_values.unwrap_or_default();
+            let defined: BTreeSet<_> = names.keys().chain(values.keys()).cloned().collect();
+
+            assert_eq!(
+                defined, used,
+                "Every placeholder should be defined, and every definition used"
+            );
+            assert_eq!(
+                names.len(),
+                names.values().collect::<HashSet<_>>().len(),
+                "Each name should have one placeholder"
+            );
+        }
+    }
+
+    #[test]
+    fn unredacted_display() {
+        for data in cases() {
+            let condition = Condition::arbitrary(&mut Unstructured::new(&data)).unwrap();
+
+            assert_eq!(
+                condition.to_string(),
+                condition.redacted(&Redaction::new()).to_string()
+            );
+        }
+    }
+
+    #[test]
+    fn key_conditions() {
+        fn scalar(operand: &Operand) -> &Scalar {
+            match &operand.op {
+                OperandType::Scalar(ValueOrRef::Value(Value::Scalar(scalar))) => scalar,
+                op => panic!("Expected a scalar, got: {op}"),
+            }
+        }
+
+        for data in cases() {
+            let key_condition = KeyCondition::arbitrary(&mut Unstructured::new(&data)).unwrap();
+
+            let expression = Expression::builder()
+                .with_key_condition(key_condition.clone())
+                .build();
+            for value in expression.expression_attribute_values.unwrap().values() {
+                match value {
+                    AttributeValue::S(s) => assert!(!s.is_empty(), "{key_condition}"),
+                    AttributeValue::B(b) => assert!(!b.as_ref().is_empty(), "{key_condition}"),
+                    AttributeValue::N(_) => {}
+                    value => panic!("Not a key value: {value:?}"),
+                }
+            }
+
+            let sort_condition = match key_condition.condition {
+                Condition::And(and) => *and.right,
+                _ => continue,
+            };
+            if let Condition::Between(between) = sort_condition {
+                let (lower, upper) = (scalar(&between.lower), scalar(&between.upper));
+                assert_eq!(
+                    core::mem::discriminant(lower),
+                    core::mem::discriminant(upper),
+                    "{lower} and {upper} should be the same type"
+                );
+                assert!(lower <= upper, "{lower} should be at most {upper}");
+            }
+        }
+    }
+}
@@ -0,0 +1,881 @@
+//! Helpers for [`BatchGetItem`][1] and [`BatchWriteItem`][2] that take any
+//! number of keys or writes, across tables.
+//!
+//! Requests are split to stay within DynamoDB's limits (100 keys per
+//! `BatchGetItem`, 25 writes and 16 MB per `BatchWriteItem`). Any
+//! `UnprocessedKeys` or `UnprocessedItems` are re-submitted, waiting between
+//! attempts according to a [`Backoff`] policy.
+//!
+//! Waiting uses the [`Client`]'s configured sleep implementation. If the
+//! client has none, retries are sent without waiting.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! # async fn example() -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+//! use std::collections::HashMap;
+//!
+//! use aws_config::BehaviorVersion;
+//! use aws_sdk_dynamodb::{types::AttributeValue, Client};
+//! use dynamodb_expression::{batch::BatchGet, Expression};
+//!
+//! let client = Client::new(&aws_config::load_defaults(BehaviorVersion::latest()).await);
+//!
+//! let keys = (0..1000).map(|id| HashMap::from([("id".to_string(), AttributeValue::N(id.to_string()))]));
+//!
+//! let output = BatchGet::new(Expression::builder().with_projection(["id", "name"]).build())
+//!     .with_keys("people", keys)
+//!     .send(&client)
+//!     .await?;
+//!
+//! let people = &output.items["people"];
+//! # _ = people;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [1]: https://docs.aws.amazon.com/amazondynamodb/latest/APIReference/API_BatchGetItem.html
+//! [2]: https://docs.aws.amazon.com/amazondynamodb/latest/APIReference/API_BatchWriteItem.html
+
+use core::{fmt, future::Future, time::Duration};
+use std::{
+    collections::{HashMap, VecDeque},
+    error::Error,
+};
+
+use aws_sdk_dynamodb::{
+    config::{AsyncSleep, SharedAsyncSleep},
+    error::SdkError,
+    operation::{batch_get_item::BatchGetItemError, batch_write_item::BatchWriteItemError},
+    types::{AttributeValue, DeleteRequest, PutRequest, WriteRequest},
+    Client,
+};
+
+#[cfg(feature = "tracing")]
+use crate::trace;
+use crate::{redact::Redaction, value::item_size_bytes, Expression};
+
+/// The most keys DynamoDB allows in a `BatchGetItem` request.
+pub const MAX_BATCH_GET_KEYS: usize = 100;
+
+/// The most writes DynamoDB allows in a `BatchWriteItem` request.
+pub const MAX_BATCH_WRITES: usize = 25;
+
+/// The largest `BatchWriteItem` request DynamoDB allows, in bytes (16 MB).
+pub const MAX_BATCH_WRITE_BYTES: usize = 16 * 1024 * 1024;
+
+/// An item, or a key, as used by the AWS SDK.
+type Item = HashMap<String, AttributeValue>;
+
+/// How long to wait before re-submitting unprocessed keys or writes.
+///
+/// Implemented for [`ExponentialBackoff`], and for closures that take the
+/// retry number and return the delay.
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use dynamodb_expression::batch::BatchWrite;
+///
+/// // Retry up to three times, waiting 100ms each time.
+/// let batch = BatchWrite::new()
+///     .with_backoff(|retry: u32| (retry <= 3).then_some(Duration::from_millis(100)));
+/// # _ = batch;
+/// ```
+pub trait Backoff {
+    /// The delay before the given retry (starting at `1`), or `None` to stop
+    /// retrying. Anything still unprocessed is returned in the output.
+    fn delay(&self, retry: u32) -> Option<Duration>;
+}
+
+impl<F> Backoff for F
+where
+    F: Fn(u32) -> Option<Duration>,
+{
+    fn delay(&self, retry: u32) -> Option<Duration> {
+        self(retry)
+    }
+}
+
+/// Waits exponentially longer before each retry, up to a maximum delay and
+/// number of retries.
+///
+/// The default starts at 50ms, doubling up to 5s, for up to 10 retries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ExponentialBackoff {
+    initial_delay: Duration,
+    max_delay: Duration,
+    max_retries: u32,
+}
+
+impl ExponentialBackoff {
+    /// The default policy. See [`ExponentialBackoff`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The delay before the first retry. Each retry after doubles it.
+    pub fn with_initial_delay(mut self, initial_delay: Duration) -> Self {
+        self.initial_delay = initial_delay;
+        self
+    }
+
+    /// The longest to wait before any retry.
+    pub fn with_max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// How many times to retry before giving up.
+    pub fn with_max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+}
+
+impl Default for ExponentialBackoff {
+    fn default() -> Self {
+        Self {
+            initial_delay: Duration::from_millis(50),
+            max_delay: Duration::from_secs(5),
+            max_retries: 10,
+        }
+    }
+}
+
+impl Backoff for ExponentialBackoff {
+    fn delay(&self, retry: u32) -> Option<Duration> {
+        if retry > self.max_retries {
+            return None;
+        }
+
+        let factor = 2_u32.saturating_pow(retry.saturating_sub(1));
+
+        Some(
+            self.initial_delay
+                .saturating_mul(factor)
+                .min(self.max_delay),
+        )
+    }
+}
+
+/// Gets any number of items, from any number of tables, with
+/// [`BatchGetItem`][1].
+///
+/// See the [module documentation](self) for details and an example.
+///
+/// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/APIReference/API_BatchGetItem.html
+pub struct BatchGet {
+    expression: Expression,
+    consistent_read: Option<bool>,
+    keys: Vec<(String, Item)>,
+    backoff: Box<dyn Backoff + Send + Sync>,
+    redaction: Redaction,
+}
+
+impl BatchGet {
+    /// Starts a batch get. The projection expression (and its attribute
+    /// names) from the [`Expression`] is used for every table.
+    pub fn new(expression: Expression) -> Self {
+        Self {
+            expression,
+            consistent_read: None,
+            keys: Vec::new(),
+            backoff: Box::new(ExponentialBackoff::default()),
+            redaction: Redaction::all(),
+        }
+    }
+
+    /// Adds keys of items to get from the table.
+    ///
+    /// A key should only be added once per table. DynamoDB rejects requests
+    /// with duplicate keys.
+    pub fn with_keys<T, I>(mut self, table_name: T, keys: I) -> Self
+    where
+        T: Into<String>,
+        I: IntoIterator<Item = Item>,
+    {
+        let table_name = table_name.into();
+        self.keys
+            .extend(keys.into_iter().map(|key| (table_name.clone(), key)));
+        self
+    }
+
+    /// Whether to use strongly consistent reads for every table.
+    pub fn with_consistent_read(mut self, consistent_read: bool) -> Self {
+        self.consistent_read = Some(consistent_read);
+        self
+    }
+
+    /// How long to wait before re-submitting unprocessed keys. Defaults to
+    /// [`ExponentialBackoff::default`].
+    pub fn with_backoff<B>(mut self, backoff: B) -> Self
+    where
+        B: Backoff + Send + Sync + 'static,
+    {
+        self.backoff = Box::new(backoff);
+        self
+    }
+
+    /// How values in the expression are redacted in the `send_request`
+    /// span, with the `tracing` feature. Defaults to [`Redaction::all`].
+    pub fn with_redaction(mut self, redaction: Redaction) -> Self {
+        self.redaction = redaction;
+        self
+    }
+
+    /// Sends as many `BatchGetItem` requests as needed.
+    ///
+    /// If sending a request fails, the error includes the items read so far,
+    /// and the keys that weren't read. See [`BatchError`].
+    pub async fn send(
+        self,
+        client: &Client,
+    ) -> Result<BatchGetOutput, BatchError<BatchGetItemError, BatchGetOutput>> {
+        let Self {
+            expression,
+            consistent_read,
+            keys,
+            backoff,
+            #[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+            redaction,
+        } = self;
+
+        let send = |chunk: Vec<(String, Item)>| {
+            let mut request = client.batch_get_item();
+            for (table_name, keys) in group_by_table(chunk) {
+                let keys_and_attributes = expression
+                    .clone()
+                    .to_keys_and_attributes_builder()
+                    .set_keys(Some(keys))
+                    .set_consistent_read(consistent_read)
+                    .build()
+                    .expect("keys are set");
+
+                request = request.request_items(table_name, keys_and_attributes);
+            }
+
+            async move {
+                let output = request.send().await?;
+
+                let items = output
+                    .responses
+                    .into_iter()
+                    .flatten()
+                    .flat_map(|(table_name, items)| {
+                        items
+                            .into_iter()
+                            .map(move |item| (table_name.clone(), item))
+                    })
+                    .collect();
+
+                let unprocessed = output
+                    .unprocessed_keys
+                    .into_iter()
+                    .flatten()
+                    .flat_map(|(table_name, keys)| {
+                        keys.keys
+                            .into_iter()
+                            .map(move |key| (table_name.clone(), key))
+                    })
+                    .collect();
+
+                Ok((items, unprocessed))
+            }
+        };
+
+        let into_output = |items, unprocessed| BatchGetOutput {
+            items: group_by_table(items),
+            unprocessed_keys: group_by_table(unprocessed),
+        };
+
+        #[cfg(feature = "tracing")]
+        let mut sent = trace::Sent::new(trace::batch_span(
+            "BatchGetItem",
+            keys.iter().map(|(table_name, _)| table_name.as_str()),
+            Some((&expression, &redaction)),
+        ));
+
+        let process = process(
+            keys,
+            Limits {
+                max_count: MAX_BATCH_GET_KEYS,
+                max_bytes: None,
+            },
+            send,
+            &*backoff,
+            client.config().sleep_impl(),
+        );
+        #[cfg(feature = "tracing")]
+        let process = tracing::Instrument::instrument(process, sent.span().clone());
+
+        let result = process
+            .await
+            .map(|(items, unprocessed)| into_output(items, unprocessed))
+            .map_err(|(error, items, unprocessed)| BatchError {
+                error,
+                output: into_output(items, unprocessed),
+            });
+
+        #[cfg(feature = "tracing")]
+        {
+            let output = match &result {
+                Ok(output) => output,
+                Err(err) => err.output(),
+            };
+            sent.record(trace::Page {
+                items: output.items.values().map(Vec::len).sum(),
+                ..trace::Page::default()
+            });
+        }
+
+        result
+    }
+}
+
+impl fmt::Debug for BatchGet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BatchGet")
+            .field("expression", &self.expression)
+            .field("consistent_read", &self.consistent_read)
+            .field("keys", &self.keys)
+            .field("redaction", &self.redaction)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The result of a [`BatchGet`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BatchGetOutput {
+    /// The items that were read, by table name.
+    pub items: HashMap<String, Vec<Item>>,
+
+    /// The keys that were not read, by table name. These are left when the
+    /// [`Backoff`] policy gave up retrying, or when a request failed.
+    pub unprocessed_keys: HashMap<String, Vec<Item>>,
+}
+
+/// Puts and deletes any number of items, in any number of tables, with
+/// [`BatchWriteItem`][1].
+///
+/// See the [module documentation](self) for details.
+///
+/// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/APIReference/API_BatchWriteItem.html
+pub struct BatchWrite {
+    writes: Vec<(String, WriteRequest)>,
+    backoff: Box<dyn Backoff + Send + Sync>,
+}
+
+impl BatchWrite {
+    /// Starts a batch write.
+    pub fn new() -> Self {
+        Self {
+            writes: Vec::new(),
+            backoff: Box::new(ExponentialBackoff::default()),
+        }
+    }
+
+    /// Adds an item to put in the table.
+    pub fn with_put<T>(self, table_name: T, item: Item) -> Self
+    where
+        T: Into<String>,
+    {
+        let put = PutRequest::builder()
+            .set_item(Some(item))
+            .build()
+            .expect("item is set");
+
+        self.with_write_request(table_name, WriteRequest::builder().put_request(put).build())
+    }
+
+    /// Adds the key of an item to delete from the table.
+    pub fn with_delete<T>(self, table_name: T, key: Item) -> Self
+    where
+        T: Into<String>,
+    {
+        let delete = DeleteRequest::builder()
+            .set_key(Some(key))
+            .build()
+            .expect("key is set");
+
+        self.with_write_request(
+            table_name,
+            WriteRequest::builder().delete_request(delete).build(),
+        )
+    }
+
+    /// Adds a write request for the table.
+    pub fn with_write_request<T>(mut self, table_name: T, write: WriteRequest) -> Self
+    where
+        T: Into<String>,
+    {
+        self.writes.push((table_name.into(), write));
+        self
+    }
+
+    /// How long to wait before re-submitting unprocessed writes. Defaults to
+    /// [`ExponentialBackoff::default`].
+    pub fn with_backoff<B>(mut self, backoff: B) -> Self
+    where
+        B: Backoff + Send + Sync + 'static,
+    {
+        self.backoff = Box::new(backoff);
+        self
+    }
+
+    /// Sends as many `BatchWriteItem` requests as needed.
+    ///
+    /// If sending a request fails, the error includes the writes that weren't
+    /// made. See [`BatchError`].
+    pub async fn send(
+        self,
+        client: &Client,
+    ) -> Result<BatchWriteOutput, BatchError<BatchWriteItemError, BatchWriteOutput>> {
+        let Self { writes, backoff } = self;
+
+        let send = |chunk: Vec<(String, WriteRequest)>| {
+            let request = client
+                .batch_write_item()
+                .set_request_items(Some(group_by_table(chunk)));
+
+            async move {
+                let output = request.send().await?;
+
+                let unprocessed = output
+                    .unprocessed_items
+                    .into_iter()
+                    .flatten()
+                    .flat_map(|(table_name, writes)| {
+                        writes
+                            .into_iter()
+                            .map(move |write| (table_name.clone(), write))
+                    })
+                    .collect();
+
+                Ok((Vec::<()>::new(), unprocessed))
+            }
+        };
+
+        let into_output = |unprocessed| BatchWriteOutput {
+            unprocessed_items: group_by_table(unprocessed),
+        };
+
+        #[cfg(feature = "tracing")]
+        let (mut sent, total) = (
+            trace::Sent::new(trace::batch_span(
+                "BatchWriteItem",
+                writes.iter().map(|(table_name, _)| table_name.as_str()),
+                None,
+            )),
+            writes.len(),
+        );
+
+        let process = process(
+            writes,
+            Limits {
+                max_count: MAX_BATCH_WRITES,
+                max_bytes: Some((MAX_BATCH_WRITE_BYTES, write_size_bytes)),
+            },
+            send,
+            &*backoff,
+            client.config().sleep_impl(),
+        );
+        #[cfg(feature = "tracing")]
+        let process = tracing::Instrument::instrument(process, sent.span().clone());
+
+        let result = process
+            .await
+            .map(|(_, unprocessed)| into_output(unprocessed))
+            .map_err(|(error, _, unprocessed)| BatchError {
+                error,
+                output: into_output(unprocessed),
+            });
+
+        #[cfg(feature = "tracing")]
+        {
+            let output = match &result {
+                Ok(output) => output,
+                Err(err) => err.output(),
+            };
+            let unprocessed: usize = output.unprocessed_items.values().map(Vec::len).sum();
+            sent.record(trace::Page {
+                items: total - unprocessed,
+                ..trace::Page::default()
+            });
+        }
+
+        result
+    }
+}
+
+impl Default for BatchWrite {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for BatchWrite {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BatchWrite")
+            .field("writes", &self.writes)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The result of a [`BatchWrite`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BatchWriteOutput {
+    /// The writes that were not made, by table name. These are left when the
+    /// [`Backoff`] policy gave up retrying, or when a request failed.
+    pub unprocessed_items: HashMap<String, Vec<WriteRequest>>,
+}
+
+/// A request in a batch operation failed.
+///
+/// Includes the output of the batch operation up to the failure. Anything
+/// that was not processed, including the request that failed, is in the
+/// output's unprocessed keys or items.
+#[derive(Debug)]
+pub struct BatchError<E, O> {
+    error: SdkError<E>,
+    output: O,
+}
+
+impl<E, O> BatchError<E, O> {
+    /// The error from the request that failed.
+    pub fn error(&self) -> &SdkError<E> {
+        &self.error
+    }
+
+    /// The output of the batch operation up to the failure.
+    pub fn output(&self) -> &O {
+        &self.output
+    }
+
+    /// Splits this into the error from the request that failed and the output
+    /// of the batch operation up to the failure.
+    pub fn into_parts(self) -> (SdkError<E>, O) {
+        (self.error, self.output)
+    }
+}
+
+impl<E, O> fmt::Display for BatchError<E, O>
+where
+    E: Error + 'static,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "batch request failed: {}", self.error)
+    }
+}
+
+impl<E, O> Error for BatchError<E, O>
+where
+    E: Error + 'static,
+    O: fmt::Debug,
+{
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// Limits on the size of each request.
+struct Limits<R> {
+    max_count: usize,
+    max_bytes: Option<(usize, SizeBytes<R>)>,
+}
+
+/// Sizes a request, in bytes.
+type SizeBytes<R> = fn(&(String, R)) -> usize;
+
+/// The approximate size of a write in a request, in bytes.
+fn write_size_bytes((table_name, write): &(String, WriteRequest)) -> usize {
+    let item = write
+        .put_request()
+        .map(PutRequest::item)
+        .or_else(|| write.delete_request().map(DeleteRequest::key));
+
+    table_name.len() + item.map(item_size_bytes).unwrap_or_default()
+}
+
+/// The outputs of the requests that succeeded, and the requests that weren't
+/// processed.
+type Processed<O, R> = (Vec<O>, Vec<(String, R)>);
+
+/// Sends requests in chunks, re-submitting any that come back unprocessed.
+///
+/// `send` sends a chunk, returning its outputs and the requests that weren't
+/// processed.
+async fn process<R, O, E, F, Fut>(
+    requests: Vec<(String, R)>,
+    limits: Limits<R>,
+    send: F,
+    backoff: &(dyn Backoff + Send + Sync),
+    sleep: Option<SharedAsyncSleep>,
+) -> Result<Processed<O, R>, (SdkError<E>, Vec<O>, Vec<(String, R)>)>
+where
+    R: Clone,
+    F: Fn(Vec<(String, R)>) -> Fut,
+    Fut: Future<Output = Result<Processed<O, R>, SdkError<E>>>,
+{
+    let mut queue = VecDeque::from(requests);
+    let mut outputs = Vec::new();
+    let mut retry = 0;
+
+    while !queue.is_empty() {
+        let chunk = next_chunk(&mut queue, &limits);
+
+        let unprocessed = match send(chunk.clone()).await {
+            Ok((output, unprocessed)) => {
+                outputs.extend(output);
+                unprocessed
+            }
+            Err(error) => {
+                let unprocessed = chunk.into_iter().chain(queue).collect();
+                return Err((error, outputs, unprocessed));
+            }
+        };
+
+        if unprocessed.is_empty() {
+            retry = 0;
+            continue;
+        }
+
+        retry += 1;
+        match backoff.delay(retry) {
+            Some(delay) => {
+                if let Some(sleep) = &sleep {
+                    sleep.sleep(delay).await;
+                }
+
+                // Re-submit these first.
+                for request in unprocessed.into_iter().rev() {
+                    queue.push_front(request);
+                }
+            }
+            None => {
+                let unprocessed = unprocessed.into_iter().chain(queue).collect();
+                return Ok((outputs, unprocessed));
+            }
+        }
+    }
+
+    Ok((outputs, Vec::new()))
+}
+
+/// Takes the next chunk of requests off the queue, within the limits. Always
+/// takes at least one request.
+fn next_chunk<R>(queue: &mut VecDeque<(String, R)>, limits: &Limits<R>) -> Vec<(String, R)> {
+    let mut len = 0;
+    let mut bytes = 0;
+
+    for request in &*queue {
+        if len == limits.max_count {
+            break;
+        }
+
+        if let Some((max_bytes, size_bytes)) = limits.max_bytes {
+            bytes += size_bytes(request);
+            if len > 0 && bytes > max_bytes {
+                break;
+            }
+        }
+
+        len += 1;
+    }
+
+    queue.drain(..len).collect()
+}
+
+/// Groups requests or results by table name, keeping their order.
+fn group_by_table<T>(items: Vec<(String, T)>) -> HashMap<String, Vec<T>> {
+    let mut tables: HashMap<String, Vec<T>> = HashMap::new();
+    for (table_name, item) in items {
+        tables.entry(table_name).or_default().push(item);
+    }
+
+    tables
+}
+
+#[cfg(test)]
+mod test {
+    use core::{future::ready, time::Duration};
+    use std::{cell::RefCell, collections::VecDeque};
+
+    use aws_sdk_dynamodb::{error::SdkError, operation::batch_get_item::BatchGetItemError};
+    use pretty_assertions::assert_eq;
+
+    use super::{next_chunk, process, Backoff, ExponentialBackoff, Limits};
+
+    fn requests(n: usize) -> Vec<(String, usize)> {
+        (0..n).map(|i| (format!("t{}", i % 2), i)).collect()
+    }
+
+    #[test]
+    fn chunks_by_count() {
+        let limits = Limits {
+            max_count: 25,
+            max_bytes: None,
+        };
+
+        let mut queue = VecDeque::from(requests(60));
+        let sizes: Vec<_> = std::iter::from_fn(|| {
+            (!queue.is_empty()).then(|| next_chunk(&mut queue, &limits).len())
+        })
+        .collect();
+        assert_eq!(vec![25, 25, 10], sizes);
+    }
+
+    #[test]
+    fn chunks_by_bytes() {
+        let limits = Limits {
+            max_count: 25,
+            max_bytes: Some((10, |(_, n): &(String, usize)| *n)),
+        };
+
+        // Sizes: 4, 4, 4 | 11 | 1, 2, 3, 4
+        let mut queue = VecDeque::from(
+            [4, 4, 4, 11, 1, 2, 3, 4]
+                .map(|n| (String::from("t"), n))
+                .to_vec(),
+        );
+        let chunks: Vec<Vec<usize>> = std::iter::from_fn(|| {
+            (!queue.is_empty()).then(|| {
+                next_chunk(&mut queue, &limits)
+                    .into_iter()
+                    .map(|(_, n)| n)
+                    .collect()
+            })
+        })
+        .collect();
+        assert_eq!(
+            vec![vec![4, 4], vec![4], vec![11], vec![1, 2, 3, 4]],
+            chunks
+        );
+    }
+
+    #[test]
+    fn exponential_backoff() {
+        let backoff = ExponentialBackoff::new()
+            .with_initial_delay(Duration::from_millis(100))
+            .with_max_delay(Duration::from_millis(500))
+            .with_max_retries(5);
+
+        assert_eq!(
+            vec![
+                Some(Duration::from_millis(100)),
+                Some(Duration::from_millis(200)),
+                Some(Duration::from_millis(400)),
+                Some(Duration::from_millis(500)),
+                Some(Duration::from_millis(500)),
+                None,
+            ],
+            (1..=6)
+                .map(|retry| backoff.delay(retry))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    /// Runs `process` with a fake `send` that leaves the first `unprocessed`
+    /// requests of each chunk unprocessed, until there are no more to leave.
+    async fn run(
+        n: usize,
+        unprocessed: Vec<usize>,
+        backoff: &(dyn Backoff + Send + Sync),
+    ) -> (Vec<Vec<usize>>, Vec<usize>, Vec<usize>) {
+        let sent = RefCell::new(Vec::new());
+        let unprocessed = RefCell::new(VecDeque::from(unprocessed));
+
+        let (outputs, left) = process::<_, _, BatchGetItemError, _, _>(
+            requests(n),
+            Limits {
+                max_count: 10,
+                max_bytes: None,
+            },
+            |chunk: Vec<(String, usize)>| {
+                sent.borrow_mut()
+                    .push(chunk.iter().map(|(_, n)| *n).collect());
+
+                let skip = unprocessed.borrow_mut().pop_front().unwrap_or(0);
+                let (left, done) = chunk.split_at(skip);
+                ready(Ok((done.iter().map(|(_, n)| *n).collect(), left.to_vec())))
+            },
+            backoff,
+            None,
+        )
+        .await
+        .unwrap();
+
+        (
+            sent.into_inner(),
+            outputs,
+            left.into_iter().map(|(_, n)| n).collect(),
+        )
+    }
+
+    #[tokio::test]
+    async fn retries_unprocessed() {
+        let (sent, outputs, left) = run(25, vec![3, 0, 2], &ExponentialBackoff::new()).await;
+
+        assert_eq!(
+            vec![
+                (0..10).collect::<Vec<_>>(),
+                // The 3 unprocessed are sent again first.
+                vec![0, 1, 2, 10, 11, 12, 13, 14, 15, 16],
+                (17..25).collect(),
+                // Then 17 and 18 are retried.
+                vec![17, 18],
+            ],
+            sent
+        );
+
+        let mut outputs = outputs;
+        outputs.sort();
+        assert_eq!((0..25).collect::<Vec<_>>(), outputs);
+        assert!(left.is_empty());
+    }
+
+    #[tokio::test]
+    async fn gives_up() {
+        // Stop after one retry.
+        let backoff = |retry: u32| (retry <= 1).then_some(Duration::ZERO);
+        let (sent, outputs, left) = run(15, vec![2, 1], &backoff).await;
+
+        assert_eq!(
+            vec![(0..10).collect::<Vec<_>>(), vec![0, 1, 10, 11, 12, 13, 14]],
+            sent
+        );
+        assert_eq!((2..10).chain(1..2).chain(10..15).count(), outputs.len());
+        assert_eq!(vec![0], left);
+    }
+
+    #[tokio::test]
+    async fn failure_returns_unsent() {
+        let sent = RefCell::new(0);
+        let (error, outputs, left) = process::<_, usize, BatchGetItemError, _, _>(
+            requests(25),
+            Limits {
+                max_count: 10,
+                max_bytes: None,
+            },
+            |chunk: Vec<(String, usize)>| {
+                *sent.borrow_mut() += 1;
+                ready(if *sent.borrow() == 2 {
+                    Err(SdkError::timeout_error("timed out"))
+                } else {
+                    Ok((chunk.into_iter().map(|(_, n)| n).collect(), Vec::new()))
+                })
+            },
+            &ExponentialBackoff::new(),
+            None,
+        )
+        .await
+        .unwrap_err();
+
+        assert!(matches!(error, SdkError::TimeoutError(_)));
+        assert_eq!((0..10).collect::<Vec<_>>(), outputs);
+        assert_eq!(
+            (10..25).collect::<Vec<_>>(),
+            left.into_iter().map(|(_, n)| n).collect::<Vec<_>>()
+        );
+    }
+}
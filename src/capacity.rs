@@ -0,0 +1,273 @@
+//! Estimate the [read and write capacity units][1] a request will consume,
+//! before sending it.
+//!
+//! These are estimates based on [item sizes](crate::value::item_size_bytes)
+//! for requests like `put_item`, `update_item`, `delete_item`, and
+//! `transact_write_items`. They only size the items you supply: nothing here
+//! reads an [`Expression`] or applies an update to an item, so for an
+//! `update_item` you provide the item as it is before the update and as it
+//! will be after it. What DynamoDB actually consumes is reported by
+//! `ConsumedCapacity`.
+//!
+//! A write with a condition expression consumes capacity even if the
+//! condition fails, so the estimate is the same either way.
+//!
+//! # Examples
+//!
+//! ```
+//! use dynamodb_expression::{
+//!     capacity::{transact_write_units, ReadConsistency, ReadEstimate, WriteEstimate},
+//!     value::Map,
+//! };
+//! # use pretty_assertions::assert_eq;
+//!
+//! let before = Map::new([("id", "abc123"), ("notes", &"a".repeat(1500))]);
+//! let after = Map::new([("id", "abc123"), ("notes", &"a".repeat(2500))]);
+//!
+//! // An `update_item` is charged for the larger of the item before and after.
+//! let update = WriteEstimate::new().with_before(&before).with_after(&after);
+//! assert_eq!(3, update.units());
+//! assert_eq!(6, update.transactional_units());
+//!
+//! // A `put_item` of a new item.
+//! let put = WriteEstimate::new().with_after(&after);
+//! assert_eq!(3, put.units());
+//!
+//! assert_eq!(12, transact_write_units([update, put]));
+//!
+//! let read = ReadEstimate::new(&after);
+//! assert_eq!(0.5, read.units(ReadConsistency::Eventual));
+//! assert_eq!(1.0, read.units(ReadConsistency::Strong));
+//! assert_eq!(2.0, read.units(ReadConsistency::Transactional));
+//! ```
+//!
+//! [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/CapacityUnitCalculations.html
+//! [`Expression`]: crate::Expression
+
+use std::collections::HashMap;
+
+use aws_sdk_dynamodb::types::AttributeValue;
+
+use crate::value::{item_size_bytes, Map};
+
+/// Each write capacity unit covers up to this many bytes of an item.
+const WRITE_UNIT_BYTES: usize = 1024;
+
+/// Each read capacity unit covers up to this many bytes of an item.
+const READ_UNIT_BYTES: usize = 4 * 1024;
+
+/// The size of an item, in bytes. Used for estimating capacity units.
+///
+/// Create it from a [`Map`], an item from the AWS SDK
+/// (`HashMap<String, AttributeValue>`), or a size in bytes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ItemSize(usize);
+
+impl ItemSize {
+    /// The size of the item, in bytes.
+    pub fn bytes(&self) -> usize {
+        self.0
+    }
+}
+
+impl From<usize> for ItemSize {
+    fn from(bytes: usize) -> Self {
+        Self(bytes)
+    }
+}
+
+impl From<&Map> for ItemSize {
+    fn from(item: &Map) -> Self {
+        Self(item.item_size_bytes())
+    }
+}
+
+impl From<&HashMap<String, AttributeValue>> for ItemSize {
+    fn from(item: &HashMap<String, AttributeValue>) -> Self {
+        Self(item_size_bytes(item))
+    }
+}
+
+/// Estimates the write capacity units for a single write.
+///
+/// A write is charged for the larger of the item before and after it. For a
+/// `put_item` that's the new item or the item it replaces. For an
+/// `update_item` it's the item before or after the update. For a
+/// `delete_item` it's the deleted item. Every write is at least one unit.
+///
+/// See the [module documentation](self) for an example.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct WriteEstimate {
+    before: Option<ItemSize>,
+    after: Option<ItemSize>,
+}
+
+impl WriteEstimate {
+    /// Starts an estimate for a write. Use [`WriteEstimate::with_before`]
+    /// and/or [`WriteEstimate::with_after`] to provide the item.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The item as it is before the write, if there is one.
+    pub fn with_before<T>(mut self, item: T) -> Self
+    where
+        T: Into<ItemSize>,
+    {
+        self.before = Some(item.into());
+        self
+    }
+
+    /// The item as it will be after the write, if there is one.
+    pub fn with_after<T>(mut self, item: T) -> Self
+    where
+        T: Into<ItemSize>,
+    {
+        self.after = Some(item.into());
+        self
+    }
+
+    /// The size of the item the write is charged for.
+    pub fn item_size(&self) -> ItemSize {
+        self.before.max(self.after).unwrap_or_default()
+    }
+
+    /// The write capacity units for a standard (non-transactional) write.
+    /// One unit per 1 KB, rounded up.
+    pub fn units(&self) -> u64 {
+        units(self.item_size(), WRITE_UNIT_BYTES)
+    }
+
+    /// The write capacity units for this write as part of a
+    /// `transact_write_items` request. Twice that of a standard write.
+    pub fn transactional_units(&self) -> u64 {
+        self.units() * 2
+    }
+}
+
+/// The total write capacity units for a `transact_write_items` request made
+/// up of the given writes.
+///
+/// See the [module documentation](self) for an example.
+pub fn transact_write_units<I>(writes: I) -> u64
+where
+    I: IntoIterator<Item = WriteEstimate>,
+{
+    writes
+        .into_iter()
+        .map(|write| write.transactional_units())
+        .sum()
+}
+
+/// How an item is read. Affects the [read capacity units][1] consumed.
+///
+/// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/read-write-operations.html#read-operation-consumption
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ReadConsistency {
+    /// An eventually consistent read. Half the cost of a strongly
+    /// consistent read.
+    Eventual,
+    /// A strongly consistent read (`consistent_read(true)`).
+    Strong,
+    /// A read as part of a `transact_get_items` request. Twice the cost of a
+    /// strongly consistent read.
+    Transactional,
+}
+
+/// Estimates the read capacity units for reading a single item. E.g., with
+/// `get_item`.
+///
+/// See the [module documentation](self) for an example.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ReadEstimate {
+    item: ItemSize,
+}
+
+impl ReadEstimate {
+    /// Starts an estimate for reading the given item.
+    pub fn new<T>(item: T) -> Self
+    where
+        T: Into<ItemSize>,
+    {
+        Self { item: item.into() }
+    }
+
+    /// The read capacity units for the read. One unit per 4 KB, rounded up,
+    /// for a strongly consistent read.
+    pub fn units(&self, consistency: ReadConsistency) -> f64 {
+        let units = units(self.item, READ_UNIT_BYTES) as f64;
+
+        match consistency {
+            ReadConsistency::Eventual => units / 2.0,
+            ReadConsistency::Strong => units,
+            ReadConsistency::Transactional => units * 2.0,
+        }
+    }
+}
+
+/// Capacity units for an item of the given size, at least one.
+fn units(item: ItemSize, unit_bytes: usize) -> u64 {
+    item.0.div_ceil(unit_bytes).max(1) as u64
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use aws_sdk_dynamodb::types::AttributeValue;
+    use pretty_assertions::assert_eq;
+
+    use super::{transact_write_units, ItemSize, ReadConsistency, ReadEstimate, WriteEstimate};
+
+    #[test]
+    fn write_rounding() {
+        for (expected, bytes) in [(1, 0), (1, 1), (1, 1024), (2, 1025), (400, 400 * 1024)] {
+            assert_eq!(
+                expected,
+                WriteEstimate::new().with_after(bytes).units(),
+                "{bytes} bytes"
+            );
+        }
+
+        // Nothing to write is still charged one unit. E.g., deleting an item
+        // that doesn't exist.
+        assert_eq!(1, WriteEstimate::new().units());
+    }
+
+    #[test]
+    fn write_uses_larger_item() {
+        let shrink = WriteEstimate::new().with_before(3000).with_after(500);
+        assert_eq!(ItemSize::from(3000), shrink.item_size());
+        assert_eq!(3, shrink.units());
+
+        let grow = WriteEstimate::new().with_before(500).with_after(3000);
+        assert_eq!(3, grow.units());
+
+        let delete = WriteEstimate::new().with_before(2048);
+        assert_eq!(2, delete.units());
+        assert_eq!(4, delete.transactional_units());
+
+        assert_eq!(
+            2 * (3 + 3 + 2),
+            transact_write_units([shrink, grow, delete])
+        );
+    }
+
+    #[test]
+    fn read_rounding() {
+        let read = ReadEstimate::new(4097);
+        assert_eq!(1.0, read.units(ReadConsistency::Eventual));
+        assert_eq!(2.0, read.units(ReadConsistency::Strong));
+        assert_eq!(4.0, read.units(ReadConsistency::Transactional));
+
+        let read = ReadEstimate::new(0);
+        assert_eq!(0.5, read.units(ReadConsistency::Eventual));
+    }
+
+    #[test]
+    fn sdk_item() {
+        let item = HashMap::from([("id".to_string(), AttributeValue::S("x".repeat(2000)))]);
+        assert_eq!(ItemSize::from(2002), ItemSize::from(&item));
+        assert_eq!(2, WriteEstimate::new().with_after(&item).units());
+    }
+}
@@ -0,0 +1,740 @@
+use core::{cmp::Ordering, fmt};
+use std::{collections::HashMap, error::Error};
+
+use aws_sdk_dynamodb::{
+    error::SdkError,
+    operation::{
+        delete_item::DeleteItemError, put_item::PutItemError, update_item::UpdateItemError,
+    },
+    types::{error::ConditionalCheckFailedException, AttributeValue},
+};
+
+use crate::{
+    operand::{Operand, OperandType},
+    path::Path,
+    value::{attribute_type, Num, Scalar, Set, Value, ValueOrRef},
+};
+
+use super::{
+    AttributeExists, AttributeNotExists, AttributeType, BeginsWith, Between, Comparator,
+    Comparison, Condition, Contains, In,
+};
+
+/// An item, as returned by the AWS SDK.
+type Item = HashMap<String, AttributeValue>;
+
+impl Condition {
+    /// Evaluates this condition against an item, the way DynamoDB would.
+    ///
+    /// Comparisons with an attribute that doesn't exist are false, including
+    /// `<>`. Returns `None` if the result depends on a [`Ref`], since its value
+    /// isn't known here.
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    ///
+    /// use aws_sdk_dynamodb::types::AttributeValue;
+    /// use dynamodb_expression::{Num, Path};
+    ///
+    /// let item = HashMap::from([("version".to_string(), AttributeValue::N("5".into()))]);
+    ///
+    /// let condition = Path::new_name("version").equal(Num::new(5));
+    /// assert_eq!(Some(true), condition.evaluate(&item));
+    ///
+    /// let condition = Path::new_name("version").less_than(Num::new(5));
+    /// assert_eq!(Some(false), condition.evaluate(&item));
+    /// ```
+    ///
+    /// [`Ref`]: crate::value::Ref
+    pub fn evaluate(&self, item: &HashMap<String, AttributeValue>) -> Option<bool> {
+        Evaluator { item }.condition(self)
+    }
+
+    /// Evaluates this condition against an item, like [`.evaluate()`], and
+    /// returns the clauses that made it false.
+    ///
+    /// For an `AND`, that's each side that was false. For an `OR`, that's both
+    /// sides. Each clause comes with the values its attributes actually have in
+    /// the item.
+    ///
+    /// Returns `Ok` if the condition is true, or if it can't be evaluated (see
+    /// [`.evaluate()`]).
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    ///
+    /// use aws_sdk_dynamodb::types::AttributeValue;
+    /// use dynamodb_expression::{Num, Path};
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let item = HashMap::from([
+    ///     ("version".to_string(), AttributeValue::N("5".into())),
+    ///     ("status".to_string(), AttributeValue::S("active".into())),
+    /// ]);
+    ///
+    /// let condition = Path::new_name("version")
+    ///     .equal(Num::new(3))
+    ///     .and(Path::new_name("status").equal("active"))
+    ///     .and(Path::new_name("lock").attribute_exists());
+    ///
+    /// let failure = condition.check(&item).unwrap_err();
+    /// assert_eq!(
+    ///     "condition check failed: `version = 3` is false: version is 5; \
+    ///         `attribute_exists(lock)` is false: lock doesn't exist",
+    ///     failure.to_string(),
+    /// );
+    /// ```
+    ///
+    /// [`.evaluate()`]: Self::evaluate
+    pub fn check(
+        &self,
+        item: &HashMap<String, AttributeValue>,
+    ) -> Result<(), ConditionCheckFailure> {
+        let evaluator = Evaluator { item };
+        if evaluator.condition(self) != Some(false) {
+            return Ok(());
+        }
+
+        let mut clauses = Vec::new();
+        evaluator.false_clauses(self, &mut clauses);
+
+        Err(ConditionCheckFailure { clauses })
+    }
+
+    /// Explains why a request with this condition failed with a
+    /// `ConditionalCheckFailedException`, by [checking] the condition against
+    /// the item DynamoDB returned with the error.
+    ///
+    /// DynamoDB only returns the item if the request sets
+    /// [`ReturnValuesOnConditionCheckFailure`][1] to `ALL_OLD`, and the item
+    /// exists. Otherwise, this returns [`FailureExplanation::NoItem`], since
+    /// there's no way to tell those cases apart.
+    ///
+    /// Returns `None` if the error isn't a `ConditionalCheckFailedException`, or
+    /// if the condition is true for the returned item.
+    ///
+    /// ```no_run
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+    /// use aws_config::BehaviorVersion;
+    /// use aws_sdk_dynamodb::{
+    ///     types::{AttributeValue, ReturnValuesOnConditionCheckFailure},
+    ///     Client,
+    /// };
+    /// use dynamodb_expression::{condition::FailureExplanation, Expression, Num, Path};
+    ///
+    /// let client = Client::new(&aws_config::load_defaults(BehaviorVersion::latest()).await);
+    ///
+    /// let condition = "version".parse::<Path>()?.equal(Num::new(3));
+    ///
+    /// let result = Expression::builder()
+    ///     .with_condition(condition.clone())
+    ///     .with_update("version".parse::<Path>()?.set(Num::new(4)))
+    ///     .build()
+    ///     .update_item(&client)
+    ///     .table_name("people")
+    ///     .key("id", AttributeValue::S("42".into()))
+    ///     .return_values_on_condition_check_failure(ReturnValuesOnConditionCheckFailure::AllOld)
+    ///     .send()
+    ///     .await;
+    ///
+    /// if let Err(err) = &result {
+    ///     match condition.explain_failure(err) {
+    ///         Some(FailureExplanation::Clauses(failure)) => {
+    ///             // E.g., "condition check failed: `version = 3` is false: version is 5"
+    ///             eprintln!("{failure}");
+    ///         }
+    ///         Some(FailureExplanation::NoItem) => eprintln!("item 42 doesn't exist"),
+    ///         None => {}
+    ///     }
+    /// }
+    /// #
+    /// # _ = result?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [checking]: Self::check
+    /// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/APIReference/API_UpdateItem.html#DDB-UpdateItem-request-ReturnValuesOnConditionCheckFailure
+    pub fn explain_failure<E>(&self, error: &E) -> Option<FailureExplanation>
+    where
+        E: ConditionalError,
+    {
+        let exception = error.conditional_check_failed()?;
+
+        match exception.item() {
+            Some(item) => self.check(item).err().map(FailureExplanation::Clauses),
+            None => Some(FailureExplanation::NoItem),
+        }
+    }
+}
+
+/// Why a condition check failed. See [`Condition::explain_failure`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum FailureExplanation {
+    /// The clauses of the condition that were false for the item DynamoDB
+    /// returned.
+    Clauses(ConditionCheckFailure),
+
+    /// DynamoDB didn't return an item. Either the item doesn't exist, or the
+    /// request didn't set `ReturnValuesOnConditionCheckFailure` to `ALL_OLD`.
+    NoItem,
+}
+
+impl fmt::Display for FailureExplanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Clauses(failure) => failure.fmt(f),
+            Self::NoItem => f.write_str("condition check failed: no item was returned"),
+        }
+    }
+}
+
+impl Error for FailureExplanation {}
+
+/// Errors from operations that can fail a condition check. See
+/// [`Condition::explain_failure`].
+pub trait ConditionalError {
+    /// The `ConditionalCheckFailedException`, if that's what this error is.
+    fn conditional_check_failed(&self) -> Option<&ConditionalCheckFailedException>;
+}
+
+impl ConditionalError for ConditionalCheckFailedException {
+    fn conditional_check_failed(&self) -> Option<&ConditionalCheckFailedException> {
+        Some(self)
+    }
+}
+
+impl ConditionalError for PutItemError {
+    fn conditional_check_failed(&self) -> Option<&ConditionalCheckFailedException> {
+        match self {
+            Self::ConditionalCheckFailedException(exception) => Some(exception),
+            _ => None,
+        }
+    }
+}
+
+impl ConditionalError for UpdateItemError {
+    fn conditional_check_failed(&self) -> Option<&ConditionalCheckFailedException> {
+        match self {
+            Self::ConditionalCheckFailedException(exception) => Some(exception),
+            _ => None,
+        }
+    }
+}
+
+impl ConditionalError for DeleteItemError {
+    fn conditional_check_failed(&self) -> Option<&ConditionalCheckFailedException> {
+        match self {
+            Self::ConditionalCheckFailedException(exception) => Some(exception),
+            _ => None,
+        }
+    }
+}
+
+impl<E, R> ConditionalError for SdkError<E, R>
+where
+    E: ConditionalError,
+{
+    fn conditional_check_failed(&self) -> Option<&ConditionalCheckFailedException> {
+        self.as_service_error()?.conditional_check_failed()
+    }
+}
+
+/// The clauses of a [`Condition`] that were false for an item.
+///
+/// See [`Condition::check`] and [`Condition::explain_failure`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConditionCheckFailure {
+    clauses: Vec<FalseClause>,
+}
+
+impl ConditionCheckFailure {
+    /// The clauses that were false, in the order they appear in the condition.
+    pub fn clauses(&self) -> &[FalseClause] {
+        &self.clauses
+    }
+}
+
+impl fmt::Display for ConditionCheckFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("condition check failed: ")?;
+
+        for (i, clause) in self.clauses.iter().enumerate() {
+            if i > 0 {
+                f.write_str("; ")?;
+            }
+
+            clause.fmt(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Error for ConditionCheckFailure {}
+
+/// A clause of a [`Condition`] that was false for an item, with the values its
+/// attributes have in that item.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FalseClause {
+    condition: Condition,
+    actual: Vec<(Path, Option<AttributeValue>)>,
+}
+
+impl FalseClause {
+    /// The clause that was false.
+    pub fn condition(&self) -> &Condition {
+        &self.condition
+    }
+
+    /// Each attribute in the clause, with its value in the item. `None` if the
+    /// attribute doesn't exist.
+    pub fn actual(&self) -> &[(Path, Option<AttributeValue>)] {
+        &self.actual
+    }
+}
+
+impl fmt::Display for FalseClause {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`{}` is false", self.condition)?;
+
+        for (i, (path, value)) in self.actual.iter().enumerate() {
+            f.write_str(if i == 0 { ": " } else { ", " })?;
+
+            match value {
+                Some(value) => match Value::try_from(value.clone()) {
+                    Ok(value) => write!(f, "{path} is {value}")?,
+                    Err(_) => write!(f, "{path} is of an unknown type")?,
+                },
+                None => write!(f, "{path} doesn't exist")?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+struct Evaluator<'a> {
+    item: &'a Item,
+}
+
+impl Evaluator<'_> {
+    fn condition(&self, condition: &Condition) -> Option<bool> {
+        match condition {
+            Condition::And(and) => match (self.condition(&and.left), self.condition(&and.right)) {
+                (Some(false), _) | (_, Some(false)) => Some(false),
+                (Some(true), Some(true)) => Some(true),
+                _ => None,
+            },
+            Condition::Or(or) => match (self.condition(&or.left), self.condition(&or.right)) {
+                (Some(true), _) | (_, Some(true)) => Some(true),
+                (Some(false), Some(false)) => Some(false),
+                _ => None,
+            },
+            Condition::Not(not) => self.condition(&not.condition).map(|result| !result),
+            Condition::Parenthetical(parens) => self.condition(&parens.condition),
+            Condition::AttributeExists(AttributeExists { path }) => {
+                Some(path.get(self.item).is_some())
+            }
+            Condition::AttributeNotExists(AttributeNotExists { path }) => {
+                Some(path.get(self.item).is_none())
+            }
+            Condition::AttributeType(AttributeType {
+                path,
+                attribute_type: expected,
+            }) => Some(
+                path.get(self.item)
+                    .and_then(attribute_type)
+                    .is_some_and(|found| found == *expected),
+            ),
+            Condition::Comparison(Comparison { left, cmp, right }) => {
+                let (Some(left), Some(right)) = (self.operand(left)?, self.operand(right)?) else {
+                    return Some(false);
+                };
+
+                Some(match cmp {
+                    Comparator::Eq => left == right,
+                    Comparator::Ne => left != right,
+                    Comparator::Lt => order(&left, &right) == Some(Ordering::Less),
+                    Comparator::Le => order(&left, &right).is_some_and(Ordering::is_le),
+                    Comparator::Gt => order(&left, &right) == Some(Ordering::Greater),
+                    Comparator::Ge => order(&left, &right).is_some_and(Ordering::is_ge),
+                })
+            }
+            Condition::Between(Between { op, lower, upper }) => {
+                let (Some(op), Some(lower), Some(upper)) = (
+                    self.operand(op)?,
+                    self.operand(lower)?,
+                    self.operand(upper)?,
+                ) else {
+                    return Some(false);
+                };
+
+                Some(
+                    order(&lower, &op).is_some_and(Ordering::is_le)
+                        && order(&op, &upper).is_some_and(Ordering::is_le),
+                )
+            }
+            Condition::In(In { op, items }) => {
+                let Some(op) = self.operand(op)? else {
+                    return Some(false);
+                };
+
+                let mut unknown = false;
+                for item in items {
+                    match self.operand(item) {
+                        Some(Some(item)) if item == op => return Some(true),
+                        None => unknown = true,
+                        _ => {}
+                    }
+                }
+
+                (!unknown).then_some(false)
+            }
+            Condition::BeginsWith(BeginsWith { path, substr }) => {
+                let substr = known(substr)?;
+
+                Some(match (self.value(path), substr) {
+                    (
+                        Some(Value::Scalar(Scalar::String(value))),
+                        Value::Scalar(Scalar::String(substr)),
+                    ) => value.starts_with(substr.as_str()),
+                    (
+                        Some(Value::Scalar(Scalar::Binary(value))),
+                        Value::Scalar(Scalar::Binary(substr)),
+                    ) => value.starts_with(substr),
+                    _ => false,
+                })
+            }
+            Condition::Contains(Contains { path, operand }) => {
+                let operand = known(operand)?;
+
+                Some(match (self.value(path), operand) {
+                    (
+                        Some(Value::Scalar(Scalar::String(value))),
+                        Value::Scalar(Scalar::String(operand)),
+                    ) => value.contains(operand.as_str()),
+                    (
+                        Some(Value::Set(Set::StringSet(set))),
+                        Value::Scalar(Scalar::String(operand)),
+                    ) => set.0.contains(operand),
+                    (Some(Value::Set(Set::NumSet(set))), Value::Scalar(Scalar::Num(operand))) => {
+                        set.0.contains(operand)
+                    }
+                    (
+                        Some(Value::Set(Set::BinarySet(set))),
+                        Value::Scalar(Scalar::Binary(operand)),
+                    ) => set.0.contains(operand),
+                    (Some(Value::List(list)), operand) => list.list.contains(operand),
+                    _ => false,
+                })
+            }
+        }
+    }
+
+    /// The value of an operand. `Some(None)` if it's an attribute that doesn't
+    /// exist, and `None` if the value isn't known.
+    fn operand(&self, operand: &Operand) -> Option<Option<Value>> {
+        match &operand.op {
+            OperandType::Path(path) => Some(self.value(path)),
+            OperandType::Scalar(value) => known(value).cloned().map(Some),
+            OperandType::Size(size) => Some(
+                size.path
+                    .get(self.item)
+                    .and_then(attribute_size)
+                    .map(|size| Num::new(size).into()),
+            ),
+            OperandType::Condition(_) => None,
+        }
+    }
+
+    /// The value of the attribute at the path, if it exists and is a type this
+    /// crate knows about.
+    fn value(&self, path: &Path) -> Option<Value> {
+        path.get(self.item)
+            .and_then(|value| Value::try_from(value.clone()).ok())
+    }
+
+    /// Collects the clauses that make a false condition false.
+    fn false_clauses(&self, condition: &Condition, clauses: &mut Vec<FalseClause>) {
+        match condition {
+            Condition::And(and) => {
+                for side in [&and.left, &and.right] {
+                    if self.condition(side) == Some(false) {
+                        self.false_clauses(side, clauses);
+                    }
+                }
+            }
+            Condition::Or(or) => {
+                self.false_clauses(&or.left, clauses);
+                self.false_clauses(&or.right, clauses);
+            }
+            Condition::Parenthetical(parens) => self.false_clauses(&parens.condition, clauses),
+            condition => {
+                let mut paths = Vec::new();
+                collect_paths(condition, &mut paths);
+
+                clauses.push(FalseClause {
+                    condition: condition.clone(),
+                    actual: paths
+                        .into_iter()
+                        .map(|path| {
+                            let value = path.get(self.item).cloned();
+                            (path, value)
+                        })
+                        .collect(),
+                });
+            }
+        }
+    }
+}
+
+/// The value, if it's not a [`Ref`](crate::value::Ref) to an unknown value.
+fn known(value: &ValueOrRef) -> Option<&Value> {
+    match value {
+        ValueOrRef::Value(value) => Some(value),
+        ValueOrRef::Ref(_) => None,
+    }
+}
+
+/// How DynamoDB orders values for `<`, `<=`, `>`, `>=`, and `BETWEEN`. Only
+/// strings, numbers, and binary values of the same type can be ordered.
+fn order(left: &Value, right: &Value) -> Option<Ordering> {
+    match (left, right) {
+        (Value::Scalar(Scalar::String(left)), Value::Scalar(Scalar::String(right))) => {
+            Some(left.cmp(right))
+        }
+        (Value::Scalar(Scalar::Num(left)), Value::Scalar(Scalar::Num(right))) => {
+            Some(left.cmp(right))
+        }
+        (Value::Scalar(Scalar::Binary(left)), Value::Scalar(Scalar::Binary(right))) => {
+            Some(left.cmp(right))
+        }
+        _ => None,
+    }
+}
+
+/// What the DynamoDB `size` function returns for the value, if it has a size.
+fn attribute_size(value: &AttributeValue) -> Option<usize> {
+    Some(match value {
+        AttributeValue::S(value) => value.chars().count(),
+        AttributeValue::B(value) => value.as_ref().len(),
+        AttributeValue::Ss(set) => set.len(),
+        AttributeValue::Ns(set) => set.len(),
+        AttributeValue::Bs(set) => set.len(),
+        AttributeValue::L(list) => list.len(),
+        AttributeValue::M(map) => map.len(),
+        _ => return None,
+    })
+}
+
+/// The distinct attribute paths used in a condition, in order.
+fn collect_paths(condition: &Condition, paths: &mut Vec<Path>) {
+    fn add(paths: &mut Vec<Path>, path: &Path) {
+        if !paths.contains(path) {
+            paths.push(path.clone());
+        }
+    }
+
+    let operands: Vec<&Operand> = match condition {
+        Condition::And(and) => {
+            collect_paths(&and.left, paths);
+            collect_paths(&and.right, paths);
+            return;
+        }
+        Condition::Or(or) => {
+            collect_paths(&or.left, paths);
+            collect_paths(&or.right, paths);
+            return;
+        }
+        Condition::Not(not) => return collect_paths(&not.condition, paths),
+        Condition::Parenthetical(parens) => return collect_paths(&parens.condition, paths),
+        Condition::AttributeExists(AttributeExists { path })
+        | Condition::AttributeNotExists(AttributeNotExists { path })
+        | Condition::AttributeType(AttributeType { path, .. })
+        | Condition::BeginsWith(BeginsWith { path, .. })
+        | Condition::Contains(Contains { path, .. }) => {
+            add(paths, path);
+            return;
+        }
+        Condition::Comparison(Comparison { left, right, .. }) => vec![left, right],
+        Condition::Between(Between { op, lower, upper }) => vec![op, lower, upper],
+        Condition::In(In { op, items }) => core::iter::once(op).chain(items).collect(),
+    };
+
+    for operand in operands {
+        match &operand.op {
+            OperandType::Path(path) => add(paths, path),
+            OperandType::Size(size) => add(paths, &size.path),
+            OperandType::Condition(condition) => collect_paths(condition, paths),
+            OperandType::Scalar(_) => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use aws_sdk_dynamodb::{
+        error::{ErrorMetadata, SdkError},
+        operation::put_item::PutItemError,
+        types::{error::ConditionalCheckFailedException, AttributeValue},
+    };
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        condition::attribute_type::Type,
+        path::test::path,
+        value::{Ref, StringSet},
+        Num, Value,
+    };
+
+    use super::FailureExplanation;
+
+    fn item() -> HashMap<String, AttributeValue> {
+        HashMap::from([
+            ("version".into(), AttributeValue::N("5".into())),
+            ("name".into(), AttributeValue::S("Jane".into())),
+            (
+                "tags".into(),
+                AttributeValue::Ss(vec!["a".into(), "b".into()]),
+            ),
+            (
+                "address".into(),
+                AttributeValue::M(HashMap::from([(
+                    "lines".into(),
+                    AttributeValue::L(vec![AttributeValue::S("1 Main St".into())]),
+                )])),
+            ),
+        ])
+    }
+
+    #[test]
+    fn evaluate() {
+        let item = item();
+
+        for (condition, expected) in [
+            (path("version").equal(Num::new(5.0)), Some(true)),
+            (path("version").not_equal(Num::new(5)), Some(false)),
+            (path("version").equal("5"), Some(false)),
+            (path("missing").not_equal(Num::new(5)), Some(false)),
+            (path("name").less_than("Joe"), Some(true)),
+            (path("name").greater_than(Num::new(1)), Some(false)),
+            (
+                path("version").between(Num::new(1), Num::new(5)),
+                Some(true),
+            ),
+            (path("version").in_([Num::new(1), Num::new(2)]), Some(false)),
+            (path("name").begins_with("Ja"), Some(true)),
+            (path("tags").contains("b"), Some(true)),
+            (path("name").contains("an"), Some(true)),
+            (path("address.lines").contains("1 Main St"), Some(true)),
+            (path("address.lines[0]").begins_with("1 "), Some(true)),
+            (path("address.lines[1]").attribute_exists(), Some(false)),
+            (path("address.zip").attribute_not_exists(), Some(true)),
+            (path("tags").attribute_type(Type::StringSet), Some(true)),
+            (path("tags").size().equal(Num::new(2)).into(), Some(true)),
+            (
+                path("name").size().greater_than(Num::new(4)).into(),
+                Some(false),
+            ),
+            (!path("version").equal(Num::new(5)), Some(false)),
+            (
+                path("version")
+                    .equal(Num::new(1))
+                    .or(path("tags").equal(Value::from(StringSet::from(["b", "a"])))),
+                Some(true),
+            ),
+            (path("version").equal(Ref::new("v")), None),
+            (
+                path("version")
+                    .equal(Ref::new("v"))
+                    .and(path("name").equal("Joe")),
+                Some(false),
+            ),
+        ] {
+            assert_eq!(expected, condition.evaluate(&item), "{condition}");
+        }
+    }
+
+    #[test]
+    fn check() {
+        let item = item();
+
+        let condition = path("version")
+            .equal(Num::new(3))
+            .and(path("name").begins_with("J"))
+            .and(
+                path("tags")
+                    .contains("c")
+                    .or(!path("address.lines[0]").attribute_exists())
+                    .parenthesize(),
+            )
+            .and(path("tags").size().greater_than(path("version")));
+
+        let failure = condition.check(&item).unwrap_err();
+        assert_eq!(
+            vec![
+                "version = 3",
+                r#"contains(tags, "c")"#,
+                "NOT attribute_exists(address.lines[0])",
+                "size(tags) > version",
+            ],
+            failure
+                .clauses()
+                .iter()
+                .map(|clause| clause.condition().to_string())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            r#"condition check failed: `version = 3` is false: version is 5; `contains(tags, "c")` is false: tags is ["a", "b"]; `NOT attribute_exists(address.lines[0])` is false: address.lines[0] is "1 Main St"; `size(tags) > version` is false: tags is ["a", "b"], version is 5"#,
+            failure.to_string()
+        );
+
+        assert_eq!(Ok(()), path("version").equal(Num::new(5)).check(&item));
+    }
+
+    #[test]
+    fn explain_failure() {
+        let condition = path("version").equal(Num::new(3));
+
+        let exception = ConditionalCheckFailedException::builder()
+            .set_item(Some(item()))
+            .build();
+        let err: SdkError<PutItemError, ()> =
+            SdkError::service_error(PutItemError::ConditionalCheckFailedException(exception), ());
+        assert_eq!(
+            "condition check failed: `version = 3` is false: version is 5",
+            condition.explain_failure(&err).unwrap().to_string()
+        );
+
+        let err: SdkError<PutItemError, ()> = SdkError::service_error(
+            PutItemError::ConditionalCheckFailedException(
+                ConditionalCheckFailedException::builder().build(),
+            ),
+            (),
+        );
+        assert_eq!(
+            Some(FailureExplanation::NoItem),
+            condition.explain_failure(&err)
+        );
+
+        let exception = ConditionalCheckFailedException::builder()
+            .set_item(Some(item()))
+            .build();
+        let err: SdkError<PutItemError, ()> =
+            SdkError::service_error(PutItemError::ConditionalCheckFailedException(exception), ());
+        assert_eq!(
+            None,
+            path("version").equal(Num::new(5)).explain_failure(&err),
+            "The condition is true for the returned item"
+        );
+
+        let err: SdkError<PutItemError, ()> = SdkError::service_error(
+            PutItemError::generic(ErrorMetadata::builder().code("Other").build()),
+            (),
+        );
+        assert_eq!(None, condition.explain_failure(&err));
+    }
+}
@@ -10,10 +10,12 @@ mod begins_with;
 mod between;
 mod comparison;
 mod contains;
+mod evaluate;
 mod in_;
 mod not;
 mod or;
 mod parenthetical;
+mod type_check;
 
 pub use and::And;
 pub use attribute_exists::AttributeExists;
@@ -26,10 +28,12 @@ pub use comparison::{
     Comparator, Comparison,
 };
 pub use contains::Contains;
+pub use evaluate::{ConditionCheckFailure, ConditionalError, FailureExplanation, FalseClause};
 pub use in_::In;
 pub use not::Not;
 pub use or::Or;
 pub use parenthetical::Parenthetical;
+pub use type_check::{AttributeTypes, TypeError};
 
 use core::{fmt, ops};
 
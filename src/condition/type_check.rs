@@ -0,0 +1,464 @@
+use core::fmt;
+use std::{collections::HashMap, error::Error};
+
+use crate::{
+    operand::{Operand, OperandType},
+    path::{Name, Path},
+    value::{Scalar, Set, Value, ValueOrRef},
+};
+
+use super::{attribute_type::Type, BeginsWith, Between, Comparison, Condition, Contains, In};
+
+/// The declared types of attributes, for checking a [`Condition`] with
+/// [`Condition::type_check`].
+///
+/// Attributes that aren't declared here aren't checked.
+///
+/// ```
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use dynamodb_expression::{
+///     condition::{attribute_type::Type, AttributeTypes},
+///     Path,
+/// };
+///
+/// let attribute_types: AttributeTypes = [
+///     ("id", Type::String),
+///     ("age", Type::Number),
+///     ("tags", Type::StringSet),
+/// ]
+/// .into_iter()
+/// .collect();
+///
+/// let attribute_types = AttributeTypes::new()
+///     .with_attribute("id", Type::String)
+///     .with_path("address.zip".parse::<Path>()?, Type::String);
+/// #
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct AttributeTypes {
+    types: HashMap<Path, Type>,
+}
+
+impl AttributeTypes {
+    /// No attribute types declared yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Declare the type of a top-level attribute.
+    pub fn with_attribute<T>(self, name: T, attribute_type: Type) -> Self
+    where
+        T: Into<Name>,
+    {
+        self.with_path(Path::from(name.into()), attribute_type)
+    }
+
+    /// Declare the type of an attribute at any path, such as a nested
+    /// attribute in a map.
+    pub fn with_path(mut self, path: Path, attribute_type: Type) -> Self {
+        self.types.insert(path, attribute_type);
+
+        self
+    }
+
+    /// The declared type of the attribute at the path, if there is one.
+    pub fn get(&self, path: &Path) -> Option<Type> {
+        self.types.get(path).copied()
+    }
+}
+
+impl<T> FromIterator<(T, Type)> for AttributeTypes
+where
+    T: Into<Name>,
+{
+    /// Declares the types of top-level attributes.
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = (T, Type)>,
+    {
+        iter.into_iter()
+            .fold(Self::new(), |attribute_types, (name, attribute_type)| {
+                attribute_types.with_attribute(name, attribute_type)
+            })
+    }
+}
+
+/// A type mistake found by [`Condition::type_check`].
+///
+/// DynamoDB doesn't report these as errors. The condition just never matches,
+/// so a query or scan quietly returns no items.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TypeError {
+    /// Two operands that can never be equal because their types differ. For
+    /// example, a number attribute compared with a string.
+    Mismatch {
+        operand: Operand,
+        expected: Type,
+        found: Type,
+    },
+
+    /// `begins_with` on an attribute that isn't a string or binary.
+    BeginsWith { path: Path, attribute_type: Type },
+
+    /// `contains` on an attribute that isn't a string, set, or list.
+    Contains { path: Path, attribute_type: Type },
+
+    /// `size()` of an attribute that doesn't have a size, like a number.
+    Size { path: Path, attribute_type: Type },
+}
+
+impl fmt::Display for TypeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Mismatch {
+                operand,
+                expected,
+                found,
+            } => write!(f, "{operand} has type {expected}, but is used with {found}"),
+            Self::BeginsWith {
+                path,
+                attribute_type,
+            } => write!(
+                f,
+                "begins_with used on {path}, which has type {attribute_type}"
+            ),
+            Self::Contains {
+                path,
+                attribute_type,
+            } => write!(
+                f,
+                "contains used on {path}, which has type {attribute_type}"
+            ),
+            Self::Size {
+                path,
+                attribute_type,
+            } => write!(f, "size used on {path}, which has type {attribute_type}"),
+        }
+    }
+}
+
+impl Error for TypeError {}
+
+impl Condition {
+    /// Checks the types used in this condition against the declared
+    /// [`AttributeTypes`], returning every mistake found.
+    ///
+    /// DynamoDB doesn't reject conditions that compare values of different
+    /// types. They just never match. This catches those mistakes before the
+    /// request is sent.
+    ///
+    /// ```
+    /// use dynamodb_expression::{
+    ///     condition::{attribute_type::Type, AttributeTypes, TypeError},
+    ///     Num, Path,
+    /// };
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let attribute_types: AttributeTypes = [("age", Type::Number), ("name", Type::String)]
+    ///     .into_iter()
+    ///     .collect();
+    ///
+    /// let condition = Path::new_name("age").greater_than(Num::new(21));
+    /// assert_eq!(Ok(()), condition.type_check(&attribute_types));
+    ///
+    /// let condition = Path::new_name("age")
+    ///     .greater_than("21")
+    ///     .and(Path::new_name("age").begins_with("2"));
+    /// assert_eq!(
+    ///     Err(vec![
+    ///         TypeError::Mismatch {
+    ///             operand: Path::new_name("age").into(),
+    ///             expected: Type::Number,
+    ///             found: Type::String,
+    ///         },
+    ///         TypeError::BeginsWith {
+    ///             path: Path::new_name("age"),
+    ///             attribute_type: Type::Number,
+    ///         },
+    ///     ]),
+    ///     condition.type_check(&attribute_types),
+    /// );
+    /// ```
+    pub fn type_check(&self, attribute_types: &AttributeTypes) -> Result<(), Vec<TypeError>> {
+        let mut errors = Vec::new();
+        TypeChecker {
+            attribute_types,
+            errors: &mut errors,
+        }
+        .condition(self);
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+struct TypeChecker<'a> {
+    attribute_types: &'a AttributeTypes,
+    errors: &'a mut Vec<TypeError>,
+}
+
+impl TypeChecker<'_> {
+    fn condition(&mut self, condition: &Condition) {
+        match condition {
+            Condition::And(and) => {
+                self.condition(&and.left);
+                self.condition(&and.right);
+            }
+            Condition::Or(or) => {
+                self.condition(&or.left);
+                self.condition(&or.right);
+            }
+            Condition::Not(not) => self.condition(&not.condition),
+            Condition::Parenthetical(parens) => self.condition(&parens.condition),
+            Condition::Comparison(Comparison { left, right, .. }) => {
+                let left = self.typed(left);
+                let right = self.typed(right);
+                self.compare(left, right);
+            }
+            Condition::Between(Between { op, lower, upper }) => {
+                let op = self.typed(op);
+                let lower = self.typed(lower);
+                let upper = self.typed(upper);
+                self.compare(op, lower);
+                self.compare(op, upper);
+                self.compare(lower, upper);
+            }
+            Condition::In(In { op, items }) => {
+                let op = self.typed(op);
+                for item in items {
+                    let item = self.typed(item);
+                    self.compare(op, item);
+                }
+            }
+            Condition::BeginsWith(BeginsWith { path, substr }) => {
+                if let Some(attribute_type) = self.attribute_types.get(path) {
+                    if !matches!(attribute_type, Type::String | Type::Binary) {
+                        self.errors.push(TypeError::BeginsWith {
+                            path: path.clone(),
+                            attribute_type,
+                        });
+                    } else if let Some(found) = value_or_ref_type(substr) {
+                        if found != attribute_type {
+                            self.errors.push(TypeError::Mismatch {
+                                operand: path.clone().into(),
+                                expected: attribute_type,
+                                found,
+                            });
+                        }
+                    }
+                }
+            }
+            Condition::Contains(Contains { path, operand }) => {
+                if let Some(attribute_type) = self.attribute_types.get(path) {
+                    let element_type = match attribute_type {
+                        Type::String | Type::StringSet => Some(Type::String),
+                        Type::NumberSet => Some(Type::Number),
+                        Type::BinarySet => Some(Type::Binary),
+                        // A list can contain anything.
+                        Type::List => None,
+                        _ => {
+                            self.errors.push(TypeError::Contains {
+                                path: path.clone(),
+                                attribute_type,
+                            });
+
+                            return;
+                        }
+                    };
+
+                    if let (Some(expected), Some(found)) =
+                        (element_type, value_or_ref_type(operand))
+                    {
+                        if expected != found {
+                            self.errors.push(TypeError::Mismatch {
+                                operand: path.clone().into(),
+                                expected: attribute_type,
+                                found,
+                            });
+                        }
+                    }
+                }
+            }
+            Condition::AttributeExists(_)
+            | Condition::AttributeNotExists(_)
+            | Condition::AttributeType(_) => {}
+        }
+    }
+
+    /// Checks two operands that are compared with each other.
+    fn compare(
+        &mut self,
+        (left, left_type): (&Operand, Option<Type>),
+        (right, right_type): (&Operand, Option<Type>),
+    ) {
+        let (Some(left_type), Some(right_type)) = (left_type, right_type) else {
+            return;
+        };
+
+        if left_type == right_type {
+            return;
+        }
+
+        // Report the mismatch against the attribute, if there is one.
+        self.errors
+            .push(if !is_attribute(left) && is_attribute(right) {
+                TypeError::Mismatch {
+                    operand: right.clone(),
+                    expected: right_type,
+                    found: left_type,
+                }
+            } else {
+                TypeError::Mismatch {
+                    operand: left.clone(),
+                    expected: left_type,
+                    found: right_type,
+                }
+            });
+    }
+
+    /// Pairs an operand with its type, if it's known.
+    fn typed<'a>(&mut self, operand: &'a Operand) -> (&'a Operand, Option<Type>) {
+        (operand, self.operand_type(operand))
+    }
+
+    /// The type of an operand, if it's known.
+    fn operand_type(&mut self, operand: &Operand) -> Option<Type> {
+        match &operand.op {
+            OperandType::Path(path) => self.attribute_types.get(path),
+            OperandType::Scalar(value) => value_or_ref_type(value),
+            OperandType::Size(size) => {
+                if let Some(attribute_type) = self.attribute_types.get(&size.path) {
+                    if matches!(attribute_type, Type::Number | Type::Boolean | Type::Null) {
+                        self.errors.push(TypeError::Size {
+                            path: size.path.clone(),
+                            attribute_type,
+                        });
+                    }
+                }
+
+                Some(Type::Number)
+            }
+            OperandType::Condition(condition) => {
+                self.condition(condition);
+
+                None
+            }
+        }
+    }
+}
+
+fn is_attribute(operand: &Operand) -> bool {
+    matches!(operand.op, OperandType::Path(_) | OperandType::Size(_))
+}
+
+fn value_or_ref_type(value: &ValueOrRef) -> Option<Type> {
+    match value {
+        ValueOrRef::Value(value) => Some(value_type(value)),
+        // The value isn't known until the expression is built.
+        ValueOrRef::Ref(_) => None,
+    }
+}
+
+fn value_type(value: &Value) -> Type {
+    match value {
+        Value::Scalar(Scalar::String(_)) => Type::String,
+        Value::Scalar(Scalar::Num(_)) => Type::Number,
+        Value::Scalar(Scalar::Bool(_)) => Type::Boolean,
+        Value::Scalar(Scalar::Binary(_)) => Type::Binary,
+        Value::Scalar(Scalar::Null) => Type::Null,
+        Value::Set(Set::StringSet(_)) => Type::StringSet,
+        Value::Set(Set::NumSet(_)) => Type::NumberSet,
+        Value::Set(Set::BinarySet(_)) => Type::BinarySet,
+        Value::Map(_) => Type::Map,
+        Value::List(_) => Type::List,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use crate::{path::test::path, value::Ref, Num};
+
+    use super::{AttributeTypes, Type, TypeError};
+
+    fn attribute_types() -> AttributeTypes {
+        [
+            ("name", Type::String),
+            ("age", Type::Number),
+            ("tags", Type::StringSet),
+        ]
+        .into_iter()
+        .collect::<AttributeTypes>()
+        .with_path(path("address.zip"), Type::String)
+    }
+
+    #[test]
+    fn valid() {
+        let condition = path("name")
+            .begins_with("A")
+            .and(path("age").between(Num::new(18), Num::new(65)))
+            .and(path("tags").contains("red"))
+            .and(path("name").size().greater_than(Num::new(3)))
+            .and(path("address.zip").in_(["12345", "54321"]))
+            .and(path("age").equal(Ref::new("age")))
+            .and(path("unknown").equal(true));
+
+        assert_eq!(Ok(()), condition.type_check(&attribute_types()));
+    }
+
+    #[test]
+    fn mistakes() {
+        let condition = path("age")
+            .equal("42")
+            .or(path("age").between(Num::new(18), "65").not())
+            .and(path("tags").contains(Num::new(1)))
+            .and(path("age").contains(Num::new(1)))
+            .and(path("age").size().greater_than(Num::new(3)))
+            .and(path("address.zip").equal(path("age")));
+
+        assert_eq!(
+            Err(vec![
+                TypeError::Mismatch {
+                    operand: path("age").into(),
+                    expected: Type::Number,
+                    found: Type::String,
+                },
+                TypeError::Mismatch {
+                    operand: path("age").into(),
+                    expected: Type::Number,
+                    found: Type::String,
+                },
+                TypeError::Mismatch {
+                    operand: Num::new(18).into(),
+                    expected: Type::Number,
+                    found: Type::String,
+                },
+                TypeError::Mismatch {
+                    operand: path("tags").into(),
+                    expected: Type::StringSet,
+                    found: Type::Number,
+                },
+                TypeError::Contains {
+                    path: path("age"),
+                    attribute_type: Type::Number,
+                },
+                TypeError::Size {
+                    path: path("age"),
+                    attribute_type: Type::Number,
+                },
+                TypeError::Mismatch {
+                    operand: path("address.zip").into(),
+                    expected: Type::String,
+                    found: Type::Number,
+                },
+            ]),
+            condition.type_check(&attribute_types())
+        );
+    }
+}
@@ -0,0 +1,470 @@
+//! Opaque pagination cursors.
+//!
+//! A [`Cursor`] turns a `LastEvaluatedKey` into a compact, URL-safe token to
+//! hand to a client, and turns that token back into an `ExclusiveStartKey`
+//! when the client asks for the next page.
+//!
+//! The token includes a fingerprint of the [`Expression`] (key condition,
+//! filter, and the names and values they use) and, optionally, the table and
+//! index names. Decoding fails if the token is used with a different query,
+//! or if it was modified.
+//!
+//! Without an HMAC key, the token has a checksum, which catches accidental
+//! changes but not a client that re-computes it. Use
+//! [`Cursor::with_hmac_key`] with a secret key to make tokens tamper-evident.
+//!
+//! Requires the `cursor` feature.
+//!
+//! # Examples
+//!
+//! ```
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! use std::collections::HashMap;
+//!
+//! use aws_sdk_dynamodb::types::AttributeValue;
+//! use dynamodb_expression::{cursor::Cursor, Expression, Num, Path};
+//! # use pretty_assertions::assert_eq;
+//!
+//! let expression = Expression::builder()
+//!     .with_key_condition("id".parse::<Path>()?.key().equal(Num::new(42)))
+//!     .build();
+//!
+//! let cursor = Cursor::new(&expression)
+//!     .with_table_name("people")
+//!     .with_hmac_key(b"a secret key");
+//!
+//! // From the `last_evaluated_key` of a query response.
+//! let last_evaluated_key = HashMap::from([
+//!     ("id".to_string(), AttributeValue::N("42".into())),
+//!     ("sk".to_string(), AttributeValue::S("2024-01-01".into())),
+//! ]);
+//!
+//! let token = cursor.encode(&last_evaluated_key)?;
+//!
+//! // Later, when the client asks for the next page...
+//! let exclusive_start_key = cursor.decode(&token)?;
+//! assert_eq!(last_evaluated_key, exclusive_start_key);
+//!
+//! // A token from one query can't be used with another.
+//! let other = Expression::builder()
+//!     .with_key_condition("id".parse::<Path>()?.key().equal(Num::new(7)))
+//!     .build();
+//! assert!(Cursor::new(&other)
+//!     .with_table_name("people")
+//!     .with_hmac_key(b"a secret key")
+//!     .decode(&token)
+//!     .is_err());
+//! #
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`Expression`]: crate::Expression
+
+use core::fmt;
+use std::{collections::HashMap, error::Error};
+
+use aws_sdk_dynamodb::types::AttributeValue;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use hmac::{Hmac, KeyInit, Mac};
+use sha2::{Digest, Sha256};
+
+use crate::{Expression, Value};
+
+/// The version of the token format.
+const VERSION: u8 = 1;
+
+/// Bytes of the expression fingerprint in the token.
+const FINGERPRINT_LEN: usize = 16;
+
+/// Bytes of the checksum or HMAC in the token.
+const TAG_LEN: usize = 16;
+
+/// Encodes and decodes pagination tokens for a particular query or scan.
+///
+/// See the [module documentation](self) for details and an example.
+#[derive(Clone)]
+pub struct Cursor {
+    /// The fingerprint of the expression.
+    expression: Sha256,
+    table_name: Option<String>,
+    index_name: Option<String>,
+    hmac_key: Option<Vec<u8>>,
+}
+
+impl Cursor {
+    /// Creates a cursor for the query or scan using this [`Expression`].
+    ///
+    /// The key condition expression, filter expression, and the expression
+    /// attribute names and values are part of the fingerprint.
+    pub fn new(expression: &Expression) -> Self {
+        let mut fingerprint = Sha256::new();
+
+        update_part(
+            &mut fingerprint,
+            b"key_condition",
+            expression.key_condition_expression.as_deref(),
+        );
+        update_part(
+            &mut fingerprint,
+            b"filter",
+            expression.filter_expression.as_deref(),
+        );
+
+        let mut names: Vec<_> = expression
+            .expression_attribute_names
+            .iter()
+            .flatten()
+            .collect();
+        names.sort();
+        for (placeholder, name) in names {
+            update_part(&mut fingerprint, b"name", Some(placeholder));
+            update_part(&mut fingerprint, b"", Some(name));
+        }
+
+        let mut values: Vec<_> = expression
+            .expression_attribute_values
+            .iter()
+            .flatten()
+            .collect();
+        values.sort_by_key(|(placeholder, _)| *placeholder);
+        for (placeholder, value) in values {
+            update_part(&mut fingerprint, b"value", Some(placeholder));
+            update_part(&mut fingerprint, b"", Some(&attribute_value_text(value)));
+        }
+
+        Self {
+            expression: fingerprint,
+            table_name: None,
+            index_name: None,
+            hmac_key: None,
+        }
+    }
+
+    /// Adds the table name to the fingerprint.
+    pub fn with_table_name<T>(mut self, table_name: T) -> Self
+    where
+        T: AsRef<str>,
+    {
+        self.table_name = Some(table_name.as_ref().into());
+        self
+    }
+
+    /// Adds the index name to the fingerprint, for a query or scan on a
+    /// secondary index.
+    pub fn with_index_name<T>(mut self, index_name: T) -> Self
+    where
+        T: AsRef<str>,
+    {
+        self.index_name = Some(index_name.as_ref().into());
+        self
+    }
+
+    /// Signs tokens with HMAC-SHA256 using this secret key, so a client can't
+    /// create or modify a token without it.
+    pub fn with_hmac_key<T>(mut self, key: T) -> Self
+    where
+        T: AsRef<[u8]>,
+    {
+        self.hmac_key = Some(key.as_ref().to_vec());
+        self
+    }
+
+    /// Encodes the `LastEvaluatedKey` from a query or scan as a token.
+    ///
+    /// The token is URL-safe base64 (without padding). Fails if an attribute
+    /// in the key is of a type this crate doesn't know about.
+    pub fn encode(
+        &self,
+        last_evaluated_key: &HashMap<String, AttributeValue>,
+    ) -> Result<String, CursorError> {
+        let key = last_evaluated_key
+            .iter()
+            .map(|(name, value)| {
+                Value::try_from(value.clone())
+                    .map(|value| (name.clone(), value.into_dynamodb_json()))
+                    .map_err(|_| CursorError::UnknownType)
+            })
+            .collect::<Result<serde_json::Map<_, _>, _>>()?;
+
+        let mut token = vec![VERSION];
+        token.extend_from_slice(&self.fingerprint());
+        token.extend(serde_json::to_vec(&key).expect("JSON values always serialize"));
+
+        let tag = self.tag(&token);
+        token.extend_from_slice(&tag);
+
+        Ok(URL_SAFE_NO_PAD.encode(token))
+    }
+
+    /// Decodes a token created by [`Cursor::encode`] into a key to use as the
+    /// `ExclusiveStartKey` of the next query or scan.
+    ///
+    /// Fails if the token is malformed, was modified, or was created for a
+    /// different query or scan.
+    pub fn decode(&self, token: &str) -> Result<HashMap<String, AttributeValue>, CursorError> {
+        let token = URL_SAFE_NO_PAD
+            .decode(token)
+            .map_err(|_| CursorError::Malformed)?;
+
+        if token.len() < 1 + FINGERPRINT_LEN + TAG_LEN {
+            return Err(CursorError::Malformed);
+        }
+
+        if token[0] != VERSION {
+            return Err(CursorError::UnsupportedVersion(token[0]));
+        }
+
+        let (data, tag) = token.split_at(token.len() - TAG_LEN);
+        if !self.verify(data, tag) {
+            return Err(CursorError::Modified);
+        }
+
+        let (fingerprint, key) = data[1..].split_at(FINGERPRINT_LEN);
+        if fingerprint != self.fingerprint() {
+            return Err(CursorError::ExpressionMismatch);
+        }
+
+        let key: serde_json::Map<String, serde_json::Value> =
+            serde_json::from_slice(key).map_err(|_| CursorError::Malformed)?;
+
+        key.into_iter()
+            .map(|(name, json)| {
+                Value::from_dynamodb_json(json)
+                    .map(|value| (name, value.into_attribute_value()))
+                    .map_err(|_| CursorError::Malformed)
+            })
+            .collect()
+    }
+
+    fn fingerprint(&self) -> [u8; FINGERPRINT_LEN] {
+        let mut hash = self.expression.clone();
+        update_part(&mut hash, b"table", self.table_name.as_deref());
+        update_part(&mut hash, b"index", self.index_name.as_deref());
+        let hash = hash.finalize();
+
+        let mut fingerprint = [0; FINGERPRINT_LEN];
+        fingerprint.copy_from_slice(&hash[..FINGERPRINT_LEN]);
+        fingerprint
+    }
+
+    fn tag(&self, data: &[u8]) -> [u8; TAG_LEN] {
+        let mut tag = [0; TAG_LEN];
+        match &self.hmac_key {
+            Some(key) => {
+                tag.copy_from_slice(&self.mac(key, data).finalize().into_bytes()[..TAG_LEN])
+            }
+            None => tag.copy_from_slice(&Sha256::digest(data)[..TAG_LEN]),
+        }
+
+        tag
+    }
+
+    fn verify(&self, data: &[u8], tag: &[u8]) -> bool {
+        match &self.hmac_key {
+            Some(key) => self.mac(key, data).verify_truncated_left(tag).is_ok(),
+            None => self.tag(data) == tag,
+        }
+    }
+
+    fn mac(&self, key: &[u8], data: &[u8]) -> Hmac<Sha256> {
+        let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+        mac.update(data);
+        mac
+    }
+}
+
+impl fmt::Debug for Cursor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Don't show the key.
+        f.debug_struct("Cursor")
+            .field("table_name", &self.table_name)
+            .field("index_name", &self.index_name)
+            .field("fingerprint", &self.fingerprint())
+            .field("hmac_key", &self.hmac_key.as_ref().map(|_| ".."))
+            .finish()
+    }
+}
+
+/// Adds a length-prefixed part to the fingerprint, so that parts can't run
+/// together (e.g., `"ab" + "c"` vs. `"a" + "bc"`).
+fn update_part(fingerprint: &mut Sha256, label: &[u8], part: Option<&str>) {
+    fingerprint.update(label);
+    match part {
+        Some(part) => {
+            fingerprint.update([1]);
+            fingerprint.update((part.len() as u64).to_be_bytes());
+            fingerprint.update(part.as_bytes());
+        }
+        None => fingerprint.update([0]),
+    }
+}
+
+/// A stable text form of an `AttributeValue`, for the fingerprint.
+fn attribute_value_text(value: &AttributeValue) -> String {
+    match Value::try_from(value.clone()) {
+        Ok(value) => value.into_dynamodb_json().to_string(),
+        Err(_) => format!("{value:?}"),
+    }
+}
+
+/// An error encoding or decoding a pagination token. See [`Cursor::encode`]
+/// and [`Cursor::decode`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CursorError {
+    /// The token isn't one created by [`Cursor::encode`].
+    Malformed,
+    /// The token was created by a different version of this crate.
+    UnsupportedVersion(u8),
+    /// The token was modified after it was created, or was signed with a
+    /// different HMAC key.
+    Modified,
+    /// The token was created for a different query or scan.
+    ExpressionMismatch,
+    /// An attribute in the key is of a type this crate doesn't know about, so
+    /// it can't be encoded.
+    UnknownType,
+}
+
+impl fmt::Display for CursorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Malformed => f.write_str("malformed pagination token"),
+            Self::UnsupportedVersion(version) => {
+                write!(f, "unsupported pagination token version: {version}")
+            }
+            Self::Modified => f.write_str("pagination token was modified"),
+            Self::ExpressionMismatch => {
+                f.write_str("pagination token is for a different query or scan")
+            }
+            Self::UnknownType => {
+                f.write_str("can't encode an attribute of an unknown type in a pagination token")
+            }
+        }
+    }
+}
+
+impl Error for CursorError {}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use aws_sdk_dynamodb::{primitives::Blob, types::AttributeValue};
+    use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+    use pretty_assertions::assert_eq;
+
+    use super::{Cursor, CursorError};
+    use crate::{Expression, Num, Path};
+
+    fn expression(id: i32) -> Expression {
+        Expression::builder()
+            .with_key_condition("id".parse::<Path>().unwrap().key().equal(Num::new(id)))
+            .with_filter("age".parse::<Path>().unwrap().greater_than(Num::new(21)))
+            .build()
+    }
+
+    fn key() -> HashMap<String, AttributeValue> {
+        HashMap::from([
+            ("id".to_string(), AttributeValue::N("42".into())),
+            ("sk".to_string(), AttributeValue::S("a/b+c".into())),
+            ("bin".to_string(), AttributeValue::B(Blob::new([0, 255]))),
+        ])
+    }
+
+    #[test]
+    fn round_trip() {
+        for cursor in [
+            Cursor::new(&expression(42)),
+            Cursor::new(&expression(42)).with_hmac_key("secret"),
+            Cursor::new(&expression(42))
+                .with_table_name("t")
+                .with_index_name("i"),
+        ] {
+            let token = cursor.encode(&key()).unwrap();
+            assert!(!token.contains(['+', '/', '=']), "{token}");
+            assert_eq!(Ok(key()), cursor.decode(&token));
+
+            // Deterministic, and the same for an equal expression.
+            assert_eq!(Ok(token), cursor.clone().encode(&key()));
+        }
+    }
+
+    #[test]
+    fn expression_mismatch() {
+        let token = Cursor::new(&expression(42)).encode(&key()).unwrap();
+
+        // Same key condition text, but a different value for the placeholder.
+        assert_eq!(
+            Err(CursorError::ExpressionMismatch),
+            Cursor::new(&expression(7)).decode(&token)
+        );
+        assert_eq!(
+            Err(CursorError::ExpressionMismatch),
+            Cursor::new(&expression(42))
+                .with_index_name("gsi")
+                .decode(&token)
+        );
+        assert_eq!(
+            Err(CursorError::ExpressionMismatch),
+            Cursor::new(&expression(42))
+                .with_table_name("gsi")
+                .decode(&token)
+        );
+
+        // Table and index names can't be swapped.
+        let token = Cursor::new(&expression(42))
+            .with_table_name("a")
+            .encode(&key())
+            .unwrap();
+        assert_eq!(
+            Err(CursorError::ExpressionMismatch),
+            Cursor::new(&expression(42))
+                .with_index_name("a")
+                .decode(&token)
+        );
+    }
+
+    #[test]
+    fn modified() {
+        let cursor = Cursor::new(&expression(42)).with_hmac_key("secret");
+        let token = cursor.encode(&key()).unwrap();
+
+        let mut bytes = URL_SAFE_NO_PAD.decode(&token).unwrap();
+        let i = bytes.len() / 2;
+        bytes[i] ^= 1;
+        let modified = URL_SAFE_NO_PAD.encode(&bytes);
+        assert_eq!(Err(CursorError::Modified), cursor.decode(&modified));
+
+        // Signed with a different key.
+        assert_eq!(
+            Err(CursorError::Modified),
+            Cursor::new(&expression(42))
+                .with_hmac_key("other")
+                .decode(&token)
+        );
+
+        // An unsigned token isn't accepted when a key is expected.
+        assert_eq!(
+            Err(CursorError::Modified),
+            cursor.decode(&Cursor::new(&expression(42)).encode(&key()).unwrap())
+        );
+    }
+
+    #[test]
+    fn malformed() {
+        let cursor = Cursor::new(&expression(42));
+        assert_eq!(Err(CursorError::Malformed), cursor.decode("not base64!"));
+        assert_eq!(Err(CursorError::Malformed), cursor.decode(""));
+        assert_eq!(Err(CursorError::Malformed), cursor.decode("AQID"));
+
+        let mut bytes = URL_SAFE_NO_PAD
+            .decode(cursor.encode(&key()).unwrap())
+            .unwrap();
+        bytes[0] = 99;
+        assert_eq!(
+            Err(CursorError::UnsupportedVersion(99)),
+            cursor.decode(&URL_SAFE_NO_PAD.encode(&bytes))
+        );
+    }
+}
@@ -14,7 +14,7 @@ use crate::{
     operand::{Operand, OperandType, Size},
     path::{Element, Name, Path},
     update::{Set, SetAction, Update},
-    value::{Ref, Value, ValueOrRef},
+    value::{Num, Ref, Value, ValueOrRef},
 };
 
 /// For building an [`Expression`]. Finish with [`.build()`].
@@ -29,7 +29,8 @@ pub struct Builder {
     key_condition: Option<KeyCondition>,
     update: Option<Update>,
     filter: Option<Condition>,
-    projection: Option<Vec<Name>>,
+    projection: Option<Vec<Path>>,
+    version: Option<(Path, Option<Num>)>,
     names: HashMap<Name, String>,
     values: HashMap<Value, Ref>,
 }
@@ -99,6 +100,9 @@ impl Builder {
 
     /// Sets the projection for this [`Expression`], overwriting any previously set.
     ///
+    /// To project nested attributes or list elements, use
+    /// [`.with_projection_paths()`].
+    ///
     /// Each of these examples produce the same projection expression.
     ///
     /// ```
@@ -151,15 +155,68 @@ impl Builder {
     /// # Ok(())
     /// # }
     /// ```
-    pub fn with_projection<I, T>(mut self, names: I) -> Self
+    ///
+    /// [`.with_projection_paths()`]: Self::with_projection_paths
+    pub fn with_projection<I, T>(self, names: I) -> Self
     where
         I: IntoIterator<Item = T>,
         T: Into<Name>,
     {
+        self.with_projection_paths(names.into_iter().map(|name| Path::from(name.into())))
+    }
+
+    /// Sets the projection for this [`Expression`] to the given [`Path`]s,
+    /// overwriting any previously set. Unlike [`.with_projection()`], nested
+    /// attributes and list elements can be projected.
+    ///
+    /// DynamoDB rejects projections with overlapping paths, so where one path
+    /// is within another (`a` and `a.b`, or `a[0]` and `a[0][1]`), only the
+    /// broader one is kept. Duplicates are only kept once.
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    /// use dynamodb_expression::{Expression, Path};
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let expression = Expression::builder()
+    ///     .with_projection_paths(["address.city".parse::<Path>()?, "tags[0]".parse()?])
+    ///     .build();
+    /// assert_eq!(
+    ///     Some("#0.#1, #2[0]"),
+    ///     expression.projection_expression.as_deref()
+    /// );
+    ///
+    /// // Overlapping paths are dropped.
+    /// let expression = Expression::builder()
+    ///     .with_projection_paths(["id.type".parse::<Path>()?, "id".parse()?, "name".parse()?])
+    ///     .build();
+    /// assert_eq!(Some("#0, #1"), expression.projection_expression.as_deref());
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`.with_projection()`]: Self::with_projection
+    pub fn with_projection_paths<I, T>(mut self, paths: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<Path>,
+    {
+        let paths = paths.into_iter().map(Into::into).collect_vec();
+
         self.projection = Some(
-            names
-                .into_iter()
-                .map(|name| self.process_name(name.into()))
+            paths
+                .iter()
+                .enumerate()
+                // DynamoDB rejects overlapping paths with:
+                // `Invalid ProjectionExpression: Two document paths overlap with each other;`
+                // Keep only the broadest of them, and the first of any duplicates.
+                .filter(|&(i, path)| {
+                    !paths.iter().enumerate().any(|(j, other)| {
+                        i != j && other.covers(path) && (j < i || !path.covers(other))
+                    })
+                })
+                .map(|(_, path)| self.process_path(path.clone()))
                 .collect(),
         )
         // Empty into `None` because DynamoDB doesn't allow empty projection
@@ -170,14 +227,76 @@ impl Builder {
         self
     }
 
+    /// Adds [optimistic locking][1] with a version number attribute, so that
+    /// a write only succeeds if the item hasn't changed since it was read.
+    ///
+    /// With the `expected` version, this increments the version
+    /// (`SET version = version + 1`) and requires the item still has the
+    /// expected version (`version = :expected`).
+    ///
+    /// With `None` for the first write, this sets the version to `1`
+    /// (`SET version = if_not_exists(version, 0) + 1`) and requires the item
+    /// doesn't have a version yet (`attribute_not_exists(version)`).
+    ///
+    /// These are combined with any update and condition set on this builder,
+    /// whether they're set before or after this.
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    /// use dynamodb_expression::{Expression, Num, Path};
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let expression = Expression::builder()
+    ///     .with_update("name".parse::<Path>()?.set("Jane"))
+    ///     .with_version("version".parse::<Path>()?, Num::new(3))
+    ///     .build();
+    /// assert_eq!(
+    ///     Some("SET #0 = :0, #1 = #1 + :1"),
+    ///     expression.update_expression.as_deref()
+    /// );
+    /// assert_eq!(Some("#1 = :2"), expression.condition_expression.as_deref());
+    ///
+    /// // The first write of an item.
+    /// let expression = Expression::builder()
+    ///     .with_version("version".parse::<Path>()?, None)
+    ///     .build();
+    /// assert_eq!(
+    ///     Some("SET #0 = if_not_exists(#0, :0) + :1"),
+    ///     expression.update_expression.as_deref()
+    /// );
+    /// assert_eq!(
+    ///     Some("attribute_not_exists(#0)"),
+    ///     expression.condition_expression.as_deref()
+    /// );
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/DynamoDBMapper.OptimisticLocking.html
+    pub fn with_version<P, T>(mut self, path: P, expected: T) -> Self
+    where
+        P: Into<Path>,
+        T: Into<Option<Num>>,
+    {
+        self.version = Some((path.into(), expected.into()));
+
+        self
+    }
+
     /// Builds the [`Expression`].
-    pub fn build(self) -> Expression {
+    pub fn build(mut self) -> Expression {
+        if let Some((path, expected)) = self.version.take() {
+            self.process_version(path, expected);
+        }
+
         let Self {
             condition,
             key_condition,
             update,
             filter,
             projection,
+            version: _,
             names,
             values,
         } = self;
@@ -191,13 +310,8 @@ impl Builder {
                 update.as_ref().map(ToString::to_string)
             },
             filter_expression: filter.map(Into::into),
-            projection_expression: projection.map(|attrs| {
-                attrs
-                    .into_iter()
-                    .map(|name| name.name)
-                    .collect_vec()
-                    .join(", ")
-            }),
+            projection_expression: projection
+                .map(|attrs| attrs.into_iter().map(String::from).collect_vec().join(", ")),
             expression_attribute_names: Some(
                 names
                     .into_iter()
@@ -218,6 +332,30 @@ impl Builder {
         }
     }
 
+    fn process_version(&mut self, path: Path, expected: Option<Num>) {
+        let (update, condition) = match expected {
+            Some(expected) => (path.clone().math().add(1), path.equal(expected)),
+            None => (
+                path.clone().math().if_not_exists(0).add(1),
+                path.attribute_not_exists(),
+            ),
+        };
+
+        let update = self.process_update(update.into());
+        self.update = Some(match self.update.take() {
+            Some(current) => current.and(update),
+            None => update,
+        });
+
+        let condition = self.process_condition(condition);
+        self.condition = Some(match self.condition.take() {
+            // `AND` takes precedence over `OR`, so keep the existing condition together.
+            Some(current @ Condition::Or(_)) => current.parenthesize().and(condition),
+            Some(current) => current.and(condition),
+            None => condition,
+        });
+    }
+
     fn process_condition(&mut self, condition: Condition) -> Condition {
         match condition {
             Condition::AttributeExists(AttributeExists { path }) => AttributeExists {
@@ -386,6 +524,9 @@ impl Builder {
                 SetAction::Math(mut action) => {
                     action.dst = self.process_path(action.dst);
                     action.src = action.src.map(|src| self.process_path(src));
+                    action.default = action
+                        .default
+                        .map(|default| self.process_value(default).into());
                     action.num = self.process_value(action.num).into();
 
                     action.into()
@@ -459,13 +600,46 @@ impl Builder {
 
 #[cfg(test)]
 mod test {
-    use aws_sdk_dynamodb::operation::query::builders::QueryInputBuilder;
+    use aws_sdk_dynamodb::{operation::query::builders::QueryInputBuilder, types::AttributeValue};
     use pretty_assertions::assert_eq;
 
-    use crate::path::Name;
+    use crate::{
+        path::{Name, Path},
+        Num,
+    };
 
     use super::Expression;
 
+    #[test]
+    fn version_combines_with_condition() -> Result<(), Box<dyn std::error::Error>> {
+        let expression = Expression::builder()
+            .with_version("version".parse::<Path>()?, Num::new(7))
+            .with_condition(
+                "a".parse::<Path>()?
+                    .attribute_exists()
+                    .or("b".parse::<Path>()?.attribute_exists()),
+            )
+            .build();
+
+        assert_eq!(
+            Some("(attribute_exists(#0) OR attribute_exists(#1)) AND #2 = :1"),
+            expression.condition_expression.as_deref()
+        );
+        assert_eq!(
+            Some("SET #2 = #2 + :0"),
+            expression.update_expression.as_deref()
+        );
+
+        let names = expression.expression_attribute_names.unwrap();
+        assert_eq!("version", names["#2"]);
+
+        let values = expression.expression_attribute_values.unwrap();
+        assert_eq!(AttributeValue::N("1".into()), values[":0"]);
+        assert_eq!(AttributeValue::N("7".into()), values[":1"]);
+
+        Ok(())
+    }
+
     #[test]
     fn empty_projection() {
         let expression = Expression::builder()
@@ -488,6 +662,39 @@ mod test {
         let query = expression.to_query_input_builder();
         assert_eq!(QueryInputBuilder::default(), query);
     }
+
+    #[test]
+    fn overlapping_projection() -> Result<(), Box<dyn std::error::Error>> {
+        let expression = Expression::builder()
+            .with_projection_paths([
+                "a.b".parse::<Path>()?,
+                "c[1][2]".parse()?,
+                "a".parse()?,
+                "c[1]".parse()?,
+                "c[2]".parse()?,
+                "d".parse()?,
+                "a".parse()?,
+                "d[0]".parse()?,
+            ])
+            .build();
+
+        assert_eq!(
+            Some("#0, #1[1], #1[2], #2"),
+            expression.projection_expression.as_deref()
+        );
+        assert_eq!(
+            Some(
+                [("#0", "a"), ("#1", "c"), ("#2", "d")]
+                    .into_iter()
+                    .map(|(k, v)| (String::from(k), String::from(v)))
+                    .collect()
+            ),
+            expression.expression_attribute_names,
+            "Names of dropped paths should not be included"
+        );
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
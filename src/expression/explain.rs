@@ -0,0 +1,799 @@
+use core::fmt::{self, Write};
+use std::collections::HashSet;
+
+use crate::{
+    path::{Element, Path},
+    redact::{Redaction, REDACTED},
+};
+
+use super::Expression;
+
+const INDENT: &str = "  ";
+
+/// A human-readable rendering of an [`Expression`], with the expression
+/// attribute names and values substituted back in. Use its [`Display`]
+/// implementation to get the text.
+///
+/// Values can be redacted by the attribute path they're used with, or by a
+/// [`Redaction`] policy, and the output can be pretty-printed across multiple
+/// lines.
+///
+/// See [`Expression::explain`].
+///
+/// [`Display`]: fmt::Display
+#[must_use = "Use the `Display` implementation to render the expression"]
+#[derive(Debug, Clone)]
+pub struct Explain<'a> {
+    expression: &'a Expression,
+    redaction: Redaction,
+    pretty: bool,
+}
+
+impl Expression {
+    /// Renders this [`Expression`] for humans, such as for logging, with the
+    /// expression attribute names and values substituted back in.
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use dynamodb_expression::{Expression, Num, Path};
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let expression = Expression::builder()
+    ///     .with_filter(
+    ///         "age"
+    ///             .parse::<Path>()?
+    ///             .greater_than(Num::new(21))
+    ///             .and("name".parse::<Path>()?.begins_with("A")),
+    ///     )
+    ///     .with_projection(["name", "age"])
+    ///     .build();
+    ///
+    /// assert_eq!(
+    ///     r#"filter: age > 21 AND begins_with(name, "A"); projection: name, age"#,
+    ///     expression.explain().to_string()
+    /// );
+    ///
+    /// // Values can be redacted by path. Values used with attributes within the
+    /// // path, or containing it, are redacted, too.
+    /// assert_eq!(
+    ///     r#"filter: age > 21 AND begins_with(name, <redacted>); projection: name, age"#,
+    ///     expression
+    ///         .explain()
+    ///         .with_redacted(["name".parse::<Path>()?])
+    ///         .to_string()
+    /// );
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Use [`.pretty()`] for multiple lines, with nested conditions indented.
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use dynamodb_expression::{Expression, Num, Path};
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let expression = Expression::builder()
+    ///     .with_condition(
+    ///         "version".parse::<Path>()?.equal(Num::new(3)).and(
+    ///             "status"
+    ///                 .parse::<Path>()?
+    ///                 .equal("active")
+    ///                 .or("status".parse::<Path>()?.attribute_not_exists())
+    ///                 .parenthesize(),
+    ///         ),
+    ///     )
+    ///     .with_update(
+    ///         "status"
+    ///             .parse::<Path>()?
+    ///             .set("done")
+    ///             .and("version".parse::<Path>()?.math().add(1))
+    ///             .and("lock".parse::<Path>()?.remove()),
+    ///     )
+    ///     .build();
+    ///
+    /// assert_eq!(
+    ///     r#"condition:
+    ///   version = 3
+    ///   AND (
+    ///     status = "active"
+    ///     OR attribute_not_exists(status)
+    ///   )
+    /// update:
+    ///   SET
+    ///     status = "done",
+    ///     version = version + 1
+    ///   REMOVE
+    ///     lock"#,
+    ///     expression.explain().pretty().to_string()
+    /// );
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`.pretty()`]: Explain::pretty
+    pub fn explain(&self) -> Explain<'_> {
+        Explain {
+            expression: self,
+            redaction: Redaction::new(),
+            pretty: false,
+        }
+    }
+}
+
+impl Explain<'_> {
+    /// Adds paths whose values should be shown as `<redacted>`. A value is
+    /// redacted if it's used in the same comparison, function, or update
+    /// action as one of these paths, an attribute within one of them, or an
+    /// attribute containing one of them (as its value may include it). Equal
+    /// values share a placeholder, so a redacted value is redacted everywhere
+    /// it's used.
+    pub fn with_redacted<I, T>(mut self, paths: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<Path>,
+    {
+        self.redaction = self.redaction.with_paths(paths);
+
+        self
+    }
+
+    /// Redacts values according to the given [`Redaction`] policy, replacing
+    /// any paths added with [`.with_redacted()`].
+    ///
+    /// [`.with_redacted()`]: Self::with_redacted
+    pub fn with_redaction(mut self, redaction: Redaction) -> Self {
+        self.redaction = redaction;
+
+        self
+    }
+
+    /// Renders each part of the expression on its own lines, with nested
+    /// conditions and update actions indented.
+    pub fn pretty(mut self) -> Self {
+        self.pretty = true;
+
+        self
+    }
+}
+
+impl fmt::Display for Explain<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Renderer {
+            expression: self.expression,
+            redaction: &self.redaction,
+            pretty: self.pretty,
+        }
+        .write(f)
+    }
+}
+
+/// Does the work for [`Explain`], and for redacted output of the types that
+/// build an [`Expression`].
+pub(crate) struct Renderer<'a> {
+    expression: &'a Expression,
+    redaction: &'a Redaction,
+    pretty: bool,
+}
+
+impl<'a> Renderer<'a> {
+    pub(crate) fn new(expression: &'a Expression, redaction: &'a Redaction) -> Self {
+        Self {
+            expression,
+            redaction,
+            pretty: false,
+        }
+    }
+
+    fn sections(&self) -> impl Iterator<Item = (&'static str, &'a str, Mode)> {
+        let Expression {
+            condition_expression,
+            key_condition_expression,
+            update_expression,
+            filter_expression,
+            projection_expression,
+            expression_attribute_names: _,
+            expression_attribute_values: _,
+        } = self.expression;
+
+        [
+            ("key condition", key_condition_expression, Mode::Condition),
+            ("condition", condition_expression, Mode::Condition),
+            ("filter", filter_expression, Mode::Condition),
+            ("update", update_expression, Mode::Update),
+            ("projection", projection_expression, Mode::Projection),
+        ]
+        .into_iter()
+        .filter_map(|(label, expression, mode)| {
+            expression
+                .as_deref()
+                .map(|expression| (label, expression, mode))
+        })
+    }
+
+    /// Writes each part of the expression, labeled.
+    pub(crate) fn write(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let redacted = self.redacted_placeholders();
+        for (i, (label, expression, mode)) in self.sections().enumerate() {
+            if i > 0 {
+                f.write_str(if self.pretty { "\n" } else { "; " })?;
+            }
+
+            f.write_str(label)?;
+            f.write_char(':')?;
+            if !self.pretty {
+                f.write_char(' ')?;
+            }
+            f.write_str(&self.render(expression, mode, &redacted))?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes the parts of the expression without labels. For an expression
+    /// built from a single condition or update, that's the same as the
+    /// `Display` output of what it was built from.
+    pub(crate) fn write_unlabeled(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let redacted = self.redacted_placeholders();
+        for (i, (_, expression, mode)) in self.sections().enumerate() {
+            if i > 0 {
+                f.write_str("; ")?;
+            }
+
+            f.write_str(&self.render(expression, mode, &redacted))?;
+        }
+
+        Ok(())
+    }
+
+    /// The value placeholders that are redacted because of the paths they're
+    /// used with. The builder uses one placeholder for equal values, so a
+    /// value redacted in one place is redacted everywhere it's used.
+    pub(crate) fn redacted_placeholders(&self) -> HashSet<&'a str> {
+        self.sections()
+            .flat_map(|(_, expression, mode)| {
+                let tokens = classify(lex(expression));
+                let clauses = clauses(&tokens, mode);
+                let redacted = self.redacted_clauses(&tokens, &clauses);
+
+                tokens
+                    .into_iter()
+                    .zip(clauses.ids)
+                    .filter_map(move |(token, id)| match token {
+                        Token::Value(value) if redacted.contains(&id) => Some(value),
+                        _ => None,
+                    })
+            })
+            .collect()
+    }
+
+    fn render(&self, expression: &'a str, mode: Mode, redacted: &HashSet<&str>) -> String {
+        let tokens = classify(lex(expression));
+        let clauses = clauses(&tokens, mode);
+
+        let mut out = Output {
+            out: String::new(),
+            indent: 1,
+            line_start: true,
+        };
+
+        if self.pretty {
+            out.newline(0);
+        }
+
+        for (i, token) in tokens.iter().enumerate() {
+            let Break { before, after } = if self.pretty {
+                clauses.breaks[i]
+            } else {
+                Break::default()
+            };
+
+            if let Some(delta) = before {
+                out.newline(delta);
+            }
+
+            match token {
+                Token::Space(_) if out.line_start => {}
+                Token::Name(name) => out.push_str(self.name(name)),
+                Token::Value(value) => out.push_str(&self.value(value, redacted.contains(value))),
+                token => out.push_str(token.as_str()),
+            }
+
+            if let Some(delta) = after {
+                out.newline(delta);
+            }
+        }
+
+        out.out.truncate(out.out.trim_end().len());
+        out.out
+    }
+
+    fn name(&self, name: &'a str) -> &'a str {
+        self.expression
+            .expression_attribute_names
+            .as_ref()
+            .and_then(|names| names.get(name))
+            .map_or(name, String::as_str)
+    }
+
+    fn value(&self, placeholder: &str, redact: bool) -> String {
+        match self
+            .expression
+            .expression_attribute_values
+            .as_ref()
+            .and_then(|values| values.get(placeholder))
+        {
+            Some(value) => self.redaction.display(value, redact),
+            None if redact => String::from(REDACTED),
+            None => placeholder.to_string(),
+        }
+    }
+
+    /// The IDs of clauses with a value to redact.
+    fn redacted_clauses(&self, tokens: &[Token<'a>], clauses: &Clauses) -> HashSet<usize> {
+        if !self.redaction.has_paths() {
+            return HashSet::new();
+        }
+
+        paths(tokens, &clauses.ids, |name| self.name(name))
+            .into_iter()
+            .filter(|(_, path)| self.redaction.covers(path))
+            .map(|(clause, _)| clause)
+            .collect()
+    }
+}
+
+struct Output {
+    out: String,
+    indent: usize,
+    line_start: bool,
+}
+
+impl Output {
+    /// Starts a new line, changing the indentation by `delta` levels first.
+    fn newline(&mut self, delta: isize) {
+        self.indent = self.indent.saturating_add_signed(delta);
+        self.out.truncate(self.out.trim_end_matches(' ').len());
+        self.out.push('\n');
+        for _ in 0..self.indent {
+            self.out.push_str(INDENT);
+        }
+        self.line_start = true;
+    }
+
+    fn push_str(&mut self, s: &str) {
+        self.out.push_str(s);
+        self.line_start = false;
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    Condition,
+    Update,
+    Projection,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Token<'a> {
+    Space(&'a str),
+    /// An attribute name, or a placeholder for one (`#0`).
+    Name(&'a str),
+    /// A placeholder for a value (`:0`).
+    Value(&'a str),
+    /// A keyword or function name.
+    Word(&'a str),
+    /// A list index, or anything else that's not recognized.
+    Other(&'a str),
+    Punct(&'a str),
+}
+
+impl<'a> Token<'a> {
+    fn as_str(&self) -> &'a str {
+        match *self {
+            Self::Space(s)
+            | Self::Name(s)
+            | Self::Value(s)
+            | Self::Word(s)
+            | Self::Other(s)
+            | Self::Punct(s) => s,
+        }
+    }
+}
+
+fn is_ident(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '-'
+}
+
+fn lex(expression: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    let mut rest = expression;
+
+    while let Some(c) = rest.chars().next() {
+        let len = if c.is_whitespace() {
+            rest.find(|c: char| !c.is_whitespace())
+                .unwrap_or(rest.len())
+        } else if c == '#' || c == ':' || is_ident(c) {
+            c.len_utf8()
+                + rest[c.len_utf8()..]
+                    .find(|c: char| !is_ident(c))
+                    .unwrap_or(rest.len() - c.len_utf8())
+        } else if matches!(c, '<' | '>') && rest[1..].starts_with(['=', '>']) {
+            2
+        } else {
+            c.len_utf8()
+        };
+
+        let (text, remaining) = rest.split_at(len);
+        rest = remaining;
+
+        tokens.push(if c.is_whitespace() {
+            Token::Space(text)
+        } else if c == '#' {
+            Token::Name(text)
+        } else if c == ':' && text.len() > 1 {
+            Token::Value(text)
+        } else if c.is_ascii_digit() {
+            Token::Other(text)
+        } else if is_ident(c) {
+            // Sorted out in `classify`.
+            Token::Name(text)
+        } else {
+            Token::Punct(text)
+        });
+    }
+
+    tokens
+}
+
+const KEYWORDS: &[&str] = &[
+    "AND", "OR", "NOT", "BETWEEN", "IN", "SET", "REMOVE", "ADD", "DELETE",
+];
+
+fn is_keyword(word: &str, keyword: &str) -> bool {
+    word.eq_ignore_ascii_case(keyword)
+}
+
+fn is_section(word: &str) -> bool {
+    ["SET", "REMOVE", "ADD", "DELETE"]
+        .iter()
+        .any(|keyword| is_keyword(word, keyword))
+}
+
+/// Identifies keywords and function names among the bare words.
+fn classify(mut tokens: Vec<Token<'_>>) -> Vec<Token<'_>> {
+    for i in 0..tokens.len() {
+        let Token::Name(name) = tokens[i] else {
+            continue;
+        };
+
+        if name.starts_with('#') {
+            continue;
+        }
+
+        let is_function = tokens[i + 1..]
+            .iter()
+            .find(|token| !matches!(token, Token::Space(_)))
+            .is_some_and(|token| *token == Token::Punct("("));
+        let after_dot = tokens[..i]
+            .iter()
+            .rev()
+            .find(|token| !matches!(token, Token::Space(_)))
+            .is_some_and(|token| *token == Token::Punct("."));
+
+        if !after_dot && (is_function || KEYWORDS.iter().any(|k| is_keyword(name, k))) {
+            tokens[i] = Token::Word(name);
+        }
+    }
+
+    tokens
+}
+
+/// Where to put line breaks when pretty-printing. Each is the change in
+/// indentation for the new line.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct Break {
+    before: Option<isize>,
+    after: Option<isize>,
+}
+
+struct Clauses {
+    /// The clause each token is in. A clause is a single comparison, function,
+    /// or update action.
+    ids: Vec<usize>,
+    breaks: Vec<Break>,
+}
+
+fn clauses(tokens: &[Token<'_>], mode: Mode) -> Clauses {
+    let mut ids = Vec::with_capacity(tokens.len());
+    let mut breaks = vec![Break::default(); tokens.len()];
+    let mut id = 0;
+    // `true` for the parentheses of a function call or `IN` list.
+    let mut parens: Vec<bool> = Vec::new();
+    let mut between = false;
+    let mut prev: Option<Token<'_>> = None;
+    let mut section_seen = false;
+
+    for (i, token) in tokens.iter().enumerate() {
+        let in_call = parens.iter().any(|call| *call);
+
+        match (mode, token) {
+            (Mode::Condition, Token::Word(word)) if !in_call => {
+                if is_keyword(word, "BETWEEN") {
+                    between = true;
+                } else if is_keyword(word, "AND") && between {
+                    between = false;
+                } else if is_keyword(word, "AND") || is_keyword(word, "OR") {
+                    id += 1;
+                    breaks[i].before = Some(0);
+                } else if is_keyword(word, "NOT") {
+                    id += 1;
+                }
+            }
+            (Mode::Condition, Token::Punct("(")) => {
+                let call = matches!(prev, Some(Token::Word(word))
+                    if !["AND", "OR", "NOT"].iter().any(|k| is_keyword(word, k)));
+                if !call && !in_call {
+                    id += 1;
+                    breaks[i].after = Some(1);
+                }
+                parens.push(call);
+            }
+            (Mode::Condition, Token::Punct(")")) => {
+                let call = parens.pop().unwrap_or(false);
+                if !call && !parens.iter().any(|call| *call) {
+                    id += 1;
+                    breaks[i].before = Some(-1);
+                }
+            }
+            (Mode::Update, Token::Word(word)) if !in_call && is_section(word) => {
+                id += 1;
+                if section_seen {
+                    breaks[i].before = Some(-1);
+                }
+                breaks[i].after = Some(1);
+                section_seen = true;
+            }
+            (Mode::Update, Token::Punct("(")) => parens.push(true),
+            (Mode::Update, Token::Punct(")")) => {
+                parens.pop();
+            }
+            (Mode::Update, Token::Punct(",")) if !in_call => {
+                id += 1;
+                breaks[i].after = Some(0);
+            }
+            _ => {}
+        }
+
+        ids.push(id);
+        if !matches!(token, Token::Space(_)) {
+            prev = Some(*token);
+        }
+    }
+
+    Clauses { ids, breaks }
+}
+
+/// The attribute paths in each clause.
+fn paths<'a, F>(tokens: &[Token<'a>], ids: &[usize], name: F) -> Vec<(usize, Path)>
+where
+    F: Fn(&'a str) -> &'a str,
+{
+    let mut paths = Vec::new();
+    // The current path's elements, each a name and its indexes.
+    let mut current: Vec<(&str, Vec<usize>)> = Vec::new();
+    let mut clause = 0;
+    let mut expect = Expect::Start;
+
+    let mut finish = |current: &mut Vec<(&str, Vec<usize>)>, clause| {
+        if !current.is_empty() {
+            let path: Path = current
+                .drain(..)
+                .map(|(name, indexes)| Element::new_indexed_field(name, indexes))
+                .collect();
+            paths.push((clause, path));
+        }
+    };
+
+    for (i, token) in tokens.iter().enumerate() {
+        match (expect, token) {
+            (Expect::Name, Token::Name(n)) | (Expect::Start, Token::Name(n)) => {
+                if expect == Expect::Start {
+                    finish(&mut current, clause);
+                }
+                clause = ids[i];
+                current.push((name(n), Vec::new()));
+                expect = Expect::Continue;
+            }
+            (Expect::Continue, Token::Punct(".")) => expect = Expect::Name,
+            (Expect::Continue, Token::Punct("[")) => expect = Expect::Index,
+            (Expect::Index, Token::Other(index)) => {
+                if let (Some((_, indexes)), Ok(index)) = (current.last_mut(), index.parse()) {
+                    indexes.push(index);
+                }
+                expect = Expect::CloseIndex;
+            }
+            (Expect::CloseIndex, Token::Punct("]")) => expect = Expect::Continue,
+            _ => {
+                finish(&mut current, clause);
+                expect = Expect::Start;
+            }
+        }
+    }
+
+    finish(&mut current, clause);
+
+    paths
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Expect {
+    Start,
+    Name,
+    Continue,
+    Index,
+    CloseIndex,
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use aws_sdk_dynamodb::types::AttributeValue;
+    use pretty_assertions::assert_eq;
+
+    use crate::{condition::Condition, path::test::path, Expression, Num};
+
+    #[test]
+    fn conditions() {
+        let expression = Expression::builder()
+            .with_key_condition(
+                path("id")
+                    .key()
+                    .equal("a")
+                    .and(path("sk").key().between(Num::new(1), Num::new(9))),
+            )
+            .with_filter(
+                path("a[2].b")
+                    .between(Num::new(1), Num::new(5))
+                    .and(!path("c").in_(["x", "y"]))
+                    .and(
+                        Condition::from(path("d").size().greater_than(Num::new(3)))
+                            .or(path("e")
+                                .equal(Num::new(1))
+                                .and(path("f").contains("z"))
+                                .parenthesize())
+                            .parenthesize(),
+                    ),
+            )
+            .build();
+
+        assert_eq!(
+            r#"key condition: id = "a" AND sk BETWEEN 1 AND 9; filter: a[2].b BETWEEN 1 AND 5 AND NOT c IN ("x","y") AND (size(d) > 3 OR (e = 1 AND contains(f, "z")))"#,
+            expression.explain().to_string()
+        );
+
+        assert_eq!(
+            r#"key condition:
+  id = "a"
+  AND sk BETWEEN 1 AND 9
+filter:
+  a[2].b BETWEEN 1 AND 5
+  AND NOT c IN ("x","y")
+  AND (
+    size(d) > 3
+    OR (
+      e = 1
+      AND contains(f, "z")
+    )
+  )"#,
+            expression.explain().pretty().to_string()
+        );
+
+        assert_eq!(
+            r#"key condition: id = "a" AND sk BETWEEN <redacted> AND 9; filter: a[2].b BETWEEN <redacted> AND <redacted> AND NOT c IN ("x","y") AND (size(d) > <redacted> OR (e = <redacted> AND contains(f, "z")))"#,
+            expression
+                .explain()
+                .with_redacted([path("a"), path("d")])
+                .to_string(),
+            "The `1` used with `a[2].b` shares a placeholder with the other `1`s"
+        );
+
+        assert_eq!(
+            r#"key condition: id = <redacted> AND sk BETWEEN 1 AND 9; filter: a[2].b BETWEEN 1 AND 5 AND NOT c IN (<redacted>,<redacted>) AND (size(d) > 3 OR (e = 1 AND contains(f, "z")))"#,
+            expression
+                .explain()
+                .with_redacted([path("id"), path("c"), path("a[3]"), path("b")])
+                .to_string(),
+            "Only values used with the redacted paths, or paths within or containing them, are redacted"
+        );
+
+        assert_eq!(
+            r#"key condition: id = "a" AND sk BETWEEN 1 AND 9; filter: a[2].b BETWEEN 1 AND 5 AND NOT c IN ("x","y") AND (size(d) > <redacted> OR (e = 1 AND contains(f, "z")))"#,
+            expression
+                .explain()
+                .with_redacted([path("d.x")])
+                .to_string(),
+            "The value of `d` may include `d.x`"
+        );
+    }
+
+    #[test]
+    fn updates() {
+        let expression = Expression::builder()
+            .with_update(
+                path("list")
+                    .list_append()
+                    .list(["a"])
+                    .and(path("count").math().src(path("other")).sub(2))
+                    .and(path("secret").set("shh"))
+                    .and(path("tags").add(crate::value::StringSet::from(["new"])))
+                    .and(path("old").remove()),
+            )
+            .build();
+
+        assert_eq!(
+            r#"update: SET list = list_append(list, ["a"]), count = other - 2, secret = "shh" REMOVE old ADD tags ["new"]"#,
+            expression.explain().to_string()
+        );
+
+        assert_eq!(
+            r#"update:
+  SET
+    list = list_append(list, <redacted>),
+    count = other - 2,
+    secret = <redacted>
+  REMOVE
+    old
+  ADD
+    tags ["new"]"#,
+            expression
+                .explain()
+                .with_redacted([path("list"), path("secret")])
+                .pretty()
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn hand_written() {
+        let expression = Expression {
+            condition_expression: Some(String::from(
+                "#status = :active and attribute_exists(owner.#n) and :missing < score",
+            )),
+            key_condition_expression: None,
+            update_expression: None,
+            filter_expression: None,
+            projection_expression: Some(String::from("#status, owner.#n")),
+            expression_attribute_names: Some(HashMap::from([
+                (String::from("#status"), String::from("status")),
+                (String::from("#n"), String::from("name")),
+            ])),
+            expression_attribute_values: Some(HashMap::from([(
+                String::from(":active"),
+                AttributeValue::S(String::from("active")),
+            )])),
+        };
+
+        assert_eq!(
+            r#"condition: status = "active" and attribute_exists(owner.name) and :missing < score; projection: status, owner.name"#,
+            expression.explain().to_string()
+        );
+
+        assert_eq!(
+            r#"condition: status = <redacted> and attribute_exists(owner.name) and <redacted> < score; projection: status, owner.name"#,
+            expression
+                .explain()
+                .with_redacted([path("status"), path("score")])
+                .to_string(),
+            "Paths after the value are also considered"
+        );
+
+        assert_eq!(
+            "condition:\n  status = \"active\"\n  and attribute_exists(owner.name)\n  and :missing < score\nprojection:\n  status, owner.name",
+            expression.explain().pretty().to_string()
+        );
+    }
+}
@@ -3,14 +3,22 @@
 //! [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/Expressions.html
 
 mod builder;
+mod explain;
 mod to_aws;
 
 pub use builder::Builder;
+pub use explain::Explain;
+pub(crate) use explain::Renderer;
 
 use std::collections::HashMap;
 
 use aws_sdk_dynamodb::types::AttributeValue;
 
+use crate::{
+    path::{Projection, ProjectionParseError},
+    value::{item_to_map, DecodeError, Map},
+};
+
 /// The data needed for various [`aws_sdk_dynamodb`] input types.
 ///
 /// Use [`Builder`] (via [`Expression::builder`]) to create DynamoDB
@@ -104,4 +112,181 @@ impl Expression {
     pub fn builder() -> Builder {
         Builder::default()
     }
+
+    /// Returns only the attributes of the item selected by the
+    /// [`.projection_expression`], the same way DynamoDB would. With no
+    /// projection expression, the whole item is returned.
+    ///
+    /// See [`Projection`] for how the projection is applied.
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use std::collections::HashMap;
+    ///
+    /// use aws_sdk_dynamodb::types::AttributeValue;
+    /// use dynamodb_expression::Expression;
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let item = HashMap::from([
+    ///     ("id".to_string(), AttributeValue::S("1".into())),
+    ///     ("name".to_string(), AttributeValue::S("Jane".into())),
+    ///     ("age".to_string(), AttributeValue::N("42".into())),
+    /// ]);
+    ///
+    /// let expression = Expression::builder().with_projection(["id", "name"]).build();
+    /// assert_eq!(
+    ///     HashMap::from([
+    ///         ("id".to_string(), AttributeValue::S("1".into())),
+    ///         ("name".to_string(), AttributeValue::S("Jane".into())),
+    ///     ]),
+    ///     expression.project_item(&item)?
+    /// );
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`.projection_expression`]: Self::projection_expression
+    pub fn project_item(
+        &self,
+        item: &HashMap<String, AttributeValue>,
+    ) -> Result<HashMap<String, AttributeValue>, ProjectionParseError> {
+        Ok(match self.projection()? {
+            Some(projection) => projection.apply(item),
+            None => item.clone(),
+        })
+    }
+
+    /// Decodes an item returned by DynamoDB for a request using this
+    /// expression into a [`Map`].
+    ///
+    /// If there's a [`.projection_expression`], every projected path must be
+    /// in the item. See [`Projection::check`]. The [`DecodeError`] has the
+    /// [`Path`] where any problem was found.
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use std::collections::HashMap;
+    ///
+    /// use aws_sdk_dynamodb::types::AttributeValue;
+    /// use dynamodb_expression::{value::DecodeErrorKind, Expression, Path, Value};
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let expression = Expression::builder()
+    ///     .with_projection_paths(["name".parse::<Path>()?, "address.city".parse()?])
+    ///     .build();
+    ///
+    /// let item = HashMap::from([
+    ///     ("name".to_string(), AttributeValue::S("Jane".into())),
+    ///     (
+    ///         "address".to_string(),
+    ///         AttributeValue::M(HashMap::from([(
+    ///             "city".to_string(),
+    ///             AttributeValue::S("Seattle".into()),
+    ///         )])),
+    ///     ),
+    /// ]);
+    /// assert_eq!(
+    ///     Value::new_map([
+    ///         ("name", Value::from("Jane")),
+    ///         ("address", Value::new_map([("city", "Seattle")])),
+    ///     ]),
+    ///     Value::from(expression.decode_item(item)?)
+    /// );
+    ///
+    /// let item = HashMap::from([
+    ///     ("name".to_string(), AttributeValue::S("Jane".into())),
+    ///     ("address".to_string(), AttributeValue::M(HashMap::new())),
+    /// ]);
+    /// let err = expression.decode_item(item).unwrap_err();
+    /// assert_eq!("address.city".parse::<Path>()?, err.path());
+    /// assert_eq!(&DecodeErrorKind::Missing, err.kind());
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`.projection_expression`]: Self::projection_expression
+    /// [`Path`]: crate::Path
+    pub fn decode_item(&self, item: HashMap<String, AttributeValue>) -> Result<Map, DecodeError> {
+        if let Some(projection) = self.projection()? {
+            projection.check(&item)?;
+        }
+
+        item_to_map(item)
+    }
+
+    /// Like [`.decode_item()`], but deserializes the item into any
+    /// [`DeserializeOwned`] type. See [`value::serde`] for how DynamoDB values
+    /// map to Rust types.
+    ///
+    /// Requires the `serde` feature.
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use std::collections::HashMap;
+    ///
+    /// use aws_sdk_dynamodb::types::AttributeValue;
+    /// use dynamodb_expression::{Expression, Path};
+    /// use serde::Deserialize;
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// #[derive(Debug, PartialEq, Deserialize)]
+    /// struct Person {
+    ///     name: String,
+    ///     age: u8,
+    /// }
+    ///
+    /// let expression = Expression::builder()
+    ///     .with_projection_paths(["name".parse::<Path>()?, "age".parse()?])
+    ///     .build();
+    ///
+    /// let item = HashMap::from([
+    ///     ("name".to_string(), AttributeValue::S("Jane".into())),
+    ///     ("age".to_string(), AttributeValue::N("42".into())),
+    /// ]);
+    /// assert_eq!(
+    ///     Person {
+    ///         name: "Jane".into(),
+    ///         age: 42
+    ///     },
+    ///     expression.decode_item_as::<Person>(item)?
+    /// );
+    ///
+    /// let item = HashMap::from([
+    ///     ("name".to_string(), AttributeValue::S("Jane".into())),
+    ///     ("age".to_string(), AttributeValue::S("old".into())),
+    /// ]);
+    /// let err = expression.decode_item_as::<Person>(item).unwrap_err();
+    /// assert_eq!("age".parse::<Path>()?, err.path());
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`.decode_item()`]: Self::decode_item
+    /// [`DeserializeOwned`]: ::serde::de::DeserializeOwned
+    /// [`value::serde`]: crate::value::serde
+    #[cfg(feature = "serde")]
+    pub fn decode_item_as<T>(&self, item: HashMap<String, AttributeValue>) -> Result<T, DecodeError>
+    where
+        T: ::serde::de::DeserializeOwned,
+    {
+        let map = self.decode_item(item)?;
+
+        Ok(crate::value::serde::from_value(map.into())?)
+    }
+
+    /// Parses the [`.projection_expression`], if there is one, into a
+    /// [`Projection`] that can be applied to any number of items.
+    ///
+    /// [`.projection_expression`]: Self::projection_expression
+    pub fn projection(&self) -> Result<Option<Projection>, ProjectionParseError> {
+        self.projection_expression
+            .as_deref()
+            .map(|projection| {
+                Projection::parse(projection, self.expression_attribute_names.as_ref())
+            })
+            .transpose()
+    }
 }
@@ -2,6 +2,15 @@
 //!
 //! [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/Query.KeyConditionExpressions.html
 
+mod parts;
+mod schema;
+mod typed;
+
+pub use schema::{Index, KeyAttribute, KeyConditionError, KeySchema, KeyType, TableSchema};
+pub use typed::{
+    KeyPrefix, KeyValue, PartitionKey, PartitionKeyCondition, SortKey, SortKeyCondition,
+};
+
 use core::fmt;
 
 use crate::{
@@ -9,7 +18,7 @@ use crate::{
         equal, greater_than, greater_than_or_equal, less_than, less_than_or_equal, Condition,
     },
     operand::Operand,
-    path::Path,
+    path::{Name, Path},
     value::StringOrRef,
 };
 
@@ -45,6 +54,42 @@ pub struct Key {
 }
 
 impl Key {
+    /// Start a key condition on the partition key. Key conditions built this
+    /// way are always ones DynamoDB will accept.
+    ///
+    /// See also: [`PartitionKey`]
+    ///
+    /// ```
+    /// use dynamodb_expression::{key::Key, Num};
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let key_condition = Key::partition("id")
+    ///     .equal(Num::new(42))
+    ///     .and_sort(Key::sort("category").begins_with("hardware."))
+    ///     .unwrap();
+    /// assert_eq!(
+    ///     r#"id = 42 AND begins_with(category, "hardware.")"#,
+    ///     key_condition.to_string()
+    /// );
+    /// ```
+    pub fn partition<T>(name: T) -> PartitionKey
+    where
+        T: Into<Name>,
+    {
+        PartitionKey::new(name)
+    }
+
+    /// Start a condition on the sort key, to be combined with a partition key
+    /// condition using [`PartitionKeyCondition::and_sort`].
+    ///
+    /// See also: [`SortKey`]
+    pub fn sort<T>(name: T) -> SortKey
+    where
+        T: Into<Name>,
+    {
+        SortKey::new(name)
+    }
+
     /// The [DynamoDB `begins_with` function][1]. True if the attribute specified by
     ///  the [`Path`] begins with a particular substring.
     ///
@@ -180,6 +225,11 @@ where
 /// Represents a DynamoDB [key condition expression][1]. Build an instance from
 /// the methods on [`Key`].
 ///
+/// DynamoDB only accepts some combinations of conditions. Prefer building key
+/// conditions with [`Key::partition`], which only allows valid ones. Otherwise,
+/// use [`KeySchema::validate`] or [`TableSchema::index_for`] to check one
+/// before sending it.
+///
 /// See also: [`Path::key`], [`expression::Builder::with_key_condition`]
 ///
 /// ```
@@ -239,6 +289,38 @@ impl fmt::Display for KeyCondition {
     }
 }
 
+impl TryFrom<Condition> for KeyCondition {
+    type Error = KeyConditionError;
+
+    /// Checks that the [`Condition`] has the shape DynamoDB requires of a key
+    /// condition: an equality test on one attribute, optionally combined with
+    /// `AND` and one more condition on another attribute.
+    ///
+    /// To also check that the attributes are the right keys with the right
+    /// types, see [`KeySchema::validate`].
+    ///
+    /// ```
+    /// use dynamodb_expression::{
+    ///     condition::Condition,
+    ///     key::{KeyCondition, KeyConditionError},
+    ///     Path,
+    /// };
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let id = Path::new_name("id");
+    /// let condition = id.clone().equal("a").or(id.equal("b"));
+    /// assert_eq!(
+    ///     Err(KeyConditionError::LogicalOperator("OR")),
+    ///     KeyCondition::try_from(condition),
+    /// );
+    /// ```
+    fn try_from(condition: Condition) -> Result<Self, Self::Error> {
+        parts::key_parts(&condition)?;
+
+        Ok(Self { condition })
+    }
+}
+
 impl From<KeyCondition> for String {
     fn from(key_condition: KeyCondition) -> Self {
         key_condition.condition.into()
@@ -247,9 +329,11 @@ impl From<KeyCondition> for String {
 
 #[cfg(test)]
 mod test {
-    use crate::{value::Ref, Path};
+    use pretty_assertions::assert_eq;
 
-    use super::Key;
+    use crate::{condition::BeginsWith, value::Ref, Num, Path};
+
+    use super::{Key, KeyCondition, KeyConditionError};
 
     #[test]
     fn begins_with_string() {
@@ -285,4 +369,50 @@ mod test {
         let begins_with = Key::from("foo".parse::<Path>().unwrap()).begins_with(Ref::new("prefix"));
         assert_eq!("begins_with(foo, :prefix)", begins_with.to_string());
     }
+
+    #[test]
+    fn try_from_condition() {
+        let id = || Path::new_name("id");
+        let sk = || Path::new_name("sk");
+
+        let condition = id().equal("a").and(sk().begins_with("b"));
+        assert_eq!(
+            Key::partition("id")
+                .equal("a")
+                .and_sort(Key::sort("sk").begins_with("b")),
+            KeyCondition::try_from(condition)
+        );
+
+        assert_eq!(
+            Err(KeyConditionError::LogicalOperator("NOT")),
+            KeyCondition::try_from(id().equal("a").not())
+        );
+        assert_eq!(
+            Err(KeyConditionError::UnsupportedCondition(
+                r#"id <> "a""#.into()
+            )),
+            KeyCondition::try_from(id().not_equal("a"))
+        );
+        assert_eq!(
+            Err(KeyConditionError::UnsupportedCondition(
+                "attribute_exists(id)".into()
+            )),
+            KeyCondition::try_from(id().attribute_exists())
+        );
+        assert_eq!(
+            Err(KeyConditionError::UnsupportedCondition("id = true".into())),
+            KeyCondition::try_from(id().equal(true))
+        );
+        assert_eq!(
+            Err(KeyConditionError::BeginsWithType("sk".into())),
+            KeyCondition::try_from(id().equal("a").and(BeginsWith {
+                path: sk(),
+                substr: Num::new(1).into(),
+            }))
+        );
+        assert_eq!(
+            Err(KeyConditionError::MissingPartitionKey),
+            KeyCondition::try_from(sk().greater_than(Num::new(1)))
+        );
+    }
 }
@@ -0,0 +1,169 @@
+//! Breaks a key condition down into the conditions on the individual key
+//! attributes, checking the rules that don't depend on a table's key schema.
+
+use crate::{
+    condition::{BeginsWith, Between, Comparator, Comparison, Condition},
+    operand::{Operand, OperandType},
+    path::{Element, Name, Path},
+    value::{Scalar, Value, ValueOrRef},
+};
+
+use super::KeyConditionError;
+
+/// The condition on a single key attribute.
+#[derive(Debug)]
+pub(crate) struct KeyPart<'a> {
+    pub(crate) name: &'a Name,
+    pub(crate) op: KeyOp<'a>,
+}
+
+/// The operations DynamoDB allows on a key attribute.
+#[derive(Debug)]
+pub(crate) enum KeyOp<'a> {
+    Comparison(Comparator, &'a ValueOrRef),
+    Between(&'a ValueOrRef, &'a ValueOrRef),
+    BeginsWith(&'a ValueOrRef),
+}
+
+impl KeyOp<'_> {
+    pub(crate) fn is_equal(&self) -> bool {
+        matches!(self, Self::Comparison(Comparator::Eq, _))
+    }
+
+    /// The values used by this operation.
+    pub(crate) fn values(&self) -> Vec<&ValueOrRef> {
+        match self {
+            Self::Comparison(_, value) | Self::BeginsWith(value) => vec![value],
+            Self::Between(lower, upper) => vec![lower, upper],
+        }
+    }
+}
+
+/// Splits a key condition into the conditions on each key attribute.
+///
+/// DynamoDB allows an equality condition on the partition key, optionally
+/// combined with `AND` and a single condition on the sort key. This checks
+/// everything about that which can be checked without knowing which attribute
+/// is the partition key and which is the sort key.
+pub(crate) fn key_parts(condition: &Condition) -> Result<Vec<KeyPart<'_>>, KeyConditionError> {
+    let mut leaves = Vec::new();
+    collect_leaves(condition, &mut leaves)?;
+
+    if leaves.len() > 2 {
+        return Err(KeyConditionError::TooManyConditions(leaves.len()));
+    }
+
+    let parts = leaves
+        .into_iter()
+        .map(key_part)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if let [first, second] = parts.as_slice() {
+        if first.name == second.name {
+            return Err(KeyConditionError::DuplicateKey(first.name.clone()));
+        }
+    }
+
+    if !parts.iter().any(|part| part.op.is_equal()) {
+        return Err(KeyConditionError::MissingPartitionKey);
+    }
+
+    Ok(parts)
+}
+
+fn collect_leaves<'a>(
+    condition: &'a Condition,
+    leaves: &mut Vec<&'a Condition>,
+) -> Result<(), KeyConditionError> {
+    match condition {
+        Condition::And(and) => {
+            collect_leaves(&and.left, leaves)?;
+            collect_leaves(&and.right, leaves)
+        }
+        Condition::Parenthetical(parens) => collect_leaves(&parens.condition, leaves),
+        Condition::Or(_) => Err(KeyConditionError::LogicalOperator("OR")),
+        Condition::Not(_) => Err(KeyConditionError::LogicalOperator("NOT")),
+        _ => {
+            leaves.push(condition);
+
+            Ok(())
+        }
+    }
+}
+
+fn key_part(condition: &Condition) -> Result<KeyPart<'_>, KeyConditionError> {
+    let unsupported = || KeyConditionError::UnsupportedCondition(condition.to_string());
+
+    match condition {
+        Condition::Comparison(Comparison { left, cmp, right }) => {
+            if *cmp == Comparator::Ne {
+                return Err(unsupported());
+            }
+
+            Ok(KeyPart {
+                name: key_name(operand_path(left).ok_or_else(unsupported)?)?,
+                op: KeyOp::Comparison(*cmp, operand_value(right).ok_or_else(unsupported)?),
+            })
+        }
+        Condition::Between(Between { op, lower, upper }) => Ok(KeyPart {
+            name: key_name(operand_path(op).ok_or_else(unsupported)?)?,
+            op: KeyOp::Between(
+                operand_value(lower).ok_or_else(unsupported)?,
+                operand_value(upper).ok_or_else(unsupported)?,
+            ),
+        }),
+        Condition::BeginsWith(BeginsWith { path, substr }) => {
+            let name = key_name(path)?;
+
+            if let ValueOrRef::Value(value) = substr {
+                if !matches!(value, Value::Scalar(Scalar::String(_) | Scalar::Binary(_))) {
+                    return Err(KeyConditionError::BeginsWithType(name.clone()));
+                }
+            }
+
+            Ok(KeyPart {
+                name,
+                op: KeyOp::BeginsWith(substr),
+            })
+        }
+        _ => Err(unsupported()),
+    }
+    .and_then(|part| {
+        // Key attributes can only be strings, numbers, or binary.
+        if part.op.values().into_iter().all(|value| {
+            matches!(
+                value,
+                ValueOrRef::Ref(_)
+                    | ValueOrRef::Value(Value::Scalar(
+                        Scalar::String(_) | Scalar::Num(_) | Scalar::Binary(_)
+                    ))
+            )
+        }) {
+            Ok(part)
+        } else {
+            Err(unsupported())
+        }
+    })
+}
+
+fn operand_path(operand: &Operand) -> Option<&Path> {
+    match &operand.op {
+        OperandType::Path(path) => Some(path),
+        _ => None,
+    }
+}
+
+fn operand_value(operand: &Operand) -> Option<&ValueOrRef> {
+    match &operand.op {
+        OperandType::Scalar(value) => Some(value),
+        _ => None,
+    }
+}
+
+/// Key attributes are always top-level attributes.
+fn key_name(path: &Path) -> Result<&Name, KeyConditionError> {
+    match path.elements.as_slice() {
+        [Element::Name(name)] => Ok(name),
+        _ => Err(KeyConditionError::InvalidKeyPath(path.clone())),
+    }
+}
@@ -0,0 +1,748 @@
+use core::fmt;
+use std::error::Error;
+
+use aws_sdk_dynamodb::{operation::query::builders::QueryFluentBuilder, Client};
+
+use crate::{
+    condition::{attribute_type::Type, Condition},
+    expression::Builder,
+    operand::Operand,
+    path::{Name, Path},
+    value::{Scalar, Value, ValueOrRef},
+};
+
+use super::{
+    parts::{key_parts, KeyOp, KeyPart},
+    KeyCondition,
+};
+
+/// The type of a key attribute. DynamoDB only allows key attributes to be
+/// [strings, numbers, or binary][1].
+///
+/// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/HowItWorks.CoreComponents.html#HowItWorks.CoreComponents.PrimaryKey
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum KeyType {
+    String,
+    Number,
+    Binary,
+}
+
+impl KeyType {
+    fn matches(self, value: &Value) -> bool {
+        matches!(
+            (self, value),
+            (Self::String, Value::Scalar(Scalar::String(_)))
+                | (Self::Number, Value::Scalar(Scalar::Num(_)))
+                | (Self::Binary, Value::Scalar(Scalar::Binary(_)))
+        )
+    }
+}
+
+impl fmt::Display for KeyType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Type::from(*self).fmt(f)
+    }
+}
+
+impl From<KeyType> for Type {
+    fn from(key_type: KeyType) -> Self {
+        match key_type {
+            KeyType::String => Self::String,
+            KeyType::Number => Self::Number,
+            KeyType::Binary => Self::Binary,
+        }
+    }
+}
+
+/// The name and type of a key attribute.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct KeyAttribute {
+    pub(crate) name: Name,
+    pub(crate) key_type: KeyType,
+}
+
+impl KeyAttribute {
+    /// A key attribute with this name and type.
+    pub fn new<T>(name: T, key_type: KeyType) -> Self
+    where
+        T: Into<Name>,
+    {
+        Self {
+            name: name.into(),
+            key_type,
+        }
+    }
+
+    /// The name of the key attribute.
+    pub fn name(&self) -> &Name {
+        &self.name
+    }
+
+    /// The type of the key attribute.
+    pub fn key_type(&self) -> KeyType {
+        self.key_type
+    }
+}
+
+/// The [primary key][1] of a table or index: a partition key and an optional
+/// sort key.
+///
+/// ```
+/// use dynamodb_expression::key::{KeyAttribute, KeySchema, KeyType};
+///
+/// let key_schema = KeySchema::new(KeyAttribute::new("id", KeyType::String))
+///     .with_sort_key(KeyAttribute::new("created", KeyType::Number));
+/// ```
+///
+/// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/HowItWorks.CoreComponents.html#HowItWorks.CoreComponents.PrimaryKey
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct KeySchema {
+    pub(crate) partition_key: KeyAttribute,
+    pub(crate) sort_key: Option<KeyAttribute>,
+}
+
+impl KeySchema {
+    /// A key schema with only a partition key.
+    pub fn new(partition_key: KeyAttribute) -> Self {
+        Self {
+            partition_key,
+            sort_key: None,
+        }
+    }
+
+    /// Add a sort key to this key schema.
+    pub fn with_sort_key(mut self, sort_key: KeyAttribute) -> Self {
+        self.sort_key = Some(sort_key);
+
+        self
+    }
+
+    /// The partition (hash) key.
+    pub fn partition_key(&self) -> &KeyAttribute {
+        &self.partition_key
+    }
+
+    /// The sort (range) key, if there is one.
+    pub fn sort_key(&self) -> Option<&KeyAttribute> {
+        self.sort_key.as_ref()
+    }
+
+    /// A condition for a `put_item` that only succeeds if there's no item with
+    /// the same key, so it won't overwrite one.
+    ///
+    /// ```
+    /// use dynamodb_expression::key::{KeyAttribute, KeySchema, KeyType};
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let key_schema = KeySchema::new(KeyAttribute::new("id", KeyType::String))
+    ///     .with_sort_key(KeyAttribute::new("created", KeyType::Number));
+    /// assert_eq!(
+    ///     "attribute_not_exists(id) AND attribute_not_exists(created)",
+    ///     key_schema.put_if_absent().to_string()
+    /// );
+    /// ```
+    pub fn put_if_absent(&self) -> Condition {
+        self.all_keys(Path::attribute_not_exists)
+    }
+
+    /// A condition for an `update_item` that only succeeds if the item already
+    /// exists, rather than creating a new item.
+    ///
+    /// ```
+    /// use dynamodb_expression::key::{KeyAttribute, KeySchema, KeyType};
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let key_schema = KeySchema::new(KeyAttribute::new("id", KeyType::String));
+    /// assert_eq!(
+    ///     "attribute_exists(id)",
+    ///     key_schema.update_if_exists().to_string()
+    /// );
+    /// ```
+    pub fn update_if_exists(&self) -> Condition {
+        self.all_keys(Path::attribute_exists)
+    }
+
+    /// A condition for a `delete_item` that only succeeds if the item exists
+    /// and each of the attributes equals its expected value.
+    ///
+    /// ```
+    /// use dynamodb_expression::{
+    ///     key::{KeyAttribute, KeySchema, KeyType},
+    ///     Path,
+    /// };
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let key_schema = KeySchema::new(KeyAttribute::new("id", KeyType::String));
+    /// let condition = key_schema.delete_if_matches([
+    ///     (Path::new_name("status"), "cancelled"),
+    ///     (Path::new_name("owner"), "jane"),
+    /// ]);
+    /// assert_eq!(
+    ///     r#"attribute_exists(id) AND status = "cancelled" AND owner = "jane""#,
+    ///     condition.to_string()
+    /// );
+    /// ```
+    pub fn delete_if_matches<I, P, T>(&self, expected: I) -> Condition
+    where
+        I: IntoIterator<Item = (P, T)>,
+        P: Into<Path>,
+        T: Into<Operand>,
+    {
+        expected.into_iter().fold(
+            self.all_keys(Path::attribute_exists),
+            |condition, (path, value)| condition.and(path.into().equal(value)),
+        )
+    }
+
+    /// The condition on each key attribute, combined with `AND`.
+    fn all_keys(&self, condition: fn(Path) -> Condition) -> Condition {
+        let partition_key = condition(self.partition_key.name.clone().into());
+
+        match &self.sort_key {
+            Some(sort_key) => partition_key.and(condition(sort_key.name.clone().into())),
+            None => partition_key,
+        }
+    }
+
+    /// Checks that the [`KeyCondition`] is one DynamoDB will accept for a
+    /// table or index with this key schema.
+    ///
+    /// ```
+    /// use dynamodb_expression::{
+    ///     key::{KeyAttribute, KeyConditionError, KeySchema, KeyType},
+    ///     Num, Path,
+    /// };
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let key_schema = KeySchema::new(KeyAttribute::new("id", KeyType::String))
+    ///     .with_sort_key(KeyAttribute::new("created", KeyType::Number));
+    ///
+    /// let id = Path::new_name("id");
+    /// let created = Path::new_name("created");
+    ///
+    /// let key_condition = id
+    ///     .clone()
+    ///     .key()
+    ///     .equal("abc")
+    ///     .and(created.clone().key().greater_than(Num::new(1_700_000_000)));
+    /// assert_eq!(Ok(()), key_schema.validate(&key_condition));
+    ///
+    /// // `begins_with` isn't allowed on a number.
+    /// let key_condition = id
+    ///     .key()
+    ///     .equal("abc")
+    ///     .and(created.key().begins_with("17"));
+    /// assert_eq!(
+    ///     Err(KeyConditionError::BeginsWithType("created".into())),
+    ///     key_schema.validate(&key_condition),
+    /// );
+    /// ```
+    pub fn validate(&self, key_condition: &KeyCondition) -> Result<(), KeyConditionError> {
+        self.check(&key_parts(&key_condition.condition)?)
+    }
+
+    fn key_attribute(&self, name: &Name) -> Option<&KeyAttribute> {
+        [Some(&self.partition_key), self.sort_key.as_ref()]
+            .into_iter()
+            .flatten()
+            .find(|key| key.name == *name)
+    }
+
+    fn check(&self, parts: &[KeyPart<'_>]) -> Result<(), KeyConditionError> {
+        if let Some(part) = parts
+            .iter()
+            .find(|part| self.key_attribute(part.name).is_none())
+        {
+            return Err(KeyConditionError::UnknownKey(part.name.clone()));
+        }
+
+        match parts
+            .iter()
+            .find(|part| *part.name == self.partition_key.name)
+        {
+            None => {
+                return Err(KeyConditionError::MissingPartitionKey);
+            }
+            Some(part) if !part.op.is_equal() => {
+                return Err(KeyConditionError::PartitionKeyNotEqual(part.name.clone()));
+            }
+            Some(_) => {}
+        }
+
+        for part in parts {
+            let key_type = self
+                .key_attribute(part.name)
+                .expect("Names were already checked")
+                .key_type;
+
+            if let KeyOp::BeginsWith(_) = part.op {
+                if key_type == KeyType::Number {
+                    return Err(KeyConditionError::BeginsWithType(part.name.clone()));
+                }
+            }
+
+            for value in part.op.values() {
+                if let ValueOrRef::Value(value) = value {
+                    if !key_type.matches(value) {
+                        return Err(KeyConditionError::TypeMismatch {
+                            name: part.name.clone(),
+                            expected: key_type,
+                        });
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A [secondary index][1] on a table.
+///
+/// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/SecondaryIndexes.html
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Index {
+    pub(crate) name: String,
+    pub(crate) key_schema: KeySchema,
+}
+
+impl Index {
+    /// The name of the index.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The key schema of the index.
+    pub fn key_schema(&self) -> &KeySchema {
+        &self.key_schema
+    }
+}
+
+/// Describes the keys of a DynamoDB table and its [secondary indexes][1].
+///
+/// Use it to check a [`KeyCondition`] before sending it to DynamoDB, and to
+/// find which index (if any) can be queried with it.
+///
+/// ```
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use dynamodb_expression::{
+///     key::{KeyAttribute, KeySchema, KeyType, TableSchema},
+///     Path,
+/// };
+/// # use pretty_assertions::assert_eq;
+///
+/// let schema = TableSchema::new(
+///     "orders",
+///     KeySchema::new(KeyAttribute::new("id", KeyType::String)),
+/// )
+/// .with_global_secondary_index(
+///     "by-customer",
+///     KeySchema::new(KeyAttribute::new("customer", KeyType::String))
+///         .with_sort_key(KeyAttribute::new("placed", KeyType::Number)),
+/// );
+///
+/// let key_condition = Path::new_name("id").key().equal("order-1");
+/// assert_eq!(None, schema.index_for(&key_condition)?);
+///
+/// let key_condition = Path::new_name("customer").key().equal("customer-1");
+/// assert_eq!(Some("by-customer"), schema.index_for(&key_condition)?);
+/// #
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/SecondaryIndexes.html
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableSchema {
+    pub(crate) table_name: String,
+    pub(crate) key_schema: KeySchema,
+    pub(crate) local_secondary_indexes: Vec<Index>,
+    pub(crate) global_secondary_indexes: Vec<Index>,
+}
+
+impl TableSchema {
+    /// A schema for the table with this name and key schema, with no
+    /// secondary indexes.
+    pub fn new<T>(table_name: T, key_schema: KeySchema) -> Self
+    where
+        T: Into<String>,
+    {
+        Self {
+            table_name: table_name.into(),
+            key_schema,
+            local_secondary_indexes: Vec::new(),
+            global_secondary_indexes: Vec::new(),
+        }
+    }
+
+    /// Add a [global secondary index][1] to the table.
+    ///
+    /// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/GSI.html
+    pub fn with_global_secondary_index<T>(mut self, index_name: T, key_schema: KeySchema) -> Self
+    where
+        T: Into<String>,
+    {
+        self.global_secondary_indexes.push(Index {
+            name: index_name.into(),
+            key_schema,
+        });
+
+        self
+    }
+
+    /// Add a [local secondary index][1] to the table. A local secondary index
+    /// shares the table's partition key, so only its sort key is needed.
+    ///
+    /// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/LSI.html
+    pub fn with_local_secondary_index<T>(mut self, index_name: T, sort_key: KeyAttribute) -> Self
+    where
+        T: Into<String>,
+    {
+        self.local_secondary_indexes.push(Index {
+            name: index_name.into(),
+            key_schema: KeySchema::new(self.key_schema.partition_key.clone())
+                .with_sort_key(sort_key),
+        });
+
+        self
+    }
+
+    /// The name of the table.
+    pub fn table_name(&self) -> &str {
+        &self.table_name
+    }
+
+    /// The key schema of the table itself.
+    pub fn key_schema(&self) -> &KeySchema {
+        &self.key_schema
+    }
+
+    /// The local secondary indexes, in the order they were added.
+    pub fn local_secondary_indexes(&self) -> &[Index] {
+        &self.local_secondary_indexes
+    }
+
+    /// The global secondary indexes, in the order they were added.
+    pub fn global_secondary_indexes(&self) -> &[Index] {
+        &self.global_secondary_indexes
+    }
+
+    /// Finds where the [`KeyCondition`] can be used to query.
+    ///
+    /// Returns `Ok(None)` if it's valid for the table's own key,
+    /// `Ok(Some(index_name))` if it's valid for a secondary index, or an error
+    /// describing why DynamoDB would reject it. The table is preferred, then
+    /// local secondary indexes, then global secondary indexes, each in the
+    /// order they were added.
+    pub fn index_for(
+        &self,
+        key_condition: &KeyCondition,
+    ) -> Result<Option<&str>, KeyConditionError> {
+        let parts = key_parts(&key_condition.condition)?;
+
+        let candidates = [(None, &self.key_schema)].into_iter().chain(
+            self.local_secondary_indexes
+                .iter()
+                .chain(&self.global_secondary_indexes)
+                .map(|index| (Some(index.name.as_str()), &index.key_schema)),
+        );
+
+        let mut error = None;
+        for (index_name, key_schema) in candidates {
+            match key_schema.check(&parts) {
+                Ok(()) => return Ok(index_name),
+                Err(err) => {
+                    // Keep the first error about a key schema the condition
+                    // otherwise lines up with. That's more useful than
+                    // reporting that nothing matched.
+                    if error.is_none() && !err.is_name_mismatch() {
+                        error = Some(err);
+                    }
+                }
+            }
+        }
+
+        Err(error.unwrap_or(KeyConditionError::NoMatchingIndex))
+    }
+
+    /// Checks the [`KeyCondition`] against this schema and builds a [`Query`][1]
+    /// with the table name, and the index name if a secondary index is needed.
+    ///
+    /// Any other parts of the query expression (filter, projection) come from
+    /// the [`Builder`].
+    ///
+    /// ```no_run
+    /// # async fn example_query(
+    /// #     client: &aws_sdk_dynamodb::Client,
+    /// # ) -> Result<(), Box<dyn std::error::Error>> {
+    /// use dynamodb_expression::{
+    ///     key::{KeyAttribute, KeySchema, KeyType, TableSchema},
+    ///     Expression, Path,
+    /// };
+    ///
+    /// let schema = TableSchema::new(
+    ///     "orders",
+    ///     KeySchema::new(KeyAttribute::new("id", KeyType::String)),
+    /// )
+    /// .with_global_secondary_index(
+    ///     "by-customer",
+    ///     KeySchema::new(KeyAttribute::new("customer", KeyType::String)),
+    /// );
+    ///
+    /// let output = schema
+    ///     .query(
+    ///         client,
+    ///         Path::new_name("customer").key().equal("customer-1"),
+    ///         Expression::builder().with_projection(["id", "total"]),
+    ///     )?
+    ///     .send()
+    ///     .await?;
+    /// # _ = output;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/APIReference/API_Query.html
+    pub fn query<T>(
+        &self,
+        client: &Client,
+        key_condition: T,
+        builder: Builder,
+    ) -> Result<QueryFluentBuilder, KeyConditionError>
+    where
+        T: Into<KeyCondition>,
+    {
+        let key_condition = key_condition.into();
+        let index_name = self.index_for(&key_condition)?.map(String::from);
+
+        Ok(builder
+            .with_key_condition(key_condition)
+            .build()
+            .query(client)
+            .table_name(&self.table_name)
+            .set_index_name(index_name))
+    }
+}
+
+/// The reasons DynamoDB would reject a [`KeyCondition`].
+///
+/// See: [`KeySchema::validate`], [`TableSchema::index_for`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeyConditionError {
+    /// Key conditions may only combine conditions with `AND`.
+    LogicalOperator(&'static str),
+
+    /// The condition isn't one DynamoDB allows in a key condition.
+    UnsupportedCondition(String),
+
+    /// Key attributes are top-level attributes, so the path must be a single name.
+    InvalidKeyPath(Path),
+
+    /// There can be at most one condition on the partition key and one on the
+    /// sort key.
+    TooManyConditions(usize),
+
+    /// There was more than one condition on the same key attribute.
+    DuplicateKey(Name),
+
+    /// The condition uses an attribute that isn't part of the key.
+    UnknownKey(Name),
+
+    /// There was no equality condition on the partition key.
+    MissingPartitionKey,
+
+    /// The partition key was used with something other than `=`.
+    PartitionKeyNotEqual(Name),
+
+    /// `begins_with` was used on a number key.
+    BeginsWithType(Name),
+
+    /// A value didn't match the type of the key attribute.
+    TypeMismatch { name: Name, expected: KeyType },
+
+    /// Neither the table nor any of its indexes have keys matching the condition.
+    NoMatchingIndex,
+}
+
+impl KeyConditionError {
+    fn is_name_mismatch(&self) -> bool {
+        matches!(self, Self::UnknownKey(_) | Self::MissingPartitionKey)
+    }
+}
+
+impl fmt::Display for KeyConditionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::LogicalOperator(op) => {
+                write!(f, "{op} is not allowed in a key condition")
+            }
+            Self::UnsupportedCondition(condition) => {
+                write!(f, "unsupported key condition: {condition}")
+            }
+            Self::InvalidKeyPath(path) => {
+                write!(f, "key attribute must be a top-level attribute: {path}")
+            }
+            Self::TooManyConditions(count) => {
+                write!(
+                    f,
+                    "a key condition allows at most 2 conditions, found {count}"
+                )
+            }
+            Self::DuplicateKey(name) => {
+                write!(f, "more than one condition on key attribute {name}")
+            }
+            Self::UnknownKey(name) => write!(f, "{name} is not a key attribute"),
+            Self::MissingPartitionKey => {
+                f.write_str("key condition must test the partition key for equality")
+            }
+            Self::PartitionKeyNotEqual(name) => {
+                write!(f, "partition key {name} may only be tested for equality")
+            }
+            Self::BeginsWithType(name) => {
+                write!(f, "begins_with cannot be used on number key {name}")
+            }
+            Self::TypeMismatch { name, expected } => {
+                write!(f, "key attribute {name} must have type {expected}")
+            }
+            Self::NoMatchingIndex => f.write_str("no table or index key matches the key condition"),
+        }
+    }
+}
+
+impl Error for KeyConditionError {}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use crate::{value::Ref, Num, Path};
+
+    use super::{KeyAttribute, KeyConditionError, KeySchema, KeyType, TableSchema};
+
+    fn schema() -> TableSchema {
+        TableSchema::new(
+            "orders",
+            KeySchema::new(KeyAttribute::new("id", KeyType::String))
+                .with_sort_key(KeyAttribute::new("sk", KeyType::String)),
+        )
+        .with_local_secondary_index("by-total", KeyAttribute::new("total", KeyType::Number))
+        .with_global_secondary_index(
+            "by-customer",
+            KeySchema::new(KeyAttribute::new("customer", KeyType::String))
+                .with_sort_key(KeyAttribute::new("placed", KeyType::Number)),
+        )
+    }
+
+    fn key(name: &str) -> crate::key::Key {
+        Path::new_name(name).key()
+    }
+
+    #[test]
+    fn picks_index() {
+        let schema = schema();
+
+        assert_eq!(Ok(None), schema.index_for(&key("id").equal("a")));
+        assert_eq!(
+            Ok(None),
+            schema.index_for(&key("id").equal("a").and(key("sk").begins_with("b")))
+        );
+        assert_eq!(
+            Ok(Some("by-total")),
+            schema.index_for(
+                &key("id")
+                    .equal("a")
+                    .and(key("total").between(Num::new(1), Num::new(5)))
+            )
+        );
+        assert_eq!(
+            Ok(Some("by-customer")),
+            schema.index_for(
+                &key("placed")
+                    .less_than(Ref::new("placed"))
+                    .and(key("customer").equal(Ref::new("customer")))
+            )
+        );
+    }
+
+    #[test]
+    fn conditions_from_key_schema() {
+        let key_schema = schema().key_schema;
+
+        assert_eq!(
+            "attribute_not_exists(id) AND attribute_not_exists(sk)",
+            key_schema.put_if_absent().to_string()
+        );
+        assert_eq!(
+            "attribute_exists(id) AND attribute_exists(sk)",
+            key_schema.update_if_exists().to_string()
+        );
+        assert_eq!(
+            "attribute_exists(id) AND attribute_exists(sk) AND version = 3",
+            key_schema
+                .delete_if_matches([(Path::new_name("version"), Num::new(3))])
+                .to_string()
+        );
+
+        // With nothing to match, it's only required that the item exists.
+        assert_eq!(
+            key_schema.update_if_exists(),
+            key_schema.delete_if_matches(Vec::<(Path, Num)>::new())
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_conditions() {
+        let schema = schema();
+
+        assert_eq!(
+            Err(KeyConditionError::LogicalOperator("OR")),
+            schema.index_for(&crate::key::KeyCondition {
+                condition: Path::new_name("id")
+                    .equal("a")
+                    .or(Path::new_name("id").equal("b")),
+            })
+        );
+        assert_eq!(
+            Err(KeyConditionError::DuplicateKey("id".into())),
+            schema.index_for(&key("id").equal("a").and(key("id").equal("b")))
+        );
+        assert_eq!(
+            Err(KeyConditionError::TooManyConditions(3)),
+            schema.index_for(
+                &key("id")
+                    .equal("a")
+                    .and(key("sk").equal("b"))
+                    .and(key("total").equal(Num::new(1)))
+            )
+        );
+        assert_eq!(
+            Err(KeyConditionError::MissingPartitionKey),
+            schema.index_for(&key("sk").greater_than("b"))
+        );
+        assert_eq!(
+            Err(KeyConditionError::PartitionKeyNotEqual("id".into())),
+            schema.index_for(&key("id").greater_than("a").and(key("sk").equal("b")))
+        );
+        assert_eq!(
+            Err(KeyConditionError::BeginsWithType("total".into())),
+            schema.index_for(&key("id").equal("a").and(key("total").begins_with("1")))
+        );
+        assert_eq!(
+            Err(KeyConditionError::TypeMismatch {
+                name: "id".into(),
+                expected: KeyType::String,
+            }),
+            schema.index_for(&key("id").equal(Num::new(1)))
+        );
+        assert_eq!(
+            Err(KeyConditionError::NoMatchingIndex),
+            schema.index_for(&key("other").equal("a"))
+        );
+        assert_eq!(
+            Err(KeyConditionError::InvalidKeyPath(
+                "id.nested".parse().unwrap()
+            )),
+            schema.index_for(&"id.nested".parse::<Path>().unwrap().key().equal("a"))
+        );
+    }
+}
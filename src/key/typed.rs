@@ -0,0 +1,504 @@
+use core::fmt;
+
+use crate::{
+    condition::{
+        equal, greater_than, greater_than_or_equal, less_than, less_than_or_equal, BeginsWith,
+        Between, Condition,
+    },
+    operand::OperandType,
+    path::{Name, Path},
+    value::{Num, Ref, Scalar, ValueOrRef},
+};
+
+use super::{KeyCondition, KeyConditionError};
+
+/// The partition key of a table or index, for building a key condition that
+/// DynamoDB is guaranteed to accept.
+///
+/// The partition key can only be tested for equality. Add a condition on the
+/// sort key with [`PartitionKeyCondition::and_sort`].
+///
+/// See also: [`Key::partition`], [`SortKey`]
+///
+/// ```
+/// use dynamodb_expression::{key::Key, Num};
+/// # use pretty_assertions::assert_eq;
+///
+/// let key_condition = Key::partition("id").equal("order-1");
+/// assert_eq!(r#"id = "order-1""#, key_condition.to_string());
+///
+/// let key_condition = Key::partition("id")
+///     .equal("order-1")
+///     .and_sort(Key::sort("line").between(Num::new(1), Num::new(10)))
+///     .unwrap();
+/// assert_eq!(
+///     r#"id = "order-1" AND line BETWEEN 1 AND 10"#,
+///     key_condition.to_string()
+/// );
+/// ```
+///
+/// [`Key::partition`]: super::Key::partition
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartitionKey {
+    name: Name,
+}
+
+impl PartitionKey {
+    /// The partition key with this attribute name.
+    pub fn new<T>(name: T) -> Self
+    where
+        T: Into<Name>,
+    {
+        Self { name: name.into() }
+    }
+
+    /// A comparison that the partition key is equal to the provided value.
+    pub fn equal<T>(self, value: T) -> PartitionKeyCondition
+    where
+        T: Into<KeyValue>,
+    {
+        PartitionKeyCondition {
+            name: self.name.clone(),
+            condition: equal(Path::from(self.name), value.into()).into(),
+        }
+    }
+}
+
+/// The sort key of a table or index, for building a key condition that
+/// DynamoDB is guaranteed to accept.
+///
+/// See also: [`Key::sort`], [`PartitionKeyCondition::and_sort`]
+///
+/// [`Key::sort`]: super::Key::sort
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SortKey {
+    name: Name,
+}
+
+impl SortKey {
+    /// The sort key with this attribute name.
+    pub fn new<T>(name: T) -> Self
+    where
+        T: Into<Name>,
+    {
+        Self { name: name.into() }
+    }
+
+    /// The [DynamoDB `begins_with` function][1]. True if the sort key begins
+    /// with the prefix.
+    ///
+    /// DynamoDB only allows `begins_with` on string and binary keys, so the
+    /// prefix can't be a number.
+    ///
+    /// ```
+    /// use dynamodb_expression::{key::Key, value::Ref};
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let sort_key_condition = Key::sort("category").begins_with("hardware.");
+    /// assert_eq!(
+    ///     r#"begins_with(category, "hardware.")"#,
+    ///     sort_key_condition.to_string()
+    /// );
+    ///
+    /// let sort_key_condition = Key::sort("category").begins_with(Ref::new("prefix"));
+    /// assert_eq!("begins_with(category, :prefix)", sort_key_condition.to_string());
+    /// ```
+    ///
+    /// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/Expressions.OperatorsAndFunctions.html#Expressions.OperatorsAndFunctions.Functions
+    pub fn begins_with<T>(self, prefix: T) -> SortKeyCondition
+    where
+        T: Into<KeyPrefix>,
+    {
+        SortKeyCondition {
+            name: self.name.clone(),
+            condition: BeginsWith {
+                path: self.name.into(),
+                substr: prefix.into().into(),
+            }
+            .into(),
+        }
+    }
+
+    /// The [DynamoDB `BETWEEN` operator][1]. True if the sort key is greater
+    /// than or equal to `lower`, and less than or equal to `upper`.
+    ///
+    /// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/Expressions.OperatorsAndFunctions.html#Expressions.OperatorsAndFunctions.Comparators
+    pub fn between<L, U>(self, lower: L, upper: U) -> SortKeyCondition
+    where
+        L: Into<KeyValue>,
+        U: Into<KeyValue>,
+    {
+        SortKeyCondition {
+            name: self.name.clone(),
+            condition: Between {
+                op: Path::from(self.name).into(),
+                lower: lower.into().into(),
+                upper: upper.into().into(),
+            }
+            .into(),
+        }
+    }
+
+    /// A simple comparison that the sort key is equal to the provided value.
+    pub fn equal<T>(self, value: T) -> SortKeyCondition
+    where
+        T: Into<KeyValue>,
+    {
+        SortKeyCondition {
+            name: self.name.clone(),
+            condition: equal(Path::from(self.name), value.into()).into(),
+        }
+    }
+
+    /// A simple comparison that the sort key is greater than the provided
+    /// value.
+    pub fn greater_than<T>(self, value: T) -> SortKeyCondition
+    where
+        T: Into<KeyValue>,
+    {
+        SortKeyCondition {
+            name: self.name.clone(),
+            condition: greater_than(Path::from(self.name), value.into()).into(),
+        }
+    }
+
+    /// A simple comparison that the sort key is greater than or equal to the
+    /// provided value.
+    pub fn greater_than_or_equal<T>(self, value: T) -> SortKeyCondition
+    where
+        T: Into<KeyValue>,
+    {
+        SortKeyCondition {
+            name: self.name.clone(),
+            condition: greater_than_or_equal(Path::from(self.name), value.into()).into(),
+        }
+    }
+
+    /// A simple comparison that the sort key is less than the provided value.
+    pub fn less_than<T>(self, value: T) -> SortKeyCondition
+    where
+        T: Into<KeyValue>,
+    {
+        SortKeyCondition {
+            name: self.name.clone(),
+            condition: less_than(Path::from(self.name), value.into()).into(),
+        }
+    }
+
+    /// A simple comparison that the sort key is less than or equal to the
+    /// provided value.
+    pub fn less_than_or_equal<T>(self, value: T) -> SortKeyCondition
+    where
+        T: Into<KeyValue>,
+    {
+        SortKeyCondition {
+            name: self.name.clone(),
+            condition: less_than_or_equal(Path::from(self.name), value.into()).into(),
+        }
+    }
+}
+
+/// An equality condition on the partition key. Use it as a [`KeyCondition`]
+/// on its own, or add a sort key condition with [`Self::and_sort`].
+#[must_use = "Use in a DynamoDB expression with \
+    `Expression::builder().with_key_condition(key_condition)`"]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartitionKeyCondition {
+    name: Name,
+    condition: Condition,
+}
+
+impl PartitionKeyCondition {
+    /// Combine this with a condition on the sort key.
+    ///
+    /// Returns [`KeyConditionError::DuplicateKey`] if the sort key has the
+    /// same name as the partition key, which DynamoDB would reject.
+    ///
+    /// ```
+    /// use dynamodb_expression::key::{Key, KeyConditionError};
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// assert_eq!(
+    ///     Err(KeyConditionError::DuplicateKey("id".into())),
+    ///     Key::partition("id")
+    ///         .equal("order-1")
+    ///         .and_sort(Key::sort("id").begins_with("order-")),
+    /// );
+    /// ```
+    pub fn and_sort(
+        self,
+        sort_key_condition: SortKeyCondition,
+    ) -> Result<KeyCondition, KeyConditionError> {
+        if sort_key_condition.name == self.name {
+            return Err(KeyConditionError::DuplicateKey(self.name));
+        }
+
+        Ok(KeyCondition {
+            condition: self.condition.and(sort_key_condition.condition),
+        })
+    }
+}
+
+impl fmt::Display for PartitionKeyCondition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.condition.fmt(f)
+    }
+}
+
+impl From<PartitionKeyCondition> for KeyCondition {
+    fn from(partition_key_condition: PartitionKeyCondition) -> Self {
+        Self {
+            condition: partition_key_condition.condition,
+        }
+    }
+}
+
+/// A condition on the sort key. Combine it with a partition key condition
+/// using [`PartitionKeyCondition::and_sort`].
+#[must_use = "Combine with a partition key condition using `.and_sort()`"]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SortKeyCondition {
+    name: Name,
+    condition: Condition,
+}
+
+impl fmt::Display for SortKeyCondition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.condition.fmt(f)
+    }
+}
+
+/// A value that can be compared to a key attribute. Key attributes can only
+/// be [strings, numbers, or binary][1], or it can be a reference to an
+/// expression attribute value.
+///
+/// ```
+/// use dynamodb_expression::{key::KeyValue, value::Ref, Num};
+///
+/// let value: KeyValue = "a string value".into();
+/// let value: KeyValue = Num::new(42).into();
+/// let value: KeyValue = b"binary".into();
+/// let value: KeyValue = Ref::new("expression_value").into();
+/// ```
+///
+/// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/HowItWorks.CoreComponents.html#HowItWorks.CoreComponents.PrimaryKey
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeyValue {
+    String(String),
+    Num(Num),
+    Binary(Vec<u8>),
+    Ref(Ref),
+}
+
+impl From<String> for KeyValue {
+    fn from(value: String) -> Self {
+        Self::String(value)
+    }
+}
+
+impl From<&String> for KeyValue {
+    fn from(value: &String) -> Self {
+        Self::String(value.to_owned())
+    }
+}
+
+impl From<&str> for KeyValue {
+    fn from(value: &str) -> Self {
+        Self::String(value.to_owned())
+    }
+}
+
+impl From<&&str> for KeyValue {
+    fn from(value: &&str) -> Self {
+        Self::String((*value).to_owned())
+    }
+}
+
+impl From<Num> for KeyValue {
+    fn from(value: Num) -> Self {
+        Self::Num(value)
+    }
+}
+
+impl From<Vec<u8>> for KeyValue {
+    fn from(value: Vec<u8>) -> Self {
+        Self::Binary(value)
+    }
+}
+
+impl From<&[u8]> for KeyValue {
+    fn from(value: &[u8]) -> Self {
+        Self::Binary(value.to_vec())
+    }
+}
+
+impl<const N: usize> From<[u8; N]> for KeyValue {
+    fn from(value: [u8; N]) -> Self {
+        Self::Binary(value.to_vec())
+    }
+}
+
+impl<const N: usize> From<&[u8; N]> for KeyValue {
+    fn from(value: &[u8; N]) -> Self {
+        Self::Binary(value.to_vec())
+    }
+}
+
+impl From<Ref> for KeyValue {
+    fn from(value: Ref) -> Self {
+        Self::Ref(value)
+    }
+}
+
+impl From<KeyValue> for ValueOrRef {
+    fn from(value: KeyValue) -> Self {
+        match value {
+            KeyValue::String(value) => Scalar::String(value).into(),
+            KeyValue::Num(value) => Scalar::Num(value).into(),
+            KeyValue::Binary(value) => Scalar::Binary(value).into(),
+            KeyValue::Ref(value) => value.into(),
+        }
+    }
+}
+
+impl From<KeyValue> for OperandType {
+    fn from(value: KeyValue) -> Self {
+        Self::Scalar(value.into())
+    }
+}
+
+/// A prefix for the [`begins_with` function][1] on a sort key. DynamoDB only
+/// allows `begins_with` on string and binary keys.
+///
+/// ```
+/// use dynamodb_expression::{key::KeyPrefix, value::Ref};
+///
+/// let prefix: KeyPrefix = "a string prefix".into();
+/// let prefix: KeyPrefix = b"binary".into();
+/// let prefix: KeyPrefix = Ref::new("prefix").into();
+/// ```
+///
+/// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/Expressions.OperatorsAndFunctions.html#Expressions.OperatorsAndFunctions.Functions
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeyPrefix {
+    String(String),
+    Binary(Vec<u8>),
+    Ref(Ref),
+}
+
+impl From<String> for KeyPrefix {
+    fn from(value: String) -> Self {
+        Self::String(value)
+    }
+}
+
+impl From<&String> for KeyPrefix {
+    fn from(value: &String) -> Self {
+        Self::String(value.to_owned())
+    }
+}
+
+impl From<&str> for KeyPrefix {
+    fn from(value: &str) -> Self {
+        Self::String(value.to_owned())
+    }
+}
+
+impl From<&&str> for KeyPrefix {
+    fn from(value: &&str) -> Self {
+        Self::String((*value).to_owned())
+    }
+}
+
+impl From<Vec<u8>> for KeyPrefix {
+    fn from(value: Vec<u8>) -> Self {
+        Self::Binary(value)
+    }
+}
+
+impl From<&[u8]> for KeyPrefix {
+    fn from(value: &[u8]) -> Self {
+        Self::Binary(value.to_vec())
+    }
+}
+
+impl<const N: usize> From<[u8; N]> for KeyPrefix {
+    fn from(value: [u8; N]) -> Self {
+        Self::Binary(value.to_vec())
+    }
+}
+
+impl<const N: usize> From<&[u8; N]> for KeyPrefix {
+    fn from(value: &[u8; N]) -> Self {
+        Self::Binary(value.to_vec())
+    }
+}
+
+impl From<Ref> for KeyPrefix {
+    fn from(value: Ref) -> Self {
+        Self::Ref(value)
+    }
+}
+
+impl From<KeyPrefix> for ValueOrRef {
+    fn from(value: KeyPrefix) -> Self {
+        match value {
+            KeyPrefix::String(value) => Scalar::String(value).into(),
+            KeyPrefix::Binary(value) => Scalar::Binary(value).into(),
+            KeyPrefix::Ref(value) => value.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        key::{Key, KeyConditionError},
+        value::Ref,
+        Num,
+    };
+
+    #[test]
+    fn partition_only() {
+        let key_condition = Key::partition("id").equal(Ref::new("id"));
+        assert_eq!("id = :id", key_condition.to_string());
+    }
+
+    #[test]
+    fn sort_key_conditions() {
+        let partition = || Key::partition("pk").equal("a");
+
+        for (sort_key_condition, expected) in [
+            (Key::sort("sk").equal(Num::new(1)), "sk = 1"),
+            (Key::sort("sk").less_than(Num::new(1)), "sk < 1"),
+            (Key::sort("sk").less_than_or_equal(Num::new(1)), "sk <= 1"),
+            (Key::sort("sk").greater_than("b"), r#"sk > "b""#),
+            (Key::sort("sk").greater_than_or_equal("b"), r#"sk >= "b""#),
+            (Key::sort("sk").begins_with("b"), r#"begins_with(sk, "b")"#),
+            (
+                Key::sort("sk").between(Ref::new("lo"), Ref::new("hi")),
+                "sk BETWEEN :lo AND :hi",
+            ),
+        ] {
+            assert_eq!(
+                format!(r#"pk = "a" AND {expected}"#),
+                partition()
+                    .and_sort(sort_key_condition)
+                    .unwrap()
+                    .to_string()
+            );
+        }
+    }
+
+    #[test]
+    fn same_key() {
+        assert_eq!(
+            Err(KeyConditionError::DuplicateKey("id".into())),
+            Key::partition("id")
+                .equal("a")
+                .and_sort(Key::sort("id").equal("b"))
+        );
+    }
+}
@@ -117,11 +117,29 @@ fn convert_av(av: AwsAv) -> RusotoAv {
 # }
 ```
 
+# Optional features
+
+* `arbitrary`: [`Arbitrary`][7] implementations that generate valid [`Path`],
+  [`Value`], [`Num`], `Condition`, `KeyCondition`, and `Update` trees, for
+  property-based testing and fuzzing.
+* `cursor`: Opaque, tamper-evident pagination tokens with the `cursor` module.
+* `items`: Convert whole items between `HashMap<String, AttributeValue>` and
+  [`Map`], and [`Value`] into `AttributeValue`.
+* `pagination`: Streams of `query` and `scan` results, across pages, with the
+  `pagination` module.
+* `rust_decimal`: Checked conversion from [`Num`] to `rust_decimal::Decimal`.
+* `serde`: Convert between [`Value`] and Rust types with the `value::serde` module.
+* `tracing`: [`tracing`][6] spans for sending requests built with
+  [`Expression`], and those sent by the `pagination` and [`batch`] helpers. See
+  the `trace` module.
+
 [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/Expressions.ExpressionAttributeNames.html
 [2]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/Expressions.ExpressionAttributeValues.html
 [3]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/Expressions.Attributes.html#Expressions.Attributes.NestedElements.DocumentPathExamples
 [4]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/Expressions.html
 [5]: https://docs.rs/rusoto_dynamodb/
+[6]: https://docs.rs/tracing/
+[7]: https://docs.rs/arbitrary/
 [`rusoto_dynamodb::AttributeValue`]: https://docs.rs/rusoto_dynamodb/latest/rusoto_dynamodb/struct.AttributeValue.html
 */
 
@@ -129,15 +147,27 @@ fn convert_av(av: AwsAv) -> RusotoAv {
 pub use ::aws_sdk_dynamodb;
 pub use ::num;
 
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
+pub mod batch;
+pub mod capacity;
 pub mod condition;
+#[cfg(feature = "cursor")]
+pub mod cursor;
 mod expression;
 pub mod key;
 pub mod operand;
+#[cfg(feature = "pagination")]
+pub mod pagination;
+pub mod partiql;
 pub mod path;
+pub mod redact;
+#[cfg(feature = "tracing")]
+pub mod trace;
 pub mod update;
 pub mod value;
 
-pub use expression::{Builder, Expression};
+pub use expression::{Builder, Explain, Expression};
 pub use path::Path;
 pub use value::{Map, Num, Scalar, Set, Value};
 
@@ -4,7 +4,7 @@ use crate::{
     condition::Condition,
     operand::Size,
     path::{Element, IndexedField, Name, Path},
-    value::{Num, Ref, Scalar, ValueOrRef},
+    value::{Num, Ref, Scalar, Value, ValueOrRef},
 };
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -119,6 +119,12 @@ impl FromIterator<u8> for OperandType {
     }
 }
 
+impl From<Value> for OperandType {
+    fn from(value: Value) -> Self {
+        Self::Scalar(value.into())
+    }
+}
+
 impl From<Ref> for OperandType {
     fn from(value: Ref) -> Self {
         Self::Scalar(value.into())
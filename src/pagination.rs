@@ -0,0 +1,713 @@
+//! Streams that page through [`query`][1] and [`scan`][2] results, handling
+//! `LastEvaluatedKey` and `ExclusiveStartKey` for you.
+//!
+//! Each function takes a fluent builder, which is typically created with
+//! [`Expression::query`] or [`Expression::scan`], and returns a [`Stream`].
+//! Use [`PageLimits`] to stop after a number of items or pages.
+//!
+//! With the `tracing` feature, each stream sends its requests in a
+//! `send_request` span (see the `trace` module), with values in the recorded
+//! expression redacted by the [`Redaction`] it's given. Without it, the
+//! [`Redaction`] isn't used.
+//!
+//! Requires the `pagination` feature.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! # async fn example() -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+//! use aws_config::BehaviorVersion;
+//! use aws_sdk_dynamodb::Client;
+//! use dynamodb_expression::{
+//!     pagination::{query_items, PageLimits},
+//!     redact::Redaction,
+//!     Expression, Num, Path,
+//! };
+//! use futures_util::TryStreamExt;
+//!
+//! let client = Client::new(&aws_config::load_defaults(BehaviorVersion::latest()).await);
+//!
+//! let query = Expression::builder()
+//!     .with_key_condition("id".parse::<Path>()?.key().equal(Num::new(42)))
+//!     .build()
+//!     .query(&client)
+//!     .table_name("people");
+//!
+//! let items: Vec<_> = query_items(query, PageLimits::new().with_max_items(500), &Redaction::all())
+//!     .try_collect()
+//!     .await?;
+//! #
+//! # _ = items;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/Query.Pagination.html
+//! [2]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/Scan.html#Scan.Pagination
+//! [`Expression::query`]: crate::Expression::query
+//! [`Expression::scan`]: crate::Expression::scan
+
+use core::{future::Future, num::NonZeroUsize};
+use std::collections::HashMap;
+
+use aws_sdk_dynamodb::{
+    error::SdkError,
+    operation::{
+        query::{builders::QueryFluentBuilder, QueryError, QueryOutput},
+        scan::{builders::ScanFluentBuilder, ScanError, ScanOutput},
+    },
+    types::AttributeValue,
+};
+use futures_util::{stream, Stream, StreamExt, TryStreamExt};
+
+use crate::redact::Redaction;
+#[cfg(feature = "tracing")]
+use crate::trace;
+
+/// An item, as returned by the AWS SDK.
+type Item = HashMap<String, AttributeValue>;
+
+/// Limits on how much a pagination stream will read.
+///
+/// These are in addition to the `limit` set on the request itself, which
+/// limits the number of items DynamoDB evaluates per page.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct PageLimits {
+    max_items: Option<usize>,
+    max_pages: Option<usize>,
+}
+
+impl PageLimits {
+    /// No limits. Reads until there are no more pages.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stop after this many items, total. For parallel scans, this is the
+    /// total across all segments.
+    pub fn with_max_items(mut self, max_items: usize) -> Self {
+        self.max_items = Some(max_items);
+        self
+    }
+
+    /// Stop after this many pages (requests). For parallel scans, this is
+    /// the limit for each segment.
+    pub fn with_max_pages(mut self, max_pages: usize) -> Self {
+        self.max_pages = Some(max_pages);
+        self
+    }
+}
+
+/// A stream of [`query`][1] result pages. Each page is a [`QueryOutput`].
+///
+/// Only [`PageLimits::with_max_pages`] applies here. See [`query_items`] to
+/// limit the number of items.
+///
+/// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/APIReference/API_Query.html
+pub fn query_pages(
+    builder: QueryFluentBuilder,
+    limits: PageLimits,
+    redaction: &Redaction,
+) -> impl Stream<Item = Result<QueryOutput, SdkError<QueryError>>> {
+    pages(builder, limits.max_pages, redaction)
+}
+
+/// A stream of the items from a [`query`][1], across all pages.
+///
+/// See the [module documentation](self) for an example.
+///
+/// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/APIReference/API_Query.html
+pub fn query_items(
+    builder: QueryFluentBuilder,
+    limits: PageLimits,
+    redaction: &Redaction,
+) -> impl Stream<Item = Result<Item, SdkError<QueryError>>> {
+    items(builder, limits, redaction)
+}
+
+/// A stream of [`scan`][1] result pages. Each page is a [`ScanOutput`].
+///
+/// Only [`PageLimits::with_max_pages`] applies here. See [`scan_items`] to
+/// limit the number of items.
+///
+/// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/APIReference/API_Scan.html
+pub fn scan_pages(
+    builder: ScanFluentBuilder,
+    limits: PageLimits,
+    redaction: &Redaction,
+) -> impl Stream<Item = Result<ScanOutput, SdkError<ScanError>>> {
+    pages(builder, limits.max_pages, redaction)
+}
+
+/// A stream of the items from a [`scan`][1], across all pages.
+///
+/// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/APIReference/API_Scan.html
+pub fn scan_items(
+    builder: ScanFluentBuilder,
+    limits: PageLimits,
+    redaction: &Redaction,
+) -> impl Stream<Item = Result<Item, SdkError<ScanError>>> {
+    items(builder, limits, redaction)
+}
+
+/// A stream of the items from a [parallel scan][1], split into
+/// `total_segments` segments, with up to `workers` segments scanned at a time.
+/// Items are yielded in the order they arrive, so items from different
+/// segments are interleaved.
+///
+/// Any `segment` or `total_segments` already set on the builder are replaced.
+///
+/// # Examples
+///
+/// ```no_run
+/// # async fn example() -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+/// use core::num::NonZeroUsize;
+///
+/// use aws_config::BehaviorVersion;
+/// use aws_sdk_dynamodb::Client;
+/// use dynamodb_expression::{
+///     pagination::{parallel_scan_items, PageLimits, TotalSegments},
+///     redact::Redaction,
+///     Expression, Num, Path,
+/// };
+/// use futures_util::TryStreamExt;
+///
+/// let client = Client::new(&aws_config::load_defaults(BehaviorVersion::latest()).await);
+///
+/// let scan = Expression::builder()
+///     .with_filter("age".parse::<Path>()?.greater_than_or_equal(Num::new(25)))
+///     .build()
+///     .scan(&client)
+///     .table_name("people");
+///
+/// let mut items = parallel_scan_items(
+///     scan,
+///     TotalSegments::new(16).unwrap(),
+///     NonZeroUsize::new(4).unwrap(),
+///     PageLimits::new(),
+///     &Redaction::all(),
+/// );
+/// while let Some(item) = items.try_next().await? {
+///     // ...
+/// #   _ = item;
+/// }
+/// #
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/Scan.html#Scan.ParallelScan
+pub fn parallel_scan_items(
+    builder: ScanFluentBuilder,
+    total_segments: TotalSegments,
+    workers: NonZeroUsize,
+    limits: PageLimits,
+    redaction: &Redaction,
+) -> impl Stream<Item = Result<Item, SdkError<ScanError>>> {
+    parallel_items(builder, total_segments, workers, limits, redaction)
+}
+
+/// The number of segments to split a [parallel scan][1] into: from 1 to
+/// 1,000,000, as DynamoDB allows. See [`parallel_scan_items`].
+///
+/// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/Scan.html#Scan.ParallelScan
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TotalSegments(i32);
+
+impl TotalSegments {
+    /// The most segments DynamoDB allows.
+    pub const MAX: u32 = 1_000_000;
+
+    /// Returns `None` if `total_segments` is zero, or more than [`Self::MAX`].
+    pub fn new(total_segments: u32) -> Option<Self> {
+        (1..=Self::MAX)
+            .contains(&total_segments)
+            .then_some(Self(total_segments as i32))
+    }
+
+    /// The number of segments.
+    pub fn get(self) -> u32 {
+        self.0 as u32
+    }
+}
+
+/// A request that returns paged results.
+trait Paginated: Clone {
+    type Output;
+    type Error;
+
+    fn with_start_key(self, key: Option<Item>) -> Self;
+
+    fn send_page(self) -> impl Future<Output = Result<Self::Output, SdkError<Self::Error>>>;
+
+    fn last_evaluated_key(output: &Self::Output) -> Option<&Item>;
+
+    fn into_items(output: Self::Output) -> Vec<Item>;
+
+    /// A `send_request` span for the pages of this request.
+    #[cfg(feature = "tracing")]
+    fn span(&self, _redaction: &Redaction) -> tracing::Span {
+        tracing::Span::none()
+    }
+
+    #[cfg(feature = "tracing")]
+    fn page(_output: &Self::Output) -> trace::Page {
+        trace::Page::default()
+    }
+}
+
+/// A request that can be split into segments, for a parallel scan.
+trait Segmented: Paginated {
+    fn with_segment(self, segment: i32, total_segments: i32) -> Self;
+}
+
+impl Paginated for QueryFluentBuilder {
+    type Output = QueryOutput;
+    type Error = QueryError;
+
+    fn with_start_key(self, key: Option<Item>) -> Self {
+        self.set_exclusive_start_key(key)
+    }
+
+    fn send_page(self) -> impl Future<Output = Result<Self::Output, SdkError<Self::Error>>> {
+        self.send()
+    }
+
+    fn last_evaluated_key(output: &Self::Output) -> Option<&Item> {
+        output.last_evaluated_key()
+    }
+
+    fn into_items(output: Self::Output) -> Vec<Item> {
+        output.items.unwrap_or_default()
+    }
+
+    #[cfg(feature = "tracing")]
+    fn span(&self, redaction: &Redaction) -> tracing::Span {
+        trace::span(self, redaction)
+    }
+
+    #[cfg(feature = "tracing")]
+    fn page(output: &Self::Output) -> trace::Page {
+        <Self as trace::Request>::page(output)
+    }
+}
+
+impl Paginated for ScanFluentBuilder {
+    type Output = ScanOutput;
+    type Error = ScanError;
+
+    fn with_start_key(self, key: Option<Item>) -> Self {
+        self.set_exclusive_start_key(key)
+    }
+
+    fn send_page(self) -> impl Future<Output = Result<Self::Output, SdkError<Self::Error>>> {
+        self.send()
+    }
+
+    fn last_evaluated_key(output: &Self::Output) -> Option<&Item> {
+        output.last_evaluated_key()
+    }
+
+    fn into_items(output: Self::Output) -> Vec<Item> {
+        output.items.unwrap_or_default()
+    }
+
+    #[cfg(feature = "tracing")]
+    fn span(&self, redaction: &Redaction) -> tracing::Span {
+        trace::span(self, redaction)
+    }
+
+    #[cfg(feature = "tracing")]
+    fn page(output: &Self::Output) -> trace::Page {
+        <Self as trace::Request>::page(output)
+    }
+}
+
+impl Segmented for ScanFluentBuilder {
+    fn with_segment(self, segment: i32, total_segments: i32) -> Self {
+        self.segment(segment).total_segments(total_segments)
+    }
+}
+
+/// The state of a pagination stream.
+struct State {
+    position: Position,
+    pages: usize,
+    #[cfg(feature = "tracing")]
+    sent: trace::Sent,
+}
+
+/// Where a pagination stream is.
+enum Position {
+    /// The first page hasn't been requested yet.
+    Start,
+    /// The next page starts after this key.
+    After(Item),
+    /// There are no more pages, or the page limit was reached.
+    Done,
+}
+
+fn pages<B>(
+    builder: B,
+    max_pages: Option<usize>,
+    #[cfg_attr(not(feature = "tracing"), allow(unused_variables))] redaction: &Redaction,
+) -> impl Stream<Item = Result<B::Output, SdkError<B::Error>>>
+where
+    B: Paginated,
+{
+    let state = State {
+        position: Position::Start,
+        pages: 0,
+        #[cfg(feature = "tracing")]
+        sent: trace::Sent::new(builder.span(redaction)),
+    };
+
+    stream::try_unfold(state, move |state| {
+        let builder = builder.clone();
+
+        async move {
+            let State {
+                position,
+                pages,
+                #[cfg(feature = "tracing")]
+                mut sent,
+            } = state;
+
+            let start_key = match position {
+                Position::Done => return Ok(None),
+                _ if max_pages.is_some_and(|max_pages| pages >= max_pages) => return Ok(None),
+                Position::Start => None,
+                Position::After(key) => Some(key),
+            };
+
+            let send = builder.with_start_key(start_key).send_page();
+            #[cfg(feature = "tracing")]
+            let send = tracing::Instrument::instrument(send, sent.span().clone());
+            let output = send.await?;
+
+            #[cfg(feature = "tracing")]
+            sent.record(B::page(&output));
+
+            // The page with no `LastEvaluatedKey` is the last one, but it
+            // may still have items.
+            let position = match B::last_evaluated_key(&output) {
+                Some(key) if !key.is_empty() => Position::After(key.clone()),
+                _ => Position::Done,
+            };
+
+            Ok(Some((
+                output,
+                State {
+                    position,
+                    pages: pages + 1,
+                    #[cfg(feature = "tracing")]
+                    sent,
+                },
+            )))
+        }
+    })
+}
+
+fn items<B>(
+    builder: B,
+    limits: PageLimits,
+    redaction: &Redaction,
+) -> impl Stream<Item = Result<Item, SdkError<B::Error>>>
+where
+    B: Paginated,
+{
+    let items = pages(builder, limits.max_pages, redaction)
+        .map_ok(|output| stream::iter(B::into_items(output).into_iter().map(Ok)))
+        .try_flatten();
+
+    limit_items(items, limits.max_items)
+}
+
+fn parallel_items<B>(
+    builder: B,
+    total_segments: TotalSegments,
+    workers: NonZeroUsize,
+    limits: PageLimits,
+    redaction: &Redaction,
+) -> impl Stream<Item = Result<Item, SdkError<B::Error>>>
+where
+    B: Segmented,
+{
+    let TotalSegments(total) = total_segments;
+    let redaction = redaction.clone();
+
+    // Segments are only started as workers are free for them.
+    let segments = stream::iter(0..total).map(move |segment| {
+        Box::pin(items(
+            builder.clone().with_segment(segment, total),
+            PageLimits {
+                max_items: None,
+                ..limits
+            },
+            &redaction,
+        ))
+    });
+
+    limit_items(segments.flatten_unordered(workers.get()), limits.max_items)
+}
+
+fn limit_items<S>(items: S, max_items: Option<usize>) -> impl Stream<Item = S::Item>
+where
+    S: Stream,
+{
+    items.take(max_items.unwrap_or(usize::MAX))
+}
+
+#[cfg(test)]
+mod test {
+    use core::{
+        future::{ready, Future},
+        num::NonZeroUsize,
+    };
+    use std::{
+        collections::HashMap,
+        sync::{Arc, Mutex},
+    };
+
+    use aws_sdk_dynamodb::{
+        config::{BehaviorVersion, Region},
+        error::SdkError,
+        operation::query::QueryError,
+        types::AttributeValue,
+        Client, Config,
+    };
+    use futures_util::{StreamExt, TryStreamExt};
+    use pretty_assertions::assert_eq;
+
+    use crate::redact::Redaction;
+
+    use super::{
+        items, pages, parallel_items, parallel_scan_items, query_items, scan_pages, Item,
+        PageLimits, Paginated, Segmented, TotalSegments,
+    };
+
+    /// Serves canned pages, keyed by the `ExclusiveStartKey` for each. For a
+    /// parallel scan, each segment serves the same pages, with 100 times the
+    /// segment number added to each item.
+    #[derive(Clone)]
+    struct FakeRequest {
+        pages: Arc<Vec<Vec<u32>>>,
+        requests: Arc<Mutex<Vec<Option<u32>>>>,
+        start_key: Option<Item>,
+        segment: Option<(i32, i32)>,
+    }
+
+    impl FakeRequest {
+        fn new(pages: Vec<Vec<u32>>) -> Self {
+            Self {
+                pages: Arc::new(pages),
+                requests: Arc::default(),
+                start_key: None,
+                segment: None,
+            }
+        }
+
+        fn requests(&self) -> Vec<Option<u32>> {
+            self.requests.lock().unwrap().clone()
+        }
+    }
+
+    fn item(n: u32) -> Item {
+        HashMap::from([("n".into(), AttributeValue::N(n.to_string()))])
+    }
+
+    fn n(item: &Item) -> u32 {
+        item["n"].as_n().unwrap().parse().unwrap()
+    }
+
+    impl Paginated for FakeRequest {
+        /// The items on the page, and the `LastEvaluatedKey`.
+        type Output = (Vec<Item>, Option<Item>);
+        type Error = QueryError;
+
+        fn with_start_key(mut self, key: Option<Item>) -> Self {
+            self.start_key = key;
+            self
+        }
+
+        fn send_page(self) -> impl Future<Output = Result<Self::Output, SdkError<Self::Error>>> {
+            // The start key holds the index of the page to return.
+            let page = self.start_key.as_ref().map(n);
+            self.requests.lock().unwrap().push(page);
+
+            let page = page.unwrap_or(0) as usize;
+            let offset = self.segment.map_or(0, |(segment, _)| segment as u32 * 100);
+            let items = self.pages[page].iter().map(|n| item(n + offset)).collect();
+            let last_key = (page + 1 < self.pages.len()).then(|| item(page as u32 + 1));
+
+            ready(Ok((items, last_key)))
+        }
+
+        fn last_evaluated_key(output: &Self::Output) -> Option<&Item> {
+            output.1.as_ref()
+        }
+
+        fn into_items(output: Self::Output) -> Vec<Item> {
+            output.0
+        }
+    }
+
+    impl Segmented for FakeRequest {
+        fn with_segment(mut self, segment: i32, total_segments: i32) -> Self {
+            assert!(segment < total_segments);
+            self.segment = Some((segment, total_segments));
+            self
+        }
+    }
+
+    async fn collect_items(request: FakeRequest, limits: PageLimits) -> Vec<u32> {
+        items(request, limits, &Redaction::all())
+            .map_ok(|item| n(&item))
+            .try_collect()
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn all_pages() {
+        let request = FakeRequest::new(vec![vec![1, 2], vec![], vec![3], vec![4, 5]]);
+        assert_eq!(
+            vec![1, 2, 3, 4, 5],
+            collect_items(request.clone(), PageLimits::new()).await
+        );
+        assert_eq!(vec![None, Some(1), Some(2), Some(3)], request.requests());
+    }
+
+    #[tokio::test]
+    async fn single_page() {
+        let request = FakeRequest::new(vec![vec![1, 2, 3]]);
+        assert_eq!(
+            vec![1, 2, 3],
+            collect_items(request.clone(), PageLimits::new()).await
+        );
+        assert_eq!(vec![None], request.requests());
+    }
+
+    #[tokio::test]
+    async fn max_items() {
+        let request = FakeRequest::new(vec![vec![1, 2], vec![3, 4], vec![5, 6]]);
+        assert_eq!(
+            vec![1, 2, 3],
+            collect_items(request.clone(), PageLimits::new().with_max_items(3)).await
+        );
+
+        // Doesn't request pages it doesn't need.
+        assert_eq!(vec![None, Some(1)], request.requests());
+    }
+
+    #[tokio::test]
+    async fn max_pages() {
+        let request = FakeRequest::new(vec![vec![1, 2], vec![3, 4], vec![5, 6]]);
+        assert_eq!(
+            vec![1, 2, 3, 4],
+            collect_items(request.clone(), PageLimits::new().with_max_pages(2)).await
+        );
+        assert_eq!(vec![None, Some(1)], request.requests());
+
+        let request = FakeRequest::new(vec![vec![1, 2], vec![3, 4]]);
+        assert_eq!(
+            2,
+            pages(request, Some(5), &Redaction::all())
+                .collect::<Vec<_>>()
+                .await
+                .len()
+        );
+    }
+
+    #[tokio::test]
+    async fn parallel() {
+        async fn collect_items(
+            request: FakeRequest,
+            total_segments: u32,
+            workers: usize,
+            limits: PageLimits,
+        ) -> Vec<u32> {
+            parallel_items(
+                request,
+                TotalSegments::new(total_segments).unwrap(),
+                NonZeroUsize::new(workers).unwrap(),
+                limits,
+                &Redaction::all(),
+            )
+            .map_ok(|item| n(&item))
+            .try_collect()
+            .await
+            .unwrap()
+        }
+
+        // One worker scans one segment at a time, in order.
+        let request = FakeRequest::new(vec![vec![1, 2], vec![3]]);
+        assert_eq!(
+            vec![1, 2, 3, 101, 102, 103, 201, 202, 203],
+            collect_items(request.clone(), 3, 1, PageLimits::new()).await
+        );
+        assert_eq!(6, request.requests().len());
+
+        let request = FakeRequest::new(vec![vec![1, 2], vec![3]]);
+        let mut items = collect_items(request.clone(), 3, 2, PageLimits::new()).await;
+        items.sort_unstable();
+        assert_eq!(vec![1, 2, 3, 101, 102, 103, 201, 202, 203], items);
+
+        // The page limit is for each segment.
+        let request = FakeRequest::new(vec![vec![1, 2], vec![3]]);
+        let mut items =
+            collect_items(request.clone(), 2, 2, PageLimits::new().with_max_pages(1)).await;
+        items.sort_unstable();
+        assert_eq!(vec![1, 2, 101, 102], items);
+
+        // The item limit is across all segments, and segments that aren't
+        // needed aren't scanned.
+        let request = FakeRequest::new(vec![vec![1, 2], vec![3]]);
+        assert_eq!(
+            vec![1, 2, 3, 101],
+            collect_items(request.clone(), 3, 1, PageLimits::new().with_max_items(4)).await
+        );
+        assert_eq!(3, request.requests().len());
+    }
+
+    #[test]
+    fn total_segments() {
+        assert_eq!(None, TotalSegments::new(0));
+        assert_eq!(Some(1), TotalSegments::new(1).map(TotalSegments::get));
+        assert_eq!(
+            Some(TotalSegments::MAX),
+            TotalSegments::new(TotalSegments::MAX).map(TotalSegments::get)
+        );
+        assert_eq!(None, TotalSegments::new(TotalSegments::MAX + 1));
+    }
+
+    #[test]
+    fn streams_are_send() {
+        fn assert_send<T: Send>(_: T) {}
+
+        let client = Client::from_conf(
+            Config::builder()
+                .behavior_version(BehaviorVersion::latest())
+                .region(Region::new("us-east-1"))
+                .build(),
+        );
+
+        assert_send(query_items(
+            client.query(),
+            PageLimits::new(),
+            &Redaction::all(),
+        ));
+        assert_send(scan_pages(
+            client.scan(),
+            PageLimits::new(),
+            &Redaction::all(),
+        ));
+        assert_send(parallel_scan_items(
+            client.scan(),
+            TotalSegments::new(4).unwrap(),
+            NonZeroUsize::new(2).unwrap(),
+            PageLimits::new(),
+            &Redaction::all(),
+        ));
+    }
+}
@@ -0,0 +1,361 @@
+//! [PartiQL][1] statements built from the same [`Condition`], [`Update`], and
+//! projection types used for DynamoDB expressions.
+//!
+//! Values become positional `?` parameters, returned in order in the
+//! [`Statement`] for use with [`ExecuteStatement`][2] or
+//! [`BatchExecuteStatement`][3]. Names are always quoted, so reserved words
+//! don't need special handling.
+//!
+//! Going the other way, [`parse_condition`] and [`parse_key_condition`] turn a
+//! PartiQL `WHERE` clause back into a [`Condition`] or [`KeyCondition`].
+//!
+//! # Examples
+//!
+//! ```
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! use aws_sdk_dynamodb::types::AttributeValue;
+//! use dynamodb_expression::{
+//!     partiql::{DeleteStatement, SelectStatement, UpdateStatement},
+//!     Num, Path,
+//! };
+//! # use pretty_assertions::assert_eq;
+//!
+//! let statement = SelectStatement::new("orders")
+//!     .with_index_name("by-customer")
+//!     .with_projection(["id".parse::<Path>()?, "total".parse()?])
+//!     .with_condition(
+//!         "customer"
+//!             .parse::<Path>()?
+//!             .equal("customer-1")
+//!             .and("total".parse::<Path>()?.greater_than(Num::new(100))),
+//!     )
+//!     .build()?;
+//! assert_eq!(
+//!     r#"SELECT "id", "total" FROM "orders"."by-customer" WHERE "customer" = ? AND "total" > ?"#,
+//!     statement.statement
+//! );
+//! assert_eq!(
+//!     vec![
+//!         AttributeValue::S("customer-1".into()),
+//!         AttributeValue::N("100".into()),
+//!     ],
+//!     statement.parameters
+//! );
+//!
+//! let statement = UpdateStatement::new(
+//!     "orders",
+//!     "status"
+//!         .parse::<Path>()?
+//!         .set("shipped")
+//!         .and("attempts".parse::<Path>()?.math().add(1))
+//!         .and("note".parse::<Path>()?.remove()),
+//!     "id".parse::<Path>()?.equal("order-1"),
+//! )
+//! .build()?;
+//! assert_eq!(
+//!     r#"UPDATE "orders" SET "status" = ? SET "attempts" = "attempts" + ? REMOVE "note" WHERE "id" = ?"#,
+//!     statement.statement
+//! );
+//!
+//! let statement = DeleteStatement::new("orders", "id".parse::<Path>()?.equal("order-1")).build()?;
+//! assert_eq!(r#"DELETE FROM "orders" WHERE "id" = ?"#, statement.statement);
+//! #
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/ql-reference.html
+//! [`KeyCondition`]: crate::key::KeyCondition
+//! [2]: https://docs.aws.amazon.com/amazondynamodb/latest/APIReference/API_ExecuteStatement.html
+//! [3]: https://docs.aws.amazon.com/amazondynamodb/latest/APIReference/API_BatchExecuteStatement.html
+
+mod parse;
+mod render;
+
+use core::fmt;
+use std::error::Error;
+
+use aws_sdk_dynamodb::{
+    operation::execute_statement::{
+        builders::{ExecuteStatementFluentBuilder, ExecuteStatementInputBuilder},
+        ExecuteStatementInput,
+    },
+    types::{AttributeValue, BatchStatementRequest},
+    Client,
+};
+use optempty::EmptyIntoNone;
+
+use crate::{condition::Condition, path::Path, update::Update, value::Ref};
+
+pub use self::parse::{parse_condition, parse_key_condition, PartiqlParseError};
+
+use self::render::Renderer;
+
+/// A PartiQL statement and its positional parameters.
+///
+/// See the [module documentation](self) for examples.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Statement {
+    /// The PartiQL statement, with a `?` for each parameter.
+    pub statement: String,
+
+    /// The values for each `?` in the statement, in order.
+    pub parameters: Vec<AttributeValue>,
+}
+
+impl Statement {
+    /// Uses this [`Statement`] to create an [`ExecuteStatementInputBuilder`]
+    /// with the statement and parameters set.
+    pub fn to_execute_statement_input_builder(self) -> ExecuteStatementInputBuilder {
+        ExecuteStatementInput::builder()
+            .statement(self.statement)
+            .set_parameters(Some(self.parameters).empty_into_none())
+    }
+
+    /// Sets up an [`ExecuteStatement`][1] using this [`Statement`].
+    ///
+    /// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/APIReference/API_ExecuteStatement.html
+    pub fn execute_statement(self, client: &Client) -> ExecuteStatementFluentBuilder {
+        client
+            .execute_statement()
+            .statement(self.statement)
+            .set_parameters(Some(self.parameters).empty_into_none())
+    }
+
+    /// Uses this [`Statement`] to create a [`BatchStatementRequest`], for use
+    /// with [`BatchExecuteStatement`][1].
+    ///
+    /// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/APIReference/API_BatchExecuteStatement.html
+    pub fn to_batch_statement_request(self) -> BatchStatementRequest {
+        BatchStatementRequest::builder()
+            .statement(self.statement)
+            .set_parameters(Some(self.parameters).empty_into_none())
+            .build()
+            .expect("statement is set")
+    }
+}
+
+impl fmt::Display for Statement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.statement)
+    }
+}
+
+/// Builds a PartiQL [`SELECT`][1] statement.
+///
+/// See the [module documentation](self) for examples.
+///
+/// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/ql-reference.select.html
+#[must_use = "Call `.build()` to create the `Statement`"]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SelectStatement {
+    table_name: String,
+    index_name: Option<String>,
+    projection: Vec<Path>,
+    condition: Option<Condition>,
+}
+
+impl SelectStatement {
+    /// Selects every attribute of every item in the table, until more is
+    /// added with the other methods.
+    pub fn new<T>(table_name: T) -> Self
+    where
+        T: Into<String>,
+    {
+        Self {
+            table_name: table_name.into(),
+            index_name: None,
+            projection: Vec::new(),
+            condition: None,
+        }
+    }
+
+    /// Selects from a secondary index of the table.
+    pub fn with_index_name<T>(mut self, index_name: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.index_name = Some(index_name.into());
+
+        self
+    }
+
+    /// The attributes to select, overwriting any previously set. With none,
+    /// every attribute is selected (`SELECT *`).
+    pub fn with_projection<I, T>(mut self, paths: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<Path>,
+    {
+        self.projection = paths.into_iter().map(Into::into).collect();
+
+        self
+    }
+
+    /// The `WHERE` clause, overwriting any previously set.
+    pub fn with_condition<T>(mut self, condition: T) -> Self
+    where
+        T: Into<Condition>,
+    {
+        self.condition = Some(condition.into());
+
+        self
+    }
+
+    /// Builds the [`Statement`].
+    pub fn build(self) -> Result<Statement, PartiqlError> {
+        let Self {
+            table_name,
+            index_name,
+            projection,
+            condition,
+        } = self;
+
+        let mut renderer = Renderer::default();
+
+        renderer.push_str("SELECT ");
+        if projection.is_empty() {
+            renderer.push_str("*");
+        } else {
+            for (i, path) in projection.iter().enumerate() {
+                if i > 0 {
+                    renderer.push_str(", ");
+                }
+
+                renderer.path(path);
+            }
+        }
+
+        renderer.push_str(" FROM ");
+        renderer.identifier(&table_name);
+        if let Some(index_name) = &index_name {
+            renderer.push_str(".");
+            renderer.identifier(index_name);
+        }
+
+        if let Some(condition) = &condition {
+            renderer.push_str(" WHERE ");
+            renderer.condition(condition)?;
+        }
+
+        Ok(renderer.finish())
+    }
+}
+
+/// Builds a PartiQL [`UPDATE`][1] statement.
+///
+/// The condition must identify a single item by its full primary key, and may
+/// include other conditions the item must meet.
+///
+/// Each `SET` action becomes its own `SET` clause. `ADD` becomes `SET` with
+/// `+` for numbers or `set_add` for sets, and `DELETE` becomes `SET` with
+/// `set_delete`. PartiQL has no `if_not_exists`, so updates using it can't be
+/// converted.
+///
+/// See the [module documentation](self) for examples.
+///
+/// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/ql-reference.update.html
+#[must_use = "Call `.build()` to create the `Statement`"]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UpdateStatement {
+    table_name: String,
+    update: Update,
+    condition: Condition,
+}
+
+impl UpdateStatement {
+    /// An `UPDATE` of the item in this table that the condition identifies.
+    pub fn new<T, U, C>(table_name: T, update: U, condition: C) -> Self
+    where
+        T: Into<String>,
+        U: Into<Update>,
+        C: Into<Condition>,
+    {
+        Self {
+            table_name: table_name.into(),
+            update: update.into(),
+            condition: condition.into(),
+        }
+    }
+
+    /// Builds the [`Statement`].
+    pub fn build(self) -> Result<Statement, PartiqlError> {
+        let mut renderer = Renderer::default();
+
+        renderer.push_str("UPDATE ");
+        renderer.identifier(&self.table_name);
+        renderer.update(&self.update)?;
+        renderer.push_str(" WHERE ");
+        renderer.condition(&self.condition)?;
+
+        Ok(renderer.finish())
+    }
+}
+
+/// Builds a PartiQL [`DELETE`][1] statement.
+///
+/// The condition must identify a single item by its full primary key, and may
+/// include other conditions the item must meet.
+///
+/// See the [module documentation](self) for examples.
+///
+/// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/ql-reference.delete.html
+#[must_use = "Call `.build()` to create the `Statement`"]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeleteStatement {
+    table_name: String,
+    condition: Condition,
+}
+
+impl DeleteStatement {
+    /// A `DELETE` of the item in this table that the condition identifies.
+    pub fn new<T, C>(table_name: T, condition: C) -> Self
+    where
+        T: Into<String>,
+        C: Into<Condition>,
+    {
+        Self {
+            table_name: table_name.into(),
+            condition: condition.into(),
+        }
+    }
+
+    /// Builds the [`Statement`].
+    pub fn build(self) -> Result<Statement, PartiqlError> {
+        let mut renderer = Renderer::default();
+
+        renderer.push_str("DELETE FROM ");
+        renderer.identifier(&self.table_name);
+        renderer.push_str(" WHERE ");
+        renderer.condition(&self.condition)?;
+
+        Ok(renderer.finish())
+    }
+}
+
+/// The reasons an expression can't be converted to PartiQL.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PartiqlError {
+    /// PartiQL statements only use positional `?` parameters, so there's no
+    /// value for a [`Ref`] to refer to.
+    ValueRef(Ref),
+
+    /// The expression uses something PartiQL for DynamoDB doesn't support.
+    Unsupported(&'static str),
+}
+
+impl fmt::Display for PartiqlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ValueRef(value_ref) => {
+                write!(
+                    f,
+                    "value references are not supported in PartiQL: {value_ref}"
+                )
+            }
+            Self::Unsupported(what) => write!(f, "{what} is not supported in PartiQL"),
+        }
+    }
+}
+
+impl Error for PartiqlError {}
@@ -0,0 +1,833 @@
+use core::{fmt, iter::Peekable, str::CharIndices};
+use std::error::Error;
+
+use aws_sdk_dynamodb::types::AttributeValue;
+
+use crate::{
+    condition::{
+        attribute_type::Type, AttributeExists, AttributeNotExists, AttributeType, BeginsWith,
+        Between, Comparator, Comparison, Condition, Contains, In,
+    },
+    key::{KeyCondition, KeyConditionError},
+    operand::{Operand, OperandType, Size},
+    path::{Element, Path},
+    value::{Num, Scalar, Value, ValueOrRef},
+};
+
+const TYPES: [Type; 10] = [
+    Type::String,
+    Type::StringSet,
+    Type::Number,
+    Type::NumberSet,
+    Type::Binary,
+    Type::BinarySet,
+    Type::Boolean,
+    Type::Null,
+    Type::List,
+    Type::Map,
+];
+
+/// Parses the `WHERE` clause of a PartiQL statement into a [`Condition`],
+/// binding each `?` to the next of the `parameters`.
+///
+/// The clause may start with `WHERE`, or not. This supports the subset of
+/// PartiQL that DynamoDB does:
+///
+/// * Comparisons: `=`, `<>` (or `!=`), `<`, `<=`, `>`, `>=`
+/// * `BETWEEN ... AND ...`, and `IN [...]`
+/// * `IS MISSING` and `IS NOT MISSING`
+/// * `begins_with`, `contains`, `attribute_type`, and `size`
+/// * `AND`, `OR`, `NOT`, and parentheses
+///
+/// Attribute names may be quoted (`"name"`) or not, and paths may be nested
+/// (`"a".b[2]`). Besides `?` parameters, values may be string literals
+/// (`'text'`), numbers, `TRUE`, `FALSE`, or `NULL`.
+///
+/// See also: [`parse_key_condition`]
+///
+/// ```
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use aws_sdk_dynamodb::types::AttributeValue;
+/// use dynamodb_expression::partiql::parse_condition;
+/// # use pretty_assertions::assert_eq;
+///
+/// let condition = parse_condition(
+///     r#"WHERE "status" IN ['open', ?] AND begins_with(customer.name, ?) AND total IS NOT MISSING"#,
+///     &[
+///         AttributeValue::S("pending".into()),
+///         AttributeValue::S("A".into()),
+///     ],
+/// )?;
+/// assert_eq!(
+///     r#"status IN ("open","pending") AND begins_with(customer.name, "A") AND attribute_exists(total)"#,
+///     condition.to_string()
+/// );
+/// #
+/// # Ok(())
+/// # }
+/// ```
+pub fn parse_condition(
+    where_clause: &str,
+    parameters: &[AttributeValue],
+) -> Result<Condition, PartiqlParseError> {
+    let mut parser = Parser {
+        tokens: lex(where_clause)?,
+        next: 0,
+        parameters,
+        used: 0,
+        depth: 0,
+    };
+
+    parser.eat_keyword("WHERE");
+    let condition = parser.condition()?;
+
+    if parser.peek().is_some() {
+        return Err(parser.unexpected("AND, OR, or the end of the clause"));
+    }
+
+    if parser.used != parameters.len() {
+        return Err(PartiqlParseError::UnusedParameters {
+            used: parser.used,
+            provided: parameters.len(),
+        });
+    }
+
+    Ok(condition)
+}
+
+/// Parses the `WHERE` clause of a PartiQL statement into a [`KeyCondition`],
+/// for moving a PartiQL `SELECT` to a [`Query`][1].
+///
+/// See [`parse_condition`] for what's supported. The condition must also have
+/// the shape of a key condition (see [`KeyCondition::try_from`]).
+///
+/// ```
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use aws_sdk_dynamodb::types::AttributeValue;
+/// use dynamodb_expression::{key::KeyConditionError, partiql::{parse_key_condition, PartiqlParseError}};
+/// # use pretty_assertions::assert_eq;
+///
+/// let key_condition = parse_key_condition(
+///     "id = ? AND created BETWEEN ? AND ?",
+///     &[
+///         AttributeValue::S("abc".into()),
+///         AttributeValue::N("1".into()),
+///         AttributeValue::N("9".into()),
+///     ],
+/// )?;
+/// assert_eq!(r#"id = "abc" AND created BETWEEN 1 AND 9"#, key_condition.to_string());
+///
+/// assert_eq!(
+///     Err(PartiqlParseError::KeyCondition(KeyConditionError::LogicalOperator("OR"))),
+///     parse_key_condition("id = 'a' OR id = 'b'", &[]),
+/// );
+/// #
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/APIReference/API_Query.html
+/// [`KeyCondition::try_from`]: KeyCondition#impl-TryFrom<Condition>-for-KeyCondition
+pub fn parse_key_condition(
+    where_clause: &str,
+    parameters: &[AttributeValue],
+) -> Result<KeyCondition, PartiqlParseError> {
+    KeyCondition::try_from(parse_condition(where_clause, parameters)?)
+        .map_err(PartiqlParseError::KeyCondition)
+}
+
+/// The reasons a PartiQL `WHERE` clause couldn't be parsed.
+///
+/// Positions are byte offsets into the clause.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PartiqlParseError {
+    /// Found something other than what was expected.
+    Unexpected {
+        position: usize,
+        expected: &'static str,
+    },
+
+    /// The clause ended where more was expected.
+    UnexpectedEnd { expected: &'static str },
+
+    /// A quoted name or string starting here wasn't closed.
+    UnterminatedQuote(usize),
+
+    /// A number DynamoDB wouldn't accept.
+    InvalidNumber(String),
+
+    /// A function DynamoDB doesn't support in PartiQL.
+    UnknownFunction(String),
+
+    /// An `attribute_type` that isn't a DynamoDB type, such as `'S'` or `'M'`.
+    UnknownType(String),
+
+    /// There was no parameter for the `?` with this (zero-based) index.
+    MissingParameter(usize),
+
+    /// The parameter with this index is an [`AttributeValue`] variant that
+    /// isn't supported.
+    UnsupportedParameter(usize),
+
+    /// There were more parameters than `?`s.
+    UnusedParameters { used: usize, provided: usize },
+
+    /// The condition isn't a valid key condition.
+    KeyCondition(KeyConditionError),
+
+    /// `NOT`s and parentheses are nested more than 100 deep, starting from
+    /// here.
+    TooDeep(usize),
+}
+
+impl fmt::Display for PartiqlParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unexpected { position, expected } => {
+                write!(f, "expected {expected} at position {position}")
+            }
+            Self::UnexpectedEnd { expected } => {
+                write!(f, "expected {expected} at the end of the clause")
+            }
+            Self::UnterminatedQuote(position) => {
+                write!(f, "unterminated quote at position {position}")
+            }
+            Self::InvalidNumber(n) => write!(f, "invalid number: {n}"),
+            Self::UnknownFunction(name) => write!(f, "unknown function: {name}"),
+            Self::UnknownType(name) => write!(f, "unknown attribute type: {name}"),
+            Self::MissingParameter(index) => write!(f, "no value for parameter {index}"),
+            Self::UnsupportedParameter(index) => {
+                write!(f, "parameter {index} is an unsupported AttributeValue")
+            }
+            Self::UnusedParameters { used, provided } => {
+                write!(
+                    f,
+                    "{provided} parameters were provided, but {used} were used"
+                )
+            }
+            Self::KeyCondition(err) => err.fmt(f),
+            Self::TooDeep(position) => {
+                write!(f, "nested too deeply at position {position}")
+            }
+        }
+    }
+}
+
+impl Error for PartiqlParseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::KeyCondition(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    /// An unquoted name or keyword.
+    Ident(String),
+    /// A double-quoted name.
+    Quoted(String),
+    /// A single-quoted string.
+    Str(String),
+    Number(String),
+    Param,
+    Cmp(Comparator),
+    LParen,
+    RParen,
+    LBracket,
+    RBracket,
+    Comma,
+    Dot,
+}
+
+fn lex(input: &str) -> Result<Vec<(usize, Token)>, PartiqlParseError> {
+    let mut tokens = Vec::new();
+    let mut chars = input.char_indices().peekable();
+
+    while let Some(&(position, c)) = chars.peek() {
+        let token = match c {
+            c if c.is_whitespace() => {
+                chars.next();
+                continue;
+            }
+            '"' | '\'' => {
+                chars.next();
+                let s =
+                    quoted(&mut chars, c).ok_or(PartiqlParseError::UnterminatedQuote(position))?;
+
+                if c == '"' {
+                    Token::Quoted(s)
+                } else {
+                    Token::Str(s)
+                }
+            }
+            c if c.is_ascii_digit() || c == '-' => {
+                let mut n = String::new();
+                while let Some(&(_, c)) = chars.peek() {
+                    let exponent_sign =
+                        (c == '+' || c == '-') && n.ends_with(['e', 'E']) || n.is_empty();
+                    if !(c.is_ascii_alphanumeric() || c == '.' || exponent_sign) {
+                        break;
+                    }
+
+                    n.push(c);
+                    chars.next();
+                }
+
+                Token::Number(n)
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let mut ident = String::new();
+                while let Some(&(_, c)) = chars.peek() {
+                    if !(c.is_alphanumeric() || c == '_') {
+                        break;
+                    }
+
+                    ident.push(c);
+                    chars.next();
+                }
+
+                Token::Ident(ident)
+            }
+            _ => {
+                chars.next();
+
+                match c {
+                    '?' => Token::Param,
+                    '(' => Token::LParen,
+                    ')' => Token::RParen,
+                    '[' => Token::LBracket,
+                    ']' => Token::RBracket,
+                    ',' => Token::Comma,
+                    '.' => Token::Dot,
+                    '=' => Token::Cmp(Comparator::Eq),
+                    '<' => Token::Cmp(match chars.next_if(|&(_, c)| c == '=' || c == '>') {
+                        Some((_, '=')) => Comparator::Le,
+                        Some(_) => Comparator::Ne,
+                        None => Comparator::Lt,
+                    }),
+                    '>' => Token::Cmp(match chars.next_if(|&(_, c)| c == '=') {
+                        Some(_) => Comparator::Ge,
+                        None => Comparator::Gt,
+                    }),
+                    '!' if chars.next_if(|&(_, c)| c == '=').is_some() => {
+                        Token::Cmp(Comparator::Ne)
+                    }
+                    _ => {
+                        return Err(PartiqlParseError::Unexpected {
+                            position,
+                            expected: "a name, value, or operator",
+                        })
+                    }
+                }
+            }
+        };
+
+        tokens.push((position, token));
+    }
+
+    Ok(tokens)
+}
+
+/// Reads up to the closing quote, after the opening one. A doubled quote is
+/// an escaped quote.
+fn quoted(chars: &mut Peekable<CharIndices<'_>>, quote: char) -> Option<String> {
+    let mut s = String::new();
+    loop {
+        let (_, c) = chars.next()?;
+        if c == quote && chars.next_if(|&(_, c)| c == quote).is_none() {
+            return Some(s);
+        }
+
+        s.push(c);
+    }
+}
+
+/// How deeply `NOT`s and parentheses may be nested.
+const MAX_DEPTH: usize = 100;
+
+struct Parser<'a> {
+    tokens: Vec<(usize, Token)>,
+    next: usize,
+    parameters: &'a [AttributeValue],
+    used: usize,
+    /// How many `NOT`s and parentheses enclose the current position.
+    depth: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.peek_nth(0)
+    }
+
+    fn peek_nth(&self, n: usize) -> Option<&Token> {
+        self.tokens.get(self.next + n).map(|(_, token)| token)
+    }
+
+    fn unexpected(&self, expected: &'static str) -> PartiqlParseError {
+        match self.tokens.get(self.next) {
+            Some(&(position, _)) => PartiqlParseError::Unexpected { position, expected },
+            None => PartiqlParseError::UnexpectedEnd { expected },
+        }
+    }
+
+    fn is_keyword(&self, n: usize, keyword: &str) -> bool {
+        matches!(self.peek_nth(n), Some(Token::Ident(ident)) if ident.eq_ignore_ascii_case(keyword))
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        let is_keyword = self.is_keyword(0, keyword);
+        if is_keyword {
+            self.next += 1;
+        }
+
+        is_keyword
+    }
+
+    fn expect_keyword(&mut self, keyword: &'static str) -> Result<(), PartiqlParseError> {
+        if self.eat_keyword(keyword) {
+            Ok(())
+        } else {
+            Err(self.unexpected(keyword))
+        }
+    }
+
+    fn expect(&mut self, token: Token, expected: &'static str) -> Result<(), PartiqlParseError> {
+        if self.peek() == Some(&token) {
+            self.next += 1;
+            Ok(())
+        } else {
+            Err(self.unexpected(expected))
+        }
+    }
+
+    /// Enters a `NOT` or parenthesis at the current token.
+    fn nest(&mut self) -> Result<(), PartiqlParseError> {
+        if self.depth == MAX_DEPTH {
+            let position = self
+                .tokens
+                .get(self.next)
+                .map_or(0, |&(position, _)| position);
+            return Err(PartiqlParseError::TooDeep(position));
+        }
+
+        self.depth += 1;
+
+        Ok(())
+    }
+
+    fn condition(&mut self) -> Result<Condition, PartiqlParseError> {
+        let mut condition = self.and()?;
+        while self.eat_keyword("OR") {
+            condition = condition.or(self.and()?);
+        }
+
+        Ok(condition)
+    }
+
+    fn and(&mut self) -> Result<Condition, PartiqlParseError> {
+        let mut condition = self.not()?;
+        while self.eat_keyword("AND") {
+            condition = condition.and(self.not()?);
+        }
+
+        Ok(condition)
+    }
+
+    fn not(&mut self) -> Result<Condition, PartiqlParseError> {
+        let mut nots = 0;
+        while self.is_keyword(0, "NOT") {
+            self.nest()?;
+            self.next += 1;
+            nots += 1;
+        }
+
+        let mut condition = self.predicate()?;
+        for _ in 0..nots {
+            condition = condition.not();
+        }
+        self.depth -= nots;
+
+        Ok(condition)
+    }
+
+    fn predicate(&mut self) -> Result<Condition, PartiqlParseError> {
+        if self.peek() == Some(&Token::LParen) {
+            self.nest()?;
+            self.next += 1;
+            let condition = self.condition()?;
+            self.expect(Token::RParen, ")")?;
+            self.depth -= 1;
+
+            return Ok(condition.parenthesize());
+        }
+
+        if let (Some(Token::Ident(function)), Some(Token::LParen)) = (self.peek(), self.peek_nth(1))
+        {
+            let function = function.to_ascii_lowercase();
+            if function != "size" {
+                self.next += 2;
+                let condition = self.function(function)?;
+                self.expect(Token::RParen, ")")?;
+
+                return Ok(condition);
+            }
+        }
+
+        let left_start = self.next;
+        let left = self.operand()?;
+
+        if self.eat_keyword("BETWEEN") {
+            let lower = self.operand()?;
+            self.expect_keyword("AND")?;
+            let upper = self.operand()?;
+
+            return Ok(Between {
+                op: left,
+                lower,
+                upper,
+            }
+            .into());
+        }
+
+        if self.eat_keyword("IN") {
+            let close = match self.peek() {
+                Some(Token::LBracket) => Token::RBracket,
+                Some(Token::LParen) => Token::RParen,
+                _ => return Err(self.unexpected("[ or (")),
+            };
+            self.next += 1;
+
+            let mut items = vec![self.operand()?];
+            while self.peek() == Some(&Token::Comma) {
+                self.next += 1;
+                items.push(self.operand()?);
+            }
+            self.expect(close, "] or )")?;
+
+            return Ok(In { op: left, items }.into());
+        }
+
+        if self.eat_keyword("IS") {
+            let not = self.eat_keyword("NOT");
+            self.expect_keyword("MISSING")?;
+
+            let OperandType::Path(path) = left.op else {
+                self.next = left_start;
+                return Err(self.unexpected("a path"));
+            };
+
+            return Ok(if not {
+                AttributeExists { path }.into()
+            } else {
+                AttributeNotExists { path }.into()
+            });
+        }
+
+        if let Some(&Token::Cmp(cmp)) = self.peek() {
+            self.next += 1;
+            let right = self.operand()?;
+
+            return Ok(Comparison { left, cmp, right }.into());
+        }
+
+        Err(self.unexpected("a comparison, BETWEEN, IN, or IS"))
+    }
+
+    /// A function returning a condition, after the opening parenthesis.
+    fn function(&mut self, function: String) -> Result<Condition, PartiqlParseError> {
+        match function.as_str() {
+            "begins_with" => {
+                let path = self.path()?;
+                self.expect(Token::Comma, ",")?;
+                let substr = self.value()?;
+
+                Ok(BeginsWith { path, substr }.into())
+            }
+            "contains" => {
+                let path = self.path()?;
+                self.expect(Token::Comma, ",")?;
+                let operand = self.value()?;
+
+                Ok(Contains { path, operand }.into())
+            }
+            "attribute_type" => {
+                let path = self.path()?;
+                self.expect(Token::Comma, ",")?;
+
+                let start = self.next;
+                let ValueOrRef::Value(Value::Scalar(Scalar::String(name))) = self.value()? else {
+                    self.next = start;
+                    return Err(self.unexpected("an attribute type string"));
+                };
+
+                let attribute_type = TYPES
+                    .into_iter()
+                    .find(|t| t.as_str() == name)
+                    .ok_or(PartiqlParseError::UnknownType(name))?;
+
+                Ok(AttributeType {
+                    path,
+                    attribute_type,
+                }
+                .into())
+            }
+            _ => Err(PartiqlParseError::UnknownFunction(function)),
+        }
+    }
+
+    fn operand(&mut self) -> Result<Operand, PartiqlParseError> {
+        match self.peek() {
+            Some(Token::Ident(ident))
+                if ident.eq_ignore_ascii_case("size")
+                    && self.peek_nth(1) == Some(&Token::LParen) =>
+            {
+                self.next += 2;
+                let path = self.path()?;
+                self.expect(Token::RParen, ")")?;
+
+                Ok(Size { path }.into())
+            }
+            Some(Token::Ident(_)) if !self.is_literal_keyword() => Ok(self.path()?.into()),
+            Some(Token::Quoted(_)) => Ok(self.path()?.into()),
+            _ => Ok(Operand {
+                op: OperandType::Scalar(self.value()?),
+            }),
+        }
+    }
+
+    fn is_literal_keyword(&self) -> bool {
+        ["TRUE", "FALSE", "NULL"]
+            .into_iter()
+            .any(|keyword| self.is_keyword(0, keyword))
+    }
+
+    fn value(&mut self) -> Result<ValueOrRef, PartiqlParseError> {
+        let value = match self.peek() {
+            Some(Token::Param) => {
+                let index = self.used;
+                let parameter = self
+                    .parameters
+                    .get(index)
+                    .ok_or(PartiqlParseError::MissingParameter(index))?;
+                self.used += 1;
+
+                Value::try_from(parameter.clone())
+                    .map_err(|_| PartiqlParseError::UnsupportedParameter(index))?
+            }
+            Some(Token::Str(s)) => s.as_str().into(),
+            Some(Token::Number(n)) => n
+                .parse::<Num>()
+                .map_err(|_| PartiqlParseError::InvalidNumber(n.clone()))?
+                .into(),
+            Some(Token::Ident(_)) if self.is_keyword(0, "TRUE") => true.into(),
+            Some(Token::Ident(_)) if self.is_keyword(0, "FALSE") => false.into(),
+            Some(Token::Ident(_)) if self.is_keyword(0, "NULL") => Value::new_null(),
+            _ => return Err(self.unexpected("a value")),
+        };
+
+        self.next += 1;
+
+        Ok(value.into())
+    }
+
+    fn path(&mut self) -> Result<Path, PartiqlParseError> {
+        let mut elements = Vec::new();
+
+        loop {
+            let name = match self.peek() {
+                Some(Token::Ident(name) | Token::Quoted(name)) => name.clone(),
+                _ => return Err(self.unexpected("an attribute name")),
+            };
+            self.next += 1;
+
+            let mut indexes = Vec::new();
+            while self.peek() == Some(&Token::LBracket) {
+                self.next += 1;
+                let Some(index) = self.peek().and_then(|token| match token {
+                    Token::Number(index) => index.parse::<usize>().ok(),
+                    _ => None,
+                }) else {
+                    return Err(self.unexpected("a list index"));
+                };
+                indexes.push(index);
+                self.next += 1;
+                self.expect(Token::RBracket, "]")?;
+            }
+
+            elements.push(if indexes.is_empty() {
+                Element::new_name(name)
+            } else {
+                Element::new_indexed_field(name, indexes)
+            });
+
+            if self.peek() != Some(&Token::Dot) {
+                return Ok(Path::from_iter(elements));
+            }
+
+            self.next += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use aws_sdk_dynamodb::types::AttributeValue;
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        condition::attribute_type::Type, partiql::SelectStatement, path::test::path,
+        value::StringSet, Num,
+    };
+
+    use super::{parse_condition, PartiqlParseError};
+
+    #[test]
+    fn round_trip() {
+        let condition = path("a[2].b")
+            .attribute_exists()
+            .and(path("c").attribute_not_exists())
+            .and(
+                path("d")
+                    .between(Num::new(1), Num::new(5))
+                    .or(path("e").in_(["x", "y"]))
+                    .parenthesize(),
+            )
+            .and(path("f").begins_with("pre").not())
+            .and(path("g").contains(StringSet::from(["s"])))
+            .and(path("h").attribute_type(Type::NumberSet))
+            .and(path("i").size().greater_than_or_equal(Num::new(3)))
+            .and(path("j").not_equal(path("k")))
+            .and(path("l").less_than(Num::new(-1.5)));
+
+        let statement = SelectStatement::new("t")
+            .with_condition(condition.clone())
+            .build()
+            .unwrap();
+        let (_, where_clause) = statement.statement.split_once(" WHERE ").unwrap();
+
+        assert_eq!(
+            Ok(condition),
+            parse_condition(where_clause, &statement.parameters)
+        );
+    }
+
+    #[test]
+    fn literals_and_keywords() {
+        assert_eq!(
+            Ok(path("a")
+                .equal("it's")
+                .or(path("b").equal(true))
+                .or(path("c").not_equal(Num::new(1e3)))
+                .or(path("d").equal(()))),
+            parse_condition(
+                "where a = 'it''s' or \"b\" = TRUE or c != 1e3 or d = null",
+                &[]
+            )
+        );
+    }
+
+    #[test]
+    fn parameters() {
+        assert_eq!(
+            Err(PartiqlParseError::MissingParameter(1)),
+            parse_condition("a = ? AND b = ?", &[AttributeValue::S("x".into())])
+        );
+        assert_eq!(
+            Err(PartiqlParseError::UnusedParameters {
+                used: 1,
+                provided: 2
+            }),
+            parse_condition(
+                "a = ?",
+                &[AttributeValue::S("x".into()), AttributeValue::S("y".into())]
+            )
+        );
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            Err(PartiqlParseError::Unexpected {
+                position: 2,
+                expected: "a comparison, BETWEEN, IN, or IS"
+            }),
+            parse_condition("a b", &[])
+        );
+        assert_eq!(
+            Err(PartiqlParseError::UnexpectedEnd {
+                expected: "a value"
+            }),
+            parse_condition("a =", &[])
+        );
+        assert_eq!(
+            Err(PartiqlParseError::UnterminatedQuote(4)),
+            parse_condition("a = 'b", &[])
+        );
+        assert_eq!(
+            Err(PartiqlParseError::UnknownFunction("exists".into())),
+            parse_condition("exists(a)", &[])
+        );
+        assert_eq!(
+            Err(PartiqlParseError::UnknownType("X".into())),
+            parse_condition("attribute_type(a, 'X')", &[])
+        );
+        assert_eq!(
+            Err(PartiqlParseError::InvalidNumber("1x".into())),
+            parse_condition("a = 1x", &[])
+        );
+        assert_eq!(
+            Err(PartiqlParseError::InvalidNumber(
+                "0.0001e-99999999999999999999".into()
+            )),
+            parse_condition("a = 0.0001e-99999999999999999999", &[])
+        );
+        assert_eq!(
+            Err(PartiqlParseError::Unexpected {
+                position: 0,
+                expected: "a path"
+            }),
+            parse_condition("'a' IS MISSING", &[])
+        );
+        assert_eq!(
+            Err(PartiqlParseError::Unexpected {
+                position: 8,
+                expected: "AND, OR, or the end of the clause"
+            }),
+            parse_condition("a = 'b' )", &[])
+        );
+    }
+
+    #[test]
+    fn depth() {
+        let nested = |nots: usize, parens: usize| {
+            format!(
+                "{}{}a = 1{}",
+                "NOT ".repeat(nots),
+                "(".repeat(parens),
+                ")".repeat(parens)
+            )
+        };
+
+        assert!(parse_condition(&nested(100, 0), &[]).is_ok());
+        assert!(parse_condition(&nested(0, 100), &[]).is_ok());
+        assert!(parse_condition(&nested(50, 50), &[]).is_ok());
+        assert_eq!(
+            Err(PartiqlParseError::TooDeep(400)),
+            parse_condition(&nested(101, 0), &[])
+        );
+        assert_eq!(
+            Err(PartiqlParseError::TooDeep(250)),
+            parse_condition(&nested(50, 51), &[])
+        );
+        assert_eq!(
+            Err(PartiqlParseError::TooDeep(400)),
+            parse_condition(&nested(200_000, 0), &[])
+        );
+        assert_eq!(
+            Err(PartiqlParseError::TooDeep(100)),
+            parse_condition(&nested(0, 200_000), &[])
+        );
+    }
+}
@@ -0,0 +1,406 @@
+use core::fmt::Write;
+
+use aws_sdk_dynamodb::types::AttributeValue;
+
+use crate::{
+    condition::{
+        And, AttributeExists, AttributeNotExists, AttributeType, BeginsWith, Between, Comparison,
+        Condition, Contains, In, Not, Or, Parenthetical,
+    },
+    operand::{Operand, OperandType, Size},
+    path::{Element, Path},
+    update::{math::MathOp, Add, Delete, Remove, Set, SetAction, Update},
+    value::{Scalar, Value, ValueOrRef},
+};
+
+use super::{PartiqlError, Statement};
+
+/// Writes a PartiQL statement, collecting its parameters as it goes.
+#[derive(Debug, Default)]
+pub(super) struct Renderer {
+    statement: String,
+    parameters: Vec<AttributeValue>,
+}
+
+impl Renderer {
+    pub(super) fn finish(self) -> Statement {
+        let Self {
+            statement,
+            parameters,
+        } = self;
+
+        Statement {
+            statement,
+            parameters,
+        }
+    }
+
+    pub(super) fn push_str(&mut self, s: &str) {
+        self.statement.push_str(s);
+    }
+
+    /// A quoted identifier. Quotes within it are doubled.
+    pub(super) fn identifier(&mut self, name: &str) {
+        self.statement.push('"');
+        self.statement.push_str(&name.replace('"', r#""""#));
+        self.statement.push('"');
+    }
+
+    pub(super) fn path(&mut self, path: &Path) {
+        for (i, element) in path.elements.iter().enumerate() {
+            if i > 0 {
+                self.statement.push('.');
+            }
+
+            match element {
+                Element::Name(name) => self.identifier(&name.name),
+                Element::IndexedField(field) => {
+                    self.identifier(&field.name.name);
+                    for index in &field.indexes {
+                        write!(self.statement, "[{index}]").expect("writing to a String");
+                    }
+                }
+            }
+        }
+    }
+
+    fn value(&mut self, value: &ValueOrRef) -> Result<(), PartiqlError> {
+        match value {
+            ValueOrRef::Value(value) => {
+                self.parameters.push(value.clone().into_attribute_value());
+                self.statement.push('?');
+
+                Ok(())
+            }
+            ValueOrRef::Ref(value_ref) => Err(PartiqlError::ValueRef(value_ref.clone())),
+        }
+    }
+
+    fn operand(&mut self, operand: &Operand) -> Result<(), PartiqlError> {
+        match &operand.op {
+            OperandType::Path(path) => self.path(path),
+            OperandType::Scalar(value) => self.value(value)?,
+            OperandType::Condition(condition) => self.condition(condition)?,
+            OperandType::Size(Size { path }) => {
+                self.push_str("size(");
+                self.path(path);
+                self.push_str(")");
+            }
+        }
+
+        Ok(())
+    }
+
+    pub(super) fn condition(&mut self, condition: &Condition) -> Result<(), PartiqlError> {
+        match condition {
+            Condition::AttributeExists(AttributeExists { path }) => {
+                self.path(path);
+                self.push_str(" IS NOT MISSING");
+            }
+            Condition::AttributeNotExists(AttributeNotExists { path }) => {
+                self.path(path);
+                self.push_str(" IS MISSING");
+            }
+            Condition::AttributeType(AttributeType {
+                path,
+                attribute_type,
+            }) => {
+                self.push_str("attribute_type(");
+                self.path(path);
+                write!(self.statement, ", '{attribute_type}')").expect("writing to a String");
+            }
+            Condition::BeginsWith(BeginsWith { path, substr }) => {
+                self.push_str("begins_with(");
+                self.path(path);
+                self.push_str(", ");
+                self.value(substr)?;
+                self.push_str(")");
+            }
+            Condition::Contains(Contains { path, operand }) => {
+                self.push_str("contains(");
+                self.path(path);
+                self.push_str(", ");
+                self.value(operand)?;
+                self.push_str(")");
+            }
+            Condition::Between(Between { op, lower, upper }) => {
+                self.operand(op)?;
+                self.push_str(" BETWEEN ");
+                self.operand(lower)?;
+                self.push_str(" AND ");
+                self.operand(upper)?;
+            }
+            Condition::In(In { op, items }) => {
+                self.operand(op)?;
+                self.push_str(" IN [");
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        self.push_str(", ");
+                    }
+
+                    self.operand(item)?;
+                }
+                self.push_str("]");
+            }
+            Condition::Comparison(Comparison { left, cmp, right }) => {
+                self.operand(left)?;
+                write!(self.statement, " {cmp} ").expect("writing to a String");
+                self.operand(right)?;
+            }
+            Condition::And(And { left, right }) => {
+                self.condition(left)?;
+                self.push_str(" AND ");
+                self.condition(right)?;
+            }
+            Condition::Or(Or { left, right }) => {
+                self.condition(left)?;
+                self.push_str(" OR ");
+                self.condition(right)?;
+            }
+            Condition::Not(Not { condition }) => {
+                self.push_str("NOT ");
+                self.condition(condition)?;
+            }
+            Condition::Parenthetical(Parenthetical { condition }) => {
+                self.push_str("(");
+                self.condition(condition)?;
+                self.push_str(")");
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The `SET` and `REMOVE` clauses of an `UPDATE` statement, each with a
+    /// leading space.
+    pub(super) fn update(&mut self, update: &Update) -> Result<(), PartiqlError> {
+        let Update {
+            set,
+            remove,
+            add,
+            delete,
+        } = update;
+
+        if let Some(Set { actions }) = set {
+            for action in actions {
+                self.push_str(" SET ");
+                self.set_action(action)?;
+            }
+        }
+
+        if let Some(Remove { paths }) = remove {
+            for path in paths {
+                self.push_str(" REMOVE ");
+                self.path(path);
+            }
+        }
+
+        if let Some(Add { actions }) = add {
+            for action in actions {
+                self.push_str(" SET ");
+                self.path(&action.path);
+                self.push_str(" = ");
+
+                match &action.value {
+                    ValueOrRef::Value(Value::Scalar(Scalar::Num(_))) => {
+                        self.path(&action.path);
+                        self.push_str(" + ");
+                        self.value(&action.value)?;
+                    }
+                    ValueOrRef::Value(Value::Set(_)) => {
+                        self.push_str("set_add(");
+                        self.path(&action.path);
+                        self.push_str(", ");
+                        self.value(&action.value)?;
+                        self.push_str(")");
+                    }
+                    ValueOrRef::Value(_) => {
+                        return Err(PartiqlError::Unsupported(
+                            "ADD with a value other than a number or set",
+                        ))
+                    }
+                    ValueOrRef::Ref(value_ref) => {
+                        return Err(PartiqlError::ValueRef(value_ref.clone()))
+                    }
+                }
+            }
+        }
+
+        if let Some(Delete { actions }) = delete {
+            for action in actions {
+                self.push_str(" SET ");
+                self.path(&action.path);
+                self.push_str(" = set_delete(");
+                self.path(&action.path);
+                self.push_str(", ");
+                self.value(&action.subset)?;
+                self.push_str(")");
+            }
+        }
+
+        Ok(())
+    }
+
+    fn set_action(&mut self, action: &SetAction) -> Result<(), PartiqlError> {
+        match action {
+            SetAction::Assign(action) => {
+                self.path(&action.path);
+                self.push_str(" = ");
+                self.value(&action.value)?;
+            }
+            SetAction::Math(action) => {
+                if action.default.is_some() {
+                    return Err(PartiqlError::Unsupported("if_not_exists"));
+                }
+
+                self.path(&action.dst);
+                self.push_str(" = ");
+                self.path(action.src.as_ref().unwrap_or(&action.dst));
+                self.push_str(match action.op {
+                    MathOp::Add => " + ",
+                    MathOp::Sub => " - ",
+                });
+                self.value(&action.num)?;
+            }
+            SetAction::ListAppend(action) => {
+                let src = action.src.as_ref().unwrap_or(&action.dst);
+
+                self.path(&action.dst);
+                self.push_str(" = list_append(");
+                if action.after {
+                    self.path(src);
+                    self.push_str(", ");
+                    self.value(&action.list)?;
+                } else {
+                    self.value(&action.list)?;
+                    self.push_str(", ");
+                    self.path(src);
+                }
+                self.push_str(")");
+            }
+            SetAction::IfNotExists(_) => {
+                return Err(PartiqlError::Unsupported("if_not_exists"));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use aws_sdk_dynamodb::types::AttributeValue;
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        condition::attribute_type::Type,
+        partiql::{PartiqlError, SelectStatement, UpdateStatement},
+        path::test::path,
+        value::{NumSet, Ref, StringSet},
+        Num,
+    };
+
+    #[test]
+    fn conditions() {
+        let statement = SelectStatement::new("t")
+            .with_condition(
+                path("a[2].b")
+                    .attribute_exists()
+                    .and(path("c").attribute_not_exists())
+                    .and(
+                        path("d")
+                            .between(Num::new(1), Num::new(5))
+                            .or(path("e").in_(["x", "y"]))
+                            .parenthesize(),
+                    )
+                    .and(!path("f").begins_with("pre"))
+                    .and(path("g").contains("sub"))
+                    .and(path("h").attribute_type(Type::Map))
+                    .and(path("i").size().greater_than(Num::new(3)))
+                    .and(path("j").not_equal(path("k"))),
+            )
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            r#"SELECT * FROM "t" WHERE "a"[2]."b" IS NOT MISSING AND "c" IS MISSING AND ("d" BETWEEN ? AND ? OR "e" IN [?, ?]) AND NOT begins_with("f", ?) AND contains("g", ?) AND attribute_type("h", 'M') AND size("i") > ? AND "j" <> "k""#,
+            statement.statement
+        );
+        assert_eq!(
+            vec![
+                AttributeValue::N("1".into()),
+                AttributeValue::N("5".into()),
+                AttributeValue::S("x".into()),
+                AttributeValue::S("y".into()),
+                AttributeValue::S("pre".into()),
+                AttributeValue::S("sub".into()),
+                AttributeValue::N("3".into()),
+            ],
+            statement.parameters
+        );
+    }
+
+    #[test]
+    fn quoted_identifiers() {
+        let statement = SelectStatement::new(r#"my"table"#)
+            .with_projection([path("select")])
+            .build()
+            .unwrap();
+        assert_eq!(r#"SELECT "select" FROM "my""table""#, statement.statement);
+        assert!(statement.parameters.is_empty());
+    }
+
+    #[test]
+    fn update_actions() {
+        let statement = UpdateStatement::new(
+            "t",
+            path("list")
+                .list_append()
+                .before()
+                .list(["a"])
+                .and(path("count").math().src(path("other")).sub(2))
+                .and(path("num").add(Num::new(1)))
+                .and(path("tags").add(StringSet::from(["new"])))
+                .and(path("ids").delete(NumSet::from([7]))),
+            path("id").equal("x"),
+        )
+        .build()
+        .unwrap();
+
+        assert_eq!(
+            r#"UPDATE "t" SET "list" = list_append(?, "list") SET "count" = "other" - ? SET "num" = "num" + ? SET "tags" = set_add("tags", ?) SET "ids" = set_delete("ids", ?) WHERE "id" = ?"#,
+            statement.statement
+        );
+        assert_eq!(6, statement.parameters.len());
+    }
+
+    #[test]
+    fn unsupported() {
+        assert_eq!(
+            Err(PartiqlError::ValueRef(Ref::new("v"))),
+            SelectStatement::new("t")
+                .with_condition(path("a").equal(Ref::new("v")))
+                .build()
+        );
+
+        assert_eq!(
+            Err(PartiqlError::Unsupported("if_not_exists")),
+            UpdateStatement::new(
+                "t",
+                path("a").if_not_exists().set("x"),
+                path("id").equal("x"),
+            )
+            .build()
+        );
+
+        assert_eq!(
+            Err(PartiqlError::Unsupported("if_not_exists")),
+            UpdateStatement::new(
+                "t",
+                path("a").math().if_not_exists(0).add(1),
+                path("id").equal("x"),
+            )
+            .build()
+        );
+    }
+}
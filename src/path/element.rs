@@ -218,7 +218,7 @@ impl FromStr for Element {
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct IndexedField {
     pub(crate) name: Name,
-    indexes: Vec<usize>,
+    pub(crate) indexes: Vec<usize>,
 }
 
 impl fmt::Display for IndexedField {
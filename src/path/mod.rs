@@ -4,10 +4,12 @@
 
 mod element;
 mod name;
+mod project;
 
 pub use self::{
     element::{Element, IndexedField, Indexes},
     name::Name,
+    project::{Projection, ProjectionParseError},
 };
 
 use core::{
@@ -15,7 +17,9 @@ use core::{
     ops,
     str::FromStr,
 };
+use std::collections::HashMap;
 
+use aws_sdk_dynamodb::types::AttributeValue;
 use itertools::Itertools;
 
 use crate::{
@@ -353,6 +357,31 @@ impl Path {
     pub fn is_empty(&self) -> bool {
         self.elements.is_empty()
     }
+
+    /// Returns `true` if `other` is this same path, or is nested within it.
+    /// For example, `foo` covers `foo`, `foo[2]`, and `foo.bar`.
+    pub(crate) fn covers(&self, other: &Path) -> bool {
+        let mut other = project::steps(other);
+
+        project::steps(self).all(|step| other.next().as_ref() == Some(&step))
+    }
+
+    /// The value at this path in the item, if there is one.
+    pub(crate) fn get<'a>(
+        &self,
+        item: &'a HashMap<String, AttributeValue>,
+    ) -> Option<&'a AttributeValue> {
+        let mut steps = project::steps(self);
+        let Some(project::Step::Name(name)) = steps.next() else {
+            return None;
+        };
+
+        steps.try_fold(item.get(&name)?, |value, step| match (step, value) {
+            (project::Step::Name(name), AttributeValue::M(map)) => map.get(&name),
+            (project::Step::Index(index), AttributeValue::L(list)) => list.get(index),
+            _ => None,
+        })
+    }
 }
 
 /// Methods related to building condition and filter expressions.
@@ -1134,13 +1163,18 @@ impl fmt::Display for PathParseError {
 }
 
 #[cfg(test)]
-mod test {
+pub(crate) mod test {
     use pretty_assertions::assert_eq;
 
     use crate::Num;
 
     use super::{Element, Name, Path, PathParseError};
 
+    /// Parses a path, which must be valid.
+    pub fn path(path: &str) -> Path {
+        path.parse().unwrap()
+    }
+
     #[test]
     fn parse_path() {
         let path: Path = "foo".parse().unwrap();
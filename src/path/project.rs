@@ -0,0 +1,555 @@
+//! Applying [projection expressions][1] to items in memory.
+//!
+//! [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/Expressions.ProjectionExpressions.html
+
+use core::fmt;
+use std::{
+    collections::{BTreeMap, HashMap},
+    error::Error,
+};
+
+use aws_sdk_dynamodb::types::AttributeValue;
+
+use crate::{
+    condition::attribute_type::Type,
+    value::{attribute_type, DecodeError, DecodeErrorKind, ValuePathSegment},
+};
+
+use super::{Element, Path, PathParseError};
+
+/// A set of document [`Path`]s to select from an item, the way DynamoDB
+/// applies a [projection expression][1].
+///
+/// * Maps keep their nesting, with only the projected attributes.
+/// * Projected list elements are compacted into a new list, in index order.
+/// * Paths that don't exist in the item are left out, as are maps and lists
+///   where none of the projected paths exist.
+/// * Where paths overlap (`a` and `a.b`), the broader one wins.
+///
+/// ```
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use std::collections::HashMap;
+///
+/// use aws_sdk_dynamodb::types::AttributeValue;
+/// use dynamodb_expression::{path::Projection, Path};
+/// # use pretty_assertions::assert_eq;
+///
+/// let item = HashMap::from([
+///     ("id".to_string(), AttributeValue::S("1".into())),
+///     (
+///         "tags".to_string(),
+///         AttributeValue::L(vec![
+///             AttributeValue::S("a".into()),
+///             AttributeValue::S("b".into()),
+///             AttributeValue::S("c".into()),
+///         ]),
+///     ),
+/// ]);
+///
+/// let projection = Projection::new(["id".parse::<Path>()?, "tags[2]".parse()?, "tags[5]".parse()?]);
+/// assert_eq!(
+///     HashMap::from([
+///         ("id".to_string(), AttributeValue::S("1".into())),
+///         ("tags".to_string(), AttributeValue::L(vec![AttributeValue::S("c".into())])),
+///     ]),
+///     projection.apply(&item)
+/// );
+/// #
+/// # Ok(())
+/// # }
+/// ```
+///
+/// See also: [`Expression::project_item`]
+///
+/// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/Expressions.ProjectionExpressions.html
+/// [`Expression::project_item`]: crate::Expression::project_item
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Projection {
+    root: Node,
+}
+
+impl Projection {
+    /// A projection of these paths. A path within another one in the
+    /// projection is redundant, and is dropped.
+    pub fn new<I, T>(paths: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<Path>,
+    {
+        paths.into_iter().collect()
+    }
+
+    /// Parses a projection expression, as found in
+    /// [`Expression::projection_expression`], resolving any expression
+    /// attribute names (`#0`) with the given map.
+    ///
+    /// [`Expression::projection_expression`]: crate::Expression::projection_expression
+    pub fn parse(
+        projection_expression: &str,
+        expression_attribute_names: Option<&HashMap<String, String>>,
+    ) -> Result<Self, ProjectionParseError> {
+        projection_expression
+            .split(',')
+            .map(|path| {
+                let path = path.trim();
+                let mut path: Path = path
+                    .parse()
+                    .map_err(|PathParseError| ProjectionParseError::Path(path.to_string()))?;
+
+                for element in &mut path.elements {
+                    let name = match element {
+                        Element::Name(name) => name,
+                        Element::IndexedField(field) => &mut field.name,
+                    };
+
+                    if name.name.starts_with('#') {
+                        name.name = expression_attribute_names
+                            .and_then(|names| names.get(&name.name))
+                            .cloned()
+                            .ok_or_else(|| ProjectionParseError::UnknownName(name.name.clone()))?;
+                    }
+                }
+
+                Ok(path)
+            })
+            .collect()
+    }
+
+    /// Adds a path to this projection.
+    pub fn insert<T>(&mut self, path: T)
+    where
+        T: Into<Path>,
+    {
+        let path = path.into();
+
+        let mut node = &mut self.root;
+        for step in steps(&path) {
+            if node.whole {
+                // Already covered by a broader path.
+                return;
+            }
+
+            node = node.children.entry(step).or_default();
+        }
+
+        node.whole = true;
+        node.children.clear();
+    }
+
+    /// Returns only the projected attributes of the item.
+    pub fn apply(&self, item: &HashMap<String, AttributeValue>) -> HashMap<String, AttributeValue> {
+        self.root.apply_map(item)
+    }
+
+    /// Checks that every projected path is in the item, such as one returned
+    /// by DynamoDB for a request using this projection.
+    ///
+    /// The error has the [`Path`] of the first missing attribute, or of an
+    /// attribute that isn't a map or list when a projected path goes within
+    /// it.
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use std::collections::HashMap;
+    ///
+    /// use aws_sdk_dynamodb::types::AttributeValue;
+    /// use dynamodb_expression::{
+    ///     condition::attribute_type::Type, path::Projection, value::DecodeErrorKind, Path,
+    /// };
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let item = HashMap::from([
+    ///     ("id".to_string(), AttributeValue::S("1".into())),
+    ///     ("address".to_string(), AttributeValue::S("somewhere".into())),
+    /// ]);
+    ///
+    /// Projection::new(["id".parse::<Path>()?]).check(&item)?;
+    ///
+    /// let err = Projection::new(["name".parse::<Path>()?]).check(&item).unwrap_err();
+    /// assert_eq!("name".parse::<Path>()?, err.path());
+    /// assert_eq!(&DecodeErrorKind::Missing, err.kind());
+    ///
+    /// let err = Projection::new(["address.city".parse::<Path>()?])
+    ///     .check(&item)
+    ///     .unwrap_err();
+    /// assert_eq!("address".parse::<Path>()?, err.path());
+    /// assert_eq!(
+    ///     &DecodeErrorKind::WrongType {
+    ///         expected: Type::Map,
+    ///         found: Type::String
+    ///     },
+    ///     err.kind()
+    /// );
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn check(&self, item: &HashMap<String, AttributeValue>) -> Result<(), DecodeError> {
+        let mut path = Vec::new();
+
+        self.root.children.iter().try_for_each(|(step, node)| {
+            let value = match step {
+                Step::Name(name) => item.get(name),
+                // Paths always start with a name.
+                Step::Index(_) => None,
+            };
+
+            node.check_child(step, value, &mut path)
+        })
+    }
+}
+
+impl<T> FromIterator<T> for Projection
+where
+    T: Into<Path>,
+{
+    fn from_iter<I>(paths: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut projection = Self::default();
+        paths.into_iter().for_each(|path| projection.insert(path));
+
+        projection
+    }
+}
+
+/// The attribute names and list indexes of a path, in order. For example,
+/// `a[1][2].b` is `a`, `1`, `2`, `b`.
+pub(crate) fn steps(path: &Path) -> impl Iterator<Item = Step> + '_ {
+    path.elements.iter().flat_map(|element| {
+        let (name, indexes) = match element {
+            Element::Name(name) => (name, &[][..]),
+            Element::IndexedField(field) => (&field.name, &field.indexes[..]),
+        };
+
+        core::iter::once(Step::Name(name.name.clone()))
+            .chain(indexes.iter().copied().map(Step::Index))
+    })
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum Step {
+    Name(String),
+    Index(usize),
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct Node {
+    /// The whole value at this point is projected.
+    whole: bool,
+    children: BTreeMap<Step, Node>,
+}
+
+impl Node {
+    fn apply(&self, value: &AttributeValue) -> Option<AttributeValue> {
+        if self.whole {
+            return Some(value.clone());
+        }
+
+        match value {
+            AttributeValue::M(map) => Some(self.apply_map(map))
+                .filter(|map| !map.is_empty())
+                .map(AttributeValue::M),
+            AttributeValue::L(list) => Some(
+                self.children
+                    .iter()
+                    .filter_map(|(step, node)| match step {
+                        Step::Index(index) => list.get(*index).and_then(|value| node.apply(value)),
+                        Step::Name(_) => None,
+                    })
+                    .collect::<Vec<_>>(),
+            )
+            .filter(|list| !list.is_empty())
+            .map(AttributeValue::L),
+            _ => None,
+        }
+    }
+
+    /// Checks that `value` has everything projected by this node.
+    fn check(
+        &self,
+        value: &AttributeValue,
+        path: &mut Vec<ValuePathSegment>,
+    ) -> Result<(), DecodeError> {
+        if self.whole {
+            return Ok(());
+        }
+
+        self.children.iter().try_for_each(|(step, node)| {
+            let child = match (step, value) {
+                (Step::Name(name), AttributeValue::M(map)) => map.get(name),
+                (Step::Index(index), AttributeValue::L(list)) => list.get(*index),
+                (step, value) => {
+                    let kind = match attribute_type(value) {
+                        Some(found) => DecodeErrorKind::WrongType {
+                            expected: match step {
+                                Step::Name(_) => Type::Map,
+                                Step::Index(_) => Type::List,
+                            },
+                            found,
+                        },
+                        None => DecodeErrorKind::UnknownType,
+                    };
+
+                    return Err(DecodeError::at(path.iter().cloned(), kind));
+                }
+            };
+
+            node.check_child(step, child, path)
+        })
+    }
+
+    /// Checks the value found at `step`, where this is the node for that step.
+    fn check_child(
+        &self,
+        step: &Step,
+        value: Option<&AttributeValue>,
+        path: &mut Vec<ValuePathSegment>,
+    ) -> Result<(), DecodeError> {
+        path.push(match step {
+            Step::Name(name) => ValuePathSegment::Key(name.clone()),
+            Step::Index(index) => ValuePathSegment::Index(*index),
+        });
+
+        let result = match value {
+            Some(value) => self.check(value, path),
+            None => Err(DecodeError::at(
+                path.iter().cloned(),
+                DecodeErrorKind::Missing,
+            )),
+        };
+
+        path.pop();
+
+        result
+    }
+
+    fn apply_map(&self, map: &HashMap<String, AttributeValue>) -> HashMap<String, AttributeValue> {
+        self.children
+            .iter()
+            .filter_map(|(step, node)| match step {
+                Step::Name(name) => map
+                    .get(name)
+                    .and_then(|value| node.apply(value))
+                    .map(|value| (name.clone(), value)),
+                Step::Index(_) => None,
+            })
+            .collect()
+    }
+}
+
+/// A projection expression failed to parse.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProjectionParseError {
+    /// One of the paths in the projection expression isn't a valid document
+    /// path.
+    Path(String),
+
+    /// An expression attribute name (`#0`) isn't in the expression attribute
+    /// names.
+    UnknownName(String),
+}
+
+impl fmt::Display for ProjectionParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Path(path) => write!(f, "invalid document path in projection: {path}"),
+            Self::UnknownName(name) => {
+                write!(f, "unknown expression attribute name in projection: {name}")
+            }
+        }
+    }
+}
+
+impl Error for ProjectionParseError {}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use aws_sdk_dynamodb::types::AttributeValue;
+    use pretty_assertions::assert_eq;
+
+    use crate::{condition::attribute_type::Type, value::DecodeErrorKind, Path};
+
+    use super::{Projection, ProjectionParseError};
+
+    fn s(s: &str) -> AttributeValue {
+        AttributeValue::S(s.into())
+    }
+
+    fn m<const N: usize>(entries: [(&str, AttributeValue); N]) -> AttributeValue {
+        AttributeValue::M(map(entries))
+    }
+
+    fn map<const N: usize>(
+        entries: [(&str, AttributeValue); N],
+    ) -> HashMap<String, AttributeValue> {
+        entries
+            .into_iter()
+            .map(|(name, value)| (name.to_string(), value))
+            .collect()
+    }
+
+    fn projection(paths: &[&str]) -> Projection {
+        paths
+            .iter()
+            .map(|path| path.parse::<Path>().unwrap())
+            .collect()
+    }
+
+    fn item() -> HashMap<String, AttributeValue> {
+        map([
+            ("id", s("1")),
+            (
+                "a",
+                m([
+                    (
+                        "b",
+                        AttributeValue::L(vec![
+                            m([("c", s("c0")), ("d", s("d0"))]),
+                            m([("c", s("c1")), ("d", s("d1"))]),
+                            m([("c", s("c2")), ("d", s("d2"))]),
+                        ]),
+                    ),
+                    ("e", s("e")),
+                ]),
+            ),
+            (
+                "grid",
+                AttributeValue::L(vec![
+                    AttributeValue::L(vec![s("00"), s("01")]),
+                    AttributeValue::L(vec![s("10"), s("11")]),
+                ]),
+            ),
+        ])
+    }
+
+    #[test]
+    fn nested() {
+        assert_eq!(
+            map([
+                ("id", s("1")),
+                (
+                    "a",
+                    m([(
+                        "b",
+                        AttributeValue::L(vec![m([("d", s("d0"))]), m([("c", s("c2"))])])
+                    )])
+                ),
+            ]),
+            projection(&["id", "a.b[2].c", "a.b[0].d"]).apply(&item())
+        );
+
+        assert_eq!(
+            map([(
+                "grid",
+                AttributeValue::L(vec![AttributeValue::L(vec![s("11")])])
+            )]),
+            projection(&["grid[1][1]", "grid[0][5]"]).apply(&item())
+        );
+    }
+
+    #[test]
+    fn missing() {
+        assert_eq!(
+            HashMap::new(),
+            projection(&["nope", "a.nope", "a.b[9]", "a.e.f", "id[0]", "grid.x"]).apply(&item())
+        );
+    }
+
+    #[test]
+    fn overlapping() {
+        let expected = map([("a", item()["a"].clone())]);
+        assert_eq!(
+            expected,
+            projection(&["a.b[1].c", "a", "a.e"]).apply(&item())
+        );
+        assert_eq!(expected, projection(&["a", "a.b[1].c"]).apply(&item()));
+
+        assert_eq!(
+            map([(
+                "a",
+                m([(
+                    "b",
+                    AttributeValue::L(vec![m([("c", s("c1")), ("d", s("d1"))])])
+                )])
+            )]),
+            projection(&["a.b[1].c", "a.b[1]"]).apply(&item())
+        );
+    }
+
+    #[test]
+    fn check() {
+        assert_eq!(
+            Ok(()),
+            projection(&["id", "a.b[2].c", "a.b[0]", "a.e", "grid[1][0]"]).check(&item())
+        );
+
+        let check = |paths: &[&str]| {
+            let err = projection(paths).check(&item()).unwrap_err();
+            (err.path().to_string(), err.kind().clone())
+        };
+
+        assert_eq!(
+            (String::from("a.b[1].x"), DecodeErrorKind::Missing),
+            check(&["a.b[2].c", "a.b[1].x"])
+        );
+        assert_eq!(
+            (String::from("grid[0][2]"), DecodeErrorKind::Missing),
+            check(&["grid[0][2]"])
+        );
+        assert_eq!(
+            (String::from("nope"), DecodeErrorKind::Missing),
+            check(&["id", "nope"])
+        );
+        assert_eq!(
+            (
+                String::from("a.e"),
+                DecodeErrorKind::WrongType {
+                    expected: Type::Map,
+                    found: Type::String
+                }
+            ),
+            check(&["a.e.f"])
+        );
+        assert_eq!(
+            (
+                String::from("a"),
+                DecodeErrorKind::WrongType {
+                    expected: Type::List,
+                    found: Type::Map
+                }
+            ),
+            check(&["a[0]"])
+        );
+    }
+
+    #[test]
+    fn parse() {
+        let names = HashMap::from([
+            ("#0".to_string(), "a".to_string()),
+            ("#1".to_string(), "c".to_string()),
+            ("#2".to_string(), "id".to_string()),
+        ]);
+
+        assert_eq!(
+            projection(&["a.b[2].c", "id"]),
+            Projection::parse("#0.b[2].#1, #2", Some(&names)).unwrap()
+        );
+
+        assert_eq!(
+            Err(ProjectionParseError::UnknownName("#3".into())),
+            Projection::parse("#0, #3", Some(&names))
+        );
+        assert_eq!(
+            Err(ProjectionParseError::UnknownName("#0".into())),
+            Projection::parse("#0", None)
+        );
+        assert_eq!(
+            Err(ProjectionParseError::Path("a[x]".into())),
+            Projection::parse("id, a[x]", None)
+        );
+    }
+}
@@ -0,0 +1,507 @@
+//! Redacting values from the `Display` and `Debug` output of expressions, so
+//! they can be logged without leaking sensitive data.
+//!
+//! Build a [`Redaction`] policy marking which [`Path`]s and which value
+//! [`Type`]s are sensitive, then use [`Redact::redacted`] on a [`Condition`],
+//! [`KeyCondition`], [`Update`], or [`Expression`] to get a wrapper whose
+//! `Display` and `Debug` output uses that policy.
+//!
+//! ```
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! use dynamodb_expression::{
+//!     condition::attribute_type::Type,
+//!     redact::{Redact, Redaction},
+//!     Expression, Num, Path,
+//! };
+//! # use pretty_assertions::assert_eq;
+//!
+//! let condition = "email"
+//!     .parse::<Path>()?
+//!     .equal("jane@example.com")
+//!     .and("age".parse::<Path>()?.greater_than(Num::new(21)))
+//!     .and("status".parse::<Path>()?.equal("active"));
+//!
+//! let redaction = Redaction::new()
+//!     .with_paths(["email".parse::<Path>()?])
+//!     .with_types([Type::Number]);
+//!
+//! assert_eq!(
+//!     r#"email = <redacted> AND age > <redacted> AND status = "active""#,
+//!     condition.redacted(&redaction).to_string()
+//! );
+//!
+//! let expression = Expression::builder().with_condition(condition).build();
+//! assert_eq!(
+//!     r#"condition: email = <redacted> AND age > <redacted> AND status = "active""#,
+//!     expression.redacted(&redaction).to_string()
+//! );
+//! #
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`Condition`]: crate::condition::Condition
+//! [`KeyCondition`]: crate::key::KeyCondition
+//! [`Update`]: crate::update::Update
+
+use core::fmt;
+use std::collections::{BTreeMap, HashMap};
+
+use aws_sdk_dynamodb::types::AttributeValue;
+use hmac::{Hmac, KeyInit, Mac};
+use sha2::Sha256;
+
+use crate::{
+    condition::{attribute_type::Type, Condition},
+    expression::Renderer,
+    key::KeyCondition,
+    update::Update,
+    value::{attribute_type, Value},
+    Expression, Path,
+};
+
+pub(crate) const REDACTED: &str = "<redacted>";
+
+/// A policy for which values to redact from `Display` and `Debug` output.
+///
+/// A value is redacted if it's used in the same comparison, function, or update
+/// action as one of the policy's paths, or an attribute within or containing one
+/// of them, or if it's one of the policy's types. Equal values are redacted
+/// everywhere they're used, since an expression shares one placeholder for
+/// them.
+///
+/// See the [module documentation](self) for an example.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Redaction {
+    paths: Vec<Path>,
+    types: Vec<Type>,
+    all: bool,
+    hash_key: Option<HashKey>,
+}
+
+/// The secret key for [`Redaction::hashed`]. Kept out of `Debug` output.
+#[derive(Clone, PartialEq, Eq)]
+struct HashKey(Vec<u8>);
+
+impl fmt::Debug for HashKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("..")
+    }
+}
+
+impl Redaction {
+    /// A policy that doesn't redact anything until paths or types are added.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A policy that redacts every value.
+    pub fn all() -> Self {
+        Self {
+            all: true,
+            ..Self::default()
+        }
+    }
+
+    /// Adds paths whose values should be redacted.
+    pub fn with_paths<I, T>(mut self, paths: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<Path>,
+    {
+        self.paths.extend(paths.into_iter().map(Into::into));
+
+        self
+    }
+
+    /// Adds types of values that should always be redacted, wherever they're
+    /// used.
+    pub fn with_types<I>(mut self, types: I) -> Self
+    where
+        I: IntoIterator<Item = Type>,
+    {
+        self.types.extend(types);
+
+        self
+    }
+
+    /// Shows a hash of each redacted value, like `<redacted:0123456789abcdef>`,
+    /// instead of only `<redacted>`. Equal values get equal hashes, so they can
+    /// be correlated across log lines.
+    ///
+    /// The hash is an HMAC-SHA256 of the value, keyed with `key`. Without the
+    /// key, it can't be used to guess the value, even one with few
+    /// possibilities. It's the same across processes and releases of this
+    /// crate that use the same key. Keep the key secret, like a password.
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use dynamodb_expression::{
+    ///     redact::{Redact, Redaction},
+    ///     Path,
+    /// };
+    ///
+    /// let email = "email".parse::<Path>()?;
+    /// let redaction = Redaction::new()
+    ///     .with_paths([email.clone()])
+    ///     .hashed(b"a secret key");
+    ///
+    /// let condition = email.clone().equal("jane@example.com");
+    /// let shown = condition.redacted(&redaction).to_string();
+    /// assert!(shown.starts_with("email = <redacted:"));
+    /// assert!(!shown.contains("jane"));
+    ///
+    /// // The same value gets the same hash.
+    /// assert_eq!(
+    ///     shown,
+    ///     email.equal("jane@example.com").redacted(&redaction).to_string()
+    /// );
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn hashed<T>(mut self, key: T) -> Self
+    where
+        T: AsRef<[u8]>,
+    {
+        self.hash_key = Some(HashKey(key.as_ref().to_vec()));
+
+        self
+    }
+
+    pub(crate) fn has_paths(&self) -> bool {
+        !self.paths.is_empty()
+    }
+
+    /// Whether values used with the path should be redacted. That includes
+    /// paths containing a redacted one, since their values may include it.
+    pub(crate) fn covers(&self, path: &Path) -> bool {
+        self.paths
+            .iter()
+            .any(|redacted| redacted.covers(path) || path.covers(redacted))
+    }
+
+    /// Whether the value should be redacted, given whether it's used with a
+    /// redacted path.
+    fn redacts(&self, value: &AttributeValue, by_path: bool) -> bool {
+        self.all || by_path || attribute_type(value).is_some_and(|t| self.types.contains(&t))
+    }
+
+    /// Renders a value for humans, redacting it if needed.
+    pub(crate) fn display(&self, value: &AttributeValue, by_path: bool) -> String {
+        if !self.redacts(value, by_path) {
+            return Value::try_from(value.clone())
+                .map_or_else(|_| String::from("<unknown>"), |value| value.to_string());
+        }
+
+        let Some(HashKey(key)) = &self.hash_key else {
+            return String::from(REDACTED);
+        };
+
+        // The DynamoDB JSON form includes the type, so `"1"` and `1` differ.
+        let text = match Value::try_from(value.clone()) {
+            Ok(value) => value.into_dynamodb_json().to_string(),
+            Err(_) => format!("{value:?}"),
+        };
+
+        let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+        mac.update(text.as_bytes());
+
+        let hash: String = mac.finalize().into_bytes()[..8]
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect();
+
+        format!("<redacted:{hash}>")
+    }
+}
+
+/// Types with `Display` and `Debug` output that can have values redacted. See
+/// the [module documentation](self).
+pub trait Redact {
+    /// Writes what the `Display` implementation would, with values redacted.
+    fn fmt_redacted(&self, redaction: &Redaction, f: &mut fmt::Formatter<'_>) -> fmt::Result;
+
+    /// Writes what the `Debug` implementation would, with values redacted.
+    fn fmt_redacted_debug(&self, redaction: &Redaction, f: &mut fmt::Formatter<'_>) -> fmt::Result;
+
+    /// Wraps this for `Display` and `Debug` output with values redacted
+    /// according to the [`Redaction`] policy.
+    fn redacted<'a>(&'a self, redaction: &'a Redaction) -> Redacted<'a, Self> {
+        Redacted {
+            value: self,
+            redaction,
+        }
+    }
+}
+
+/// Something with its values redacted in its `Display` and `Debug` output.
+///
+/// See [`Redact::redacted`].
+#[must_use = "Use the `Display` or `Debug` implementation to render it"]
+pub struct Redacted<'a, T: ?Sized> {
+    value: &'a T,
+    redaction: &'a Redaction,
+}
+
+impl<T> fmt::Display for Redacted<'_, T>
+where
+    T: Redact + ?Sized,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.value.fmt_redacted(self.redaction, f)
+    }
+}
+
+impl<T> fmt::Debug for Redacted<'_, T>
+where
+    T: Redact + ?Sized,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.value.fmt_redacted_debug(self.redaction, f)
+    }
+}
+
+/// Implements [`Redact`] for a type by building an [`Expression`] from it.
+macro_rules! redact_via_expression {
+    ($ty:ident, $with:ident) => {
+        impl Redact for $ty {
+            fn fmt_redacted(
+                &self,
+                redaction: &Redaction,
+                f: &mut fmt::Formatter<'_>,
+            ) -> fmt::Result {
+                let expression = Expression::builder().$with(self.clone()).build();
+
+                Renderer::new(&expression, redaction).write_unlabeled(f)
+            }
+
+            fn fmt_redacted_debug(
+                &self,
+                redaction: &Redaction,
+                f: &mut fmt::Formatter<'_>,
+            ) -> fmt::Result {
+                f.debug_tuple(stringify!($ty))
+                    .field(&format_args!("{}", self.redacted(redaction)))
+                    .finish()
+            }
+        }
+    };
+}
+
+redact_via_expression!(Condition, with_condition);
+redact_via_expression!(KeyCondition, with_key_condition);
+redact_via_expression!(Update, with_update);
+
+impl Redact for Expression {
+    fn fmt_redacted(&self, redaction: &Redaction, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Renderer::new(self, redaction).write(f)
+    }
+
+    /// Writes the fields, with the expression attribute names and values sorted
+    /// by placeholder, and the values redacted.
+    fn fmt_redacted_debug(&self, redaction: &Redaction, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self {
+            condition_expression,
+            key_condition_expression,
+            update_expression,
+            filter_expression,
+            projection_expression,
+            expression_attribute_names,
+            expression_attribute_values,
+        } = self;
+
+        f.debug_struct("Expression")
+            .field("condition_expression", condition_expression)
+            .field("key_condition_expression", key_condition_expression)
+            .field("update_expression", update_expression)
+            .field("filter_expression", filter_expression)
+            .field("projection_expression", projection_expression)
+            .field(
+                "expression_attribute_names",
+                &expression_attribute_names
+                    .as_ref()
+                    .map(|names| names.iter().collect::<BTreeMap<_, _>>()),
+            )
+            .field(
+                "expression_attribute_values",
+                &expression_attribute_values.as_ref().map(|values| Values {
+                    values,
+                    renderer: Renderer::new(self, redaction),
+                    redaction,
+                }),
+            )
+            .finish()
+    }
+}
+
+struct Values<'a> {
+    values: &'a HashMap<String, AttributeValue>,
+    renderer: Renderer<'a>,
+    redaction: &'a Redaction,
+}
+
+impl fmt::Debug for Values<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let by_path = self.renderer.redacted_placeholders();
+
+        f.debug_map()
+            .entries(
+                self.values
+                    .iter()
+                    .collect::<BTreeMap<_, _>>()
+                    .into_iter()
+                    .map(|(placeholder, value)| {
+                        let redact = self
+                            .redaction
+                            .redacts(value, by_path.contains(placeholder.as_str()));
+
+                        (
+                            placeholder,
+                            Shown {
+                                value,
+                                redact,
+                                redaction: self.redaction,
+                            },
+                        )
+                    }),
+            )
+            .finish()
+    }
+}
+
+/// An expression attribute value, or its redacted form.
+struct Shown<'a> {
+    value: &'a AttributeValue,
+    redact: bool,
+    redaction: &'a Redaction,
+}
+
+impl fmt::Debug for Shown<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.redact {
+            f.write_str(&self.redaction.display(self.value, true))
+        } else {
+            self.value.fmt(f)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use crate::{condition::attribute_type::Type, path::test::path, Expression, Num};
+
+    use super::{Redact, Redaction};
+
+    #[test]
+    fn ast() {
+        let redaction = Redaction::new()
+            .with_paths([path("user.email")])
+            .with_types([Type::Boolean]);
+
+        let key_condition = path("id")
+            .key()
+            .equal("a")
+            .and(path("user.email").key().begins_with("jane"));
+        assert_eq!(
+            r#"id = "a" AND begins_with(user.email, <redacted>)"#,
+            key_condition.redacted(&redaction).to_string()
+        );
+        assert_eq!(
+            r#"KeyCondition(id = "a" AND begins_with(user.email, <redacted>))"#,
+            format!("{:?}", key_condition.redacted(&redaction))
+        );
+
+        let update = path("user")
+            .set(Num::new(1))
+            .and(path("verified").set(true))
+            .and(path("user.email[0]").set("x"));
+        assert_eq!(
+            "SET user = <redacted>, verified = <redacted>, user.email[0] = <redacted>",
+            update.redacted(&redaction).to_string(),
+            "The value of `user` may include `user.email`"
+        );
+
+        assert_eq!(
+            "SET user = <redacted>, verified = <redacted>, user.email[0] = <redacted>",
+            update.redacted(&Redaction::all()).to_string()
+        );
+
+        let redaction = Redaction::new();
+        assert_eq!(
+            r#"SET user = 1, verified = true, user.email[0] = "x""#,
+            update.redacted(&redaction).to_string(),
+            "Nothing is redacted without paths or types"
+        );
+        assert_eq!(update.to_string(), update.redacted(&redaction).to_string());
+    }
+
+    #[test]
+    fn expression_debug() {
+        let expression = Expression::builder()
+            .with_condition(path("email").equal("jane@example.com"))
+            .with_update(path("count").set(Num::new(3)))
+            .build();
+
+        let redaction = Redaction::new().with_paths([path("email")]);
+        assert_eq!(
+            r##"Expression { condition_expression: Some("#0 = :0"), key_condition_expression: None, update_expression: Some("SET #1 = :1"), filter_expression: None, projection_expression: None, expression_attribute_names: Some({"#0": "email", "#1": "count"}), expression_attribute_values: Some({":0": <redacted>, ":1": N("3")}) }"##,
+            format!("{:?}", expression.redacted(&redaction))
+        );
+
+        let redaction = redaction.with_types([Type::Number]).hashed("key");
+        let debug = format!("{:?}", expression.redacted(&redaction));
+        assert!(!debug.contains("jane"), "{debug}");
+        assert!(!debug.contains(r#"N("3")"#), "{debug}");
+        assert_eq!(2, debug.matches("<redacted:").count(), "{debug}");
+    }
+
+    #[test]
+    fn shared_value() {
+        let condition = path("email")
+            .equal("jane@example.com")
+            .or(path("backup").equal("jane@example.com"));
+        let redaction = Redaction::new().with_paths([path("email")]);
+
+        assert_eq!(
+            "email = <redacted> OR backup = <redacted>",
+            condition.redacted(&redaction).to_string(),
+            "The builder uses one placeholder for both values, so both are redacted"
+        );
+
+        let expression = Expression::builder().with_condition(condition).build();
+        assert_eq!(
+            "condition: email = <redacted> OR backup = <redacted>",
+            expression.redacted(&redaction).to_string()
+        );
+        assert!(format!("{:?}", expression.redacted(&redaction)).contains(r#"":0": <redacted>"#));
+        assert_eq!(
+            "condition: email = <redacted> OR backup = <redacted>",
+            expression
+                .explain()
+                .with_redacted([path("email")])
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn hashed() {
+        let condition = path("email").equal("jane@example.com");
+        let shown = |key: &str| {
+            condition
+                .redacted(&Redaction::new().with_paths([path("email")]).hashed(key))
+                .to_string()
+        };
+
+        // HMAC-SHA256, so it doesn't change between builds.
+        assert_eq!("email = <redacted:85e7803d9101821b>", shown("key"));
+        assert_ne!(shown("key"), shown("other key"));
+
+        assert!(
+            !format!("{:?}", Redaction::new().hashed("secret")).contains("secret"),
+            "The key isn't in `Debug` output"
+        );
+    }
+}
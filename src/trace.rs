@@ -0,0 +1,747 @@
+//! [`tracing`][1] spans for sending DynamoDB requests.
+//!
+//! Send a request built with one of the [`Expression`] helpers with
+//! [`SendTraced::send_traced`] instead of `.send()` to run it in a
+//! `send_request` span, at the `DEBUG` level. These also get one:
+//! * The streams in the `pagination` module (with the `pagination` feature)
+//! * [`BatchGet::send`]
+//! * [`BatchWrite::send`]
+//!
+//! The span has these fields:
+//! * `operation`: The DynamoDB operation, like `Query`.
+//! * `table` and `index`: The table and index names. For [`BatchGet::send`]
+//!   and [`BatchWrite::send`], `table` lists every table, comma separated.
+//! * `expression`: The expression, rendered as with [`Expression::explain`],
+//!   with values redacted by the [`Redaction`] given to
+//!   [`send_traced`](SendTraced::send_traced), to the `pagination` streams,
+//!   or with [`BatchGet::with_redaction`].
+//! * `placeholders`: The number of expression attribute names and values.
+//! * `expression_bytes`: The total size of the expression strings.
+//!
+//! A `BatchWriteItem` has no expression, so its span has none of the
+//! expression fields.
+//!
+//! These are recorded as responses arrive:
+//! * `pages`: The number of pages read, for `query` and `scan`.
+//! * `items`: The number of items read or written.
+//! * `scanned_items`: The number of items DynamoDB evaluated, before any filter
+//!   expression, for `query` and `scan`.
+//! * `consumed_capacity`: The total capacity units consumed. Only recorded if
+//!   the request asks for [`ReturnConsumedCapacity`][2].
+//!
+//! Requests sent with `.send()` aren't traced by this crate.
+//!
+//! Requires the `tracing` feature.
+//!
+//! ```no_run
+//! # async fn example() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+//! use aws_config::BehaviorVersion;
+//! use aws_sdk_dynamodb::Client;
+//! use dynamodb_expression::{redact::Redaction, trace::SendTraced, Expression, Num, Path};
+//!
+//! let client = Client::new(&aws_config::load_defaults(BehaviorVersion::latest()).await);
+//!
+//! let output = Expression::builder()
+//!     .with_key_condition("id".parse::<Path>()?.key().equal(Num::new(42)))
+//!     .build()
+//!     .query(&client)
+//!     .table_name("people")
+//!     .send_traced(&Redaction::all())
+//!     .await?;
+//! #
+//! # _ = output;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [1]: https://docs.rs/tracing/
+//! [2]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/read-write-operations.html#read-write-operations.consumed-capacity
+//! [`BatchGet::send`]: crate::batch::BatchGet::send
+//! [`BatchWrite::send`]: crate::batch::BatchWrite::send
+//! [`BatchGet::with_redaction`]: crate::batch::BatchGet::with_redaction
+
+use core::{fmt, future::Future};
+use std::collections::BTreeSet;
+
+use aws_sdk_dynamodb::{
+    error::SdkError,
+    operation::{
+        delete_item::{builders::DeleteItemFluentBuilder, DeleteItemError, DeleteItemOutput},
+        get_item::{builders::GetItemFluentBuilder, GetItemError, GetItemOutput},
+        put_item::{builders::PutItemFluentBuilder, PutItemError, PutItemOutput},
+        query::{builders::QueryFluentBuilder, QueryError, QueryOutput},
+        scan::{builders::ScanFluentBuilder, ScanError, ScanOutput},
+        update_item::{builders::UpdateItemFluentBuilder, UpdateItemError, UpdateItemOutput},
+    },
+    types::ConsumedCapacity,
+};
+use tracing::{
+    field::{display, Empty},
+    Instrument, Span,
+};
+
+use crate::{
+    redact::{Redact, Redaction},
+    Expression,
+};
+
+/// Sends a request in a `send_request` span. See the
+/// [module documentation](self).
+pub trait SendTraced {
+    type Output;
+    type Error;
+
+    /// Sends the request, like `.send()`, in a `send_request` span. Values in
+    /// the span's `expression` field are redacted according to `redaction`.
+    fn send_traced(
+        self,
+        redaction: &Redaction,
+    ) -> impl Future<Output = Result<Self::Output, SdkError<Self::Error>>> + Send;
+}
+
+/// What's needed to trace a request.
+pub(crate) trait Request {
+    const OPERATION: &'static str;
+
+    type Output;
+    type Error;
+
+    /// The expression, put back together from the request.
+    fn expression(&self) -> Expression;
+
+    fn table(&self) -> Option<&str>;
+
+    fn index(&self) -> Option<&str> {
+        None
+    }
+
+    fn send_request(
+        self,
+    ) -> impl Future<Output = Result<Self::Output, SdkError<Self::Error>>> + Send;
+
+    fn page(output: &Self::Output) -> Page;
+}
+
+/// A `send_request` span for a request.
+pub(crate) fn span<R>(request: &R, redaction: &Redaction) -> Span
+where
+    R: Request,
+{
+    send_span(
+        R::OPERATION,
+        &request.expression(),
+        redaction,
+        request.table(),
+        request.index(),
+    )
+}
+
+macro_rules! send_traced {
+    ($($builder:ty => $output:ty, $error:ty;)*) => {
+        $(
+            impl SendTraced for $builder {
+                type Output = $output;
+                type Error = $error;
+
+                fn send_traced(
+                    self,
+                    redaction: &Redaction,
+                ) -> impl Future<Output = Result<Self::Output, SdkError<Self::Error>>> + Send {
+                    let mut sent = Sent::new(span(&self, redaction));
+                    let send = self.send_request().instrument(sent.span().clone());
+
+                    async move {
+                        let output = send.await?;
+                        sent.record(<Self as Request>::page(&output));
+
+                        Ok(output)
+                    }
+                }
+            }
+        )*
+    };
+}
+
+send_traced! {
+    PutItemFluentBuilder => PutItemOutput, PutItemError;
+    GetItemFluentBuilder => GetItemOutput, GetItemError;
+    UpdateItemFluentBuilder => UpdateItemOutput, UpdateItemError;
+    DeleteItemFluentBuilder => DeleteItemOutput, DeleteItemError;
+    QueryFluentBuilder => QueryOutput, QueryError;
+    ScanFluentBuilder => ScanOutput, ScanError;
+}
+
+fn capacity_units(consumed_capacity: Option<&ConsumedCapacity>) -> Option<f64> {
+    consumed_capacity.and_then(|capacity| capacity.capacity_units)
+}
+
+impl Request for PutItemFluentBuilder {
+    const OPERATION: &'static str = "PutItem";
+
+    type Output = PutItemOutput;
+    type Error = PutItemError;
+
+    fn expression(&self) -> Expression {
+        Expression {
+            condition_expression: self.get_condition_expression().clone(),
+            key_condition_expression: None,
+            update_expression: None,
+            filter_expression: None,
+            projection_expression: None,
+            expression_attribute_names: self.get_expression_attribute_names().clone(),
+            expression_attribute_values: self.get_expression_attribute_values().clone(),
+        }
+    }
+
+    fn table(&self) -> Option<&str> {
+        self.get_table_name().as_deref()
+    }
+
+    fn send_request(
+        self,
+    ) -> impl Future<Output = Result<Self::Output, SdkError<Self::Error>>> + Send {
+        self.send()
+    }
+
+    fn page(output: &Self::Output) -> Page {
+        Page {
+            items: 1,
+            consumed_capacity: capacity_units(output.consumed_capacity()),
+            ..Page::default()
+        }
+    }
+}
+
+impl Request for GetItemFluentBuilder {
+    const OPERATION: &'static str = "GetItem";
+
+    type Output = GetItemOutput;
+    type Error = GetItemError;
+
+    fn expression(&self) -> Expression {
+        Expression {
+            condition_expression: None,
+            key_condition_expression: None,
+            update_expression: None,
+            filter_expression: None,
+            projection_expression: self.get_projection_expression().clone(),
+            expression_attribute_names: self.get_expression_attribute_names().clone(),
+            expression_attribute_values: None,
+        }
+    }
+
+    fn table(&self) -> Option<&str> {
+        self.get_table_name().as_deref()
+    }
+
+    fn send_request(
+        self,
+    ) -> impl Future<Output = Result<Self::Output, SdkError<Self::Error>>> + Send {
+        self.send()
+    }
+
+    fn page(output: &Self::Output) -> Page {
+        Page {
+            items: usize::from(output.item.is_some()),
+            consumed_capacity: capacity_units(output.consumed_capacity()),
+            ..Page::default()
+        }
+    }
+}
+
+impl Request for UpdateItemFluentBuilder {
+    const OPERATION: &'static str = "UpdateItem";
+
+    type Output = UpdateItemOutput;
+    type Error = UpdateItemError;
+
+    fn expression(&self) -> Expression {
+        Expression {
+            condition_expression: self.get_condition_expression().clone(),
+            key_condition_expression: None,
+            update_expression: self.get_update_expression().clone(),
+            filter_expression: None,
+            projection_expression: None,
+            expression_attribute_names: self.get_expression_attribute_names().clone(),
+            expression_attribute_values: self.get_expression_attribute_values().clone(),
+        }
+    }
+
+    fn table(&self) -> Option<&str> {
+        self.get_table_name().as_deref()
+    }
+
+    fn send_request(
+        self,
+    ) -> impl Future<Output = Result<Self::Output, SdkError<Self::Error>>> + Send {
+        self.send()
+    }
+
+    fn page(output: &Self::Output) -> Page {
+        Page {
+            items: 1,
+            consumed_capacity: capacity_units(output.consumed_capacity()),
+            ..Page::default()
+        }
+    }
+}
+
+impl Request for DeleteItemFluentBuilder {
+    const OPERATION: &'static str = "DeleteItem";
+
+    type Output = DeleteItemOutput;
+    type Error = DeleteItemError;
+
+    fn expression(&self) -> Expression {
+        Expression {
+            condition_expression: self.get_condition_expression().clone(),
+            key_condition_expression: None,
+            update_expression: None,
+            filter_expression: None,
+            projection_expression: None,
+            expression_attribute_names: self.get_expression_attribute_names().clone(),
+            expression_attribute_values: self.get_expression_attribute_values().clone(),
+        }
+    }
+
+    fn table(&self) -> Option<&str> {
+        self.get_table_name().as_deref()
+    }
+
+    fn send_request(
+        self,
+    ) -> impl Future<Output = Result<Self::Output, SdkError<Self::Error>>> + Send {
+        self.send()
+    }
+
+    fn page(output: &Self::Output) -> Page {
+        Page {
+            items: 1,
+            consumed_capacity: capacity_units(output.consumed_capacity()),
+            ..Page::default()
+        }
+    }
+}
+
+impl Request for QueryFluentBuilder {
+    const OPERATION: &'static str = "Query";
+
+    type Output = QueryOutput;
+    type Error = QueryError;
+
+    fn expression(&self) -> Expression {
+        Expression {
+            condition_expression: None,
+            key_condition_expression: self.get_key_condition_expression().clone(),
+            update_expression: None,
+            filter_expression: self.get_filter_expression().clone(),
+            projection_expression: self.get_projection_expression().clone(),
+            expression_attribute_names: self.get_expression_attribute_names().clone(),
+            expression_attribute_values: self.get_expression_attribute_values().clone(),
+        }
+    }
+
+    fn table(&self) -> Option<&str> {
+        self.get_table_name().as_deref()
+    }
+
+    fn index(&self) -> Option<&str> {
+        self.get_index_name().as_deref()
+    }
+
+    fn send_request(
+        self,
+    ) -> impl Future<Output = Result<Self::Output, SdkError<Self::Error>>> + Send {
+        self.send()
+    }
+
+    fn page(output: &Self::Output) -> Page {
+        Page {
+            pages: 1,
+            items: output.count.try_into().unwrap_or_default(),
+            scanned_items: output.scanned_count.try_into().ok(),
+            consumed_capacity: capacity_units(output.consumed_capacity()),
+        }
+    }
+}
+
+impl Request for ScanFluentBuilder {
+    const OPERATION: &'static str = "Scan";
+
+    type Output = ScanOutput;
+    type Error = ScanError;
+
+    fn expression(&self) -> Expression {
+        Expression {
+            condition_expression: None,
+            key_condition_expression: None,
+            update_expression: None,
+            filter_expression: self.get_filter_expression().clone(),
+            projection_expression: self.get_projection_expression().clone(),
+            expression_attribute_names: self.get_expression_attribute_names().clone(),
+            expression_attribute_values: self.get_expression_attribute_values().clone(),
+        }
+    }
+
+    fn table(&self) -> Option<&str> {
+        self.get_table_name().as_deref()
+    }
+
+    fn index(&self) -> Option<&str> {
+        self.get_index_name().as_deref()
+    }
+
+    fn send_request(
+        self,
+    ) -> impl Future<Output = Result<Self::Output, SdkError<Self::Error>>> + Send {
+        self.send()
+    }
+
+    fn page(output: &Self::Output) -> Page {
+        Page {
+            pages: 1,
+            items: output.count.try_into().unwrap_or_default(),
+            scanned_items: output.scanned_count.try_into().ok(),
+            consumed_capacity: capacity_units(output.consumed_capacity()),
+        }
+    }
+}
+
+/// A `send_request` span. Record what comes back with [`Sent`].
+fn send_span(
+    operation: &'static str,
+    expression: &Expression,
+    redaction: &Redaction,
+    table: Option<&str>,
+    index: Option<&str>,
+) -> Span {
+    tracing::debug_span!(
+        "send_request",
+        operation,
+        table,
+        index,
+        expression = %Rendered(expression, redaction),
+        placeholders = placeholders(expression),
+        expression_bytes = expression_bytes(expression),
+        pages = Empty,
+        items = Empty,
+        scanned_items = Empty,
+        consumed_capacity = Empty,
+    )
+}
+
+/// A `send_request` span for a `BatchGetItem` or `BatchWriteItem`, which can
+/// be for more than one table. Record what comes back with [`Sent`].
+///
+/// `BatchWriteItem` has no expression, so the expression fields are only
+/// recorded if there's an `expression`.
+pub(crate) fn batch_span<'a, T>(
+    operation: &'static str,
+    tables: T,
+    expression: Option<(&Expression, &Redaction)>,
+) -> Span
+where
+    T: IntoIterator<Item = &'a str>,
+{
+    let tables: BTreeSet<_> = tables.into_iter().collect();
+
+    let span = tracing::debug_span!(
+        "send_request",
+        operation,
+        table = %tables.into_iter().collect::<Vec<_>>().join(", "),
+        expression = Empty,
+        placeholders = Empty,
+        expression_bytes = Empty,
+        items = Empty,
+        consumed_capacity = Empty,
+    );
+
+    if let Some((expression, redaction)) = expression {
+        span.record("expression", display(Rendered(expression, redaction)));
+        span.record("placeholders", placeholders(expression));
+        span.record("expression_bytes", expression_bytes(expression));
+    }
+
+    span
+}
+
+fn placeholders(expression: &Expression) -> usize {
+    expression
+        .expression_attribute_names
+        .as_ref()
+        .map_or(0, |names| names.len())
+        + expression
+            .expression_attribute_values
+            .as_ref()
+            .map_or(0, |values| values.len())
+}
+
+fn expression_bytes(expression: &Expression) -> usize {
+    [
+        &expression.condition_expression,
+        &expression.key_condition_expression,
+        &expression.update_expression,
+        &expression.filter_expression,
+        &expression.projection_expression,
+    ]
+    .into_iter()
+    .flatten()
+    .map(String::len)
+    .sum()
+}
+
+/// Renders the expression, only when the span is actually recorded.
+struct Rendered<'a>(&'a Expression, &'a Redaction);
+
+impl fmt::Display for Rendered<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.redacted(self.1).fmt(f)
+    }
+}
+
+/// What came back from requests sent for a `send_request` span.
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct Page {
+    /// The number of `query` or `scan` pages.
+    pub(crate) pages: usize,
+    pub(crate) items: usize,
+    pub(crate) scanned_items: Option<usize>,
+    pub(crate) consumed_capacity: Option<f64>,
+}
+
+/// Running totals for a `send_request` span, recorded as each response
+/// arrives.
+#[derive(Debug, Clone)]
+pub(crate) struct Sent {
+    span: Span,
+    pages: usize,
+    items: usize,
+    scanned_items: Option<usize>,
+    consumed_capacity: Option<f64>,
+}
+
+impl Sent {
+    pub(crate) fn new(span: Span) -> Self {
+        Self {
+            span,
+            pages: 0,
+            items: 0,
+            scanned_items: None,
+            consumed_capacity: None,
+        }
+    }
+
+    pub(crate) fn span(&self) -> &Span {
+        &self.span
+    }
+
+    /// Adds what came back from a request to the totals.
+    pub(crate) fn record(&mut self, page: Page) {
+        let Page {
+            pages,
+            items,
+            scanned_items,
+            consumed_capacity,
+        } = page;
+
+        if pages > 0 {
+            self.pages += pages;
+            self.span.record("pages", self.pages);
+        }
+
+        self.items += items;
+        self.span.record("items", self.items);
+
+        if let Some(scanned_items) = scanned_items {
+            let total = self.scanned_items.unwrap_or_default() + scanned_items;
+            self.scanned_items = Some(total);
+            self.span.record("scanned_items", total);
+        }
+
+        if let Some(consumed_capacity) = consumed_capacity {
+            let total = self.consumed_capacity.unwrap_or_default() + consumed_capacity;
+            self.consumed_capacity = Some(total);
+            self.span.record("consumed_capacity", total);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        collections::HashMap,
+        sync::{Arc, Mutex},
+    };
+
+    use aws_config::BehaviorVersion;
+    use aws_sdk_dynamodb::Client;
+    use pretty_assertions::assert_eq;
+    use tracing::{
+        field::{Field, Visit},
+        span, Event, Metadata, Subscriber,
+    };
+
+    use crate::{redact::Redaction, Expression, Num, Path};
+
+    use super::{batch_span, span, Page, Sent};
+
+    /// Keeps the fields recorded on the most recent span.
+    #[derive(Default, Clone)]
+    struct Fields(Arc<Mutex<HashMap<String, String>>>);
+
+    impl Visit for Fields {
+        fn record_debug(&mut self, field: &Field, value: &dyn core::fmt::Debug) {
+            self.0
+                .lock()
+                .unwrap()
+                .insert(field.name().to_string(), format!("{value:?}"));
+        }
+    }
+
+    impl Subscriber for Fields {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, span: &span::Attributes<'_>) -> span::Id {
+            self.0.lock().unwrap().clear();
+            span.record(&mut self.clone());
+            span::Id::from_u64(1)
+        }
+
+        fn record(&self, _: &span::Id, values: &span::Record<'_>) {
+            values.record(&mut self.clone());
+        }
+
+        fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+
+        fn event(&self, _: &Event<'_>) {}
+
+        fn enter(&self, _: &span::Id) {}
+
+        fn exit(&self, _: &span::Id) {}
+    }
+
+    impl Fields {
+        fn get(&self) -> HashMap<String, String> {
+            self.0.lock().unwrap().clone()
+        }
+    }
+
+    fn fields<'a, I>(fields: I) -> HashMap<String, String>
+    where
+        I: IntoIterator<Item = (&'a str, &'a str)>,
+    {
+        fields
+            .into_iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn spans() {
+        let expression = Expression::builder()
+            .with_key_condition("id".parse::<Path>().unwrap().key().equal("a"))
+            .with_filter("age".parse::<Path>().unwrap().greater_than(Num::new(21)))
+            .build();
+
+        let client = Client::from_conf(
+            aws_sdk_dynamodb::Config::builder()
+                .behavior_version(BehaviorVersion::latest())
+                .build(),
+        );
+
+        let recorded = Fields::default();
+        tracing::subscriber::with_default(recorded.clone(), || {
+            let query = expression.clone().query(&client).table_name("people");
+
+            let _span = span(&query, &Redaction::all());
+            assert_eq!(
+                fields([
+                    ("operation", r#""Query""#),
+                    ("table", r#""people""#),
+                    (
+                        "expression",
+                        "key condition: id = <redacted>; filter: age > <redacted>"
+                    ),
+                    ("placeholders", "4"),
+                    ("expression_bytes", "14"),
+                ]),
+                recorded.get(),
+            );
+
+            let mut sent = Sent::new(span(
+                &query,
+                &Redaction::new().with_paths(["id".parse::<Path>().unwrap()]),
+            ));
+            assert_eq!(
+                fields([
+                    ("operation", r#""Query""#),
+                    ("table", r#""people""#),
+                    (
+                        "expression",
+                        "key condition: id = <redacted>; filter: age > 21"
+                    ),
+                    ("placeholders", "4"),
+                    ("expression_bytes", "14"),
+                ]),
+                recorded.get(),
+            );
+
+            sent.record(Page {
+                pages: 1,
+                items: 2,
+                scanned_items: Some(5),
+                consumed_capacity: None,
+            });
+            sent.record(Page {
+                pages: 1,
+                items: 1,
+                scanned_items: Some(3),
+                consumed_capacity: Some(0.5),
+            });
+
+            let recorded = recorded.get();
+            assert_eq!(
+                [Some("2"), Some("3"), Some("8"), Some("0.5")],
+                ["pages", "items", "scanned_items", "consumed_capacity"]
+                    .map(|name| recorded.get(name).map(String::as_str))
+            );
+        });
+    }
+
+    #[test]
+    fn batch_spans() {
+        let expression = Expression::builder()
+            .with_projection(["id", "name"])
+            .build();
+
+        let recorded = Fields::default();
+        tracing::subscriber::with_default(recorded.clone(), || {
+            let _span = batch_span(
+                "BatchGetItem",
+                ["people", "pets", "people"],
+                Some((&expression, &Redaction::all())),
+            );
+            assert_eq!(
+                fields([
+                    ("operation", r#""BatchGetItem""#),
+                    ("table", "people, pets"),
+                    ("expression", "projection: id, name"),
+                    ("placeholders", "2"),
+                    ("expression_bytes", "6"),
+                ]),
+                recorded.get(),
+            );
+        });
+
+        let recorded = Fields::default();
+        tracing::subscriber::with_default(recorded.clone(), || {
+            let _span = batch_span("BatchWriteItem", ["people"], None);
+            assert_eq!(
+                fields([("operation", r#""BatchWriteItem""#), ("table", "people"),]),
+                recorded.get(),
+            );
+        });
+    }
+}
@@ -23,7 +23,7 @@ pub struct ListAppend {
     pub(crate) list: ValueOrRef,
 
     /// Whether to add the new values to the beginning or end of the source list
-    after: bool,
+    pub(crate) after: bool,
 }
 
 impl ListAppend {
@@ -15,7 +15,8 @@ use crate::{
 pub struct Math {
     pub(crate) dst: Path,
     pub(crate) src: Option<Path>,
-    op: MathOp,
+    pub(crate) default: Option<ValueOrRef>,
+    pub(crate) op: MathOp,
     pub(crate) num: ValueOrRef,
 }
 
@@ -34,6 +35,7 @@ impl Math {
         Builder {
             dst: dst.into(),
             src: None,
+            default: None,
         }
     }
 
@@ -66,8 +68,20 @@ impl fmt::Display for Math {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         self.dst.fmt(f)?;
         f.write_str(" = ")?;
+
+        if self.default.is_some() {
+            f.write_str("if_not_exists(")?;
+        }
+
         // If no source field is specified, default to using the destination field.
         self.src.as_ref().unwrap_or(&self.dst).fmt(f)?;
+
+        if let Some(default) = &self.default {
+            f.write_str(", ")?;
+            default.fmt(f)?;
+            f.write_char(')')?;
+        }
+
         f.write_char(' ')?;
         self.op.fmt(f)?;
         f.write_char(' ')?;
@@ -76,7 +90,7 @@ impl fmt::Display for Math {
 }
 
 #[derive(Clone, Copy, PartialEq, Eq)]
-enum MathOp {
+pub(crate) enum MathOp {
     Add,
     Sub,
 }
@@ -102,6 +116,7 @@ impl fmt::Display for MathOp {
 pub struct Builder {
     dst: Path,
     src: Option<Path>,
+    default: Option<ValueOrRef>,
 }
 
 impl Builder {
@@ -116,6 +131,33 @@ impl Builder {
         self
     }
 
+    /// Sets a value to use in place of the source field if it doesn't exist,
+    /// using [`if_not_exists`][1].
+    ///
+    /// Without this, the update fails if the source field doesn't exist.
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    /// use dynamodb_expression::Path;
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let math = "count".parse::<Path>()?.math().if_not_exists(0).add(1);
+    /// assert_eq!("count = if_not_exists(count, 0) + 1", math.to_string());
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/Expressions.UpdateExpressions.html#Expressions.UpdateExpressions.SET.PreventingAttributeOverwrites
+    pub fn if_not_exists<T>(mut self, default: T) -> Self
+    where
+        T: Into<Num>,
+    {
+        self.default = Some(default.into().into());
+
+        self
+    }
+
     /// Sets addition as the operation to perform.
     #[rustversion::attr(before(1.81), allow(clippy::should_implement_trait))]
     #[rustversion::attr(
@@ -152,11 +194,12 @@ impl Builder {
     where
         T: Into<Num>,
     {
-        let Self { dst, src } = self;
+        let Self { dst, src, default } = self;
 
         Math {
             dst,
             src,
+            default,
             op,
             num: num.into().into(),
         }
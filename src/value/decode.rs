@@ -0,0 +1,328 @@
+use core::fmt;
+use std::{collections::HashMap, error::Error};
+
+use aws_sdk_dynamodb::types::AttributeValue;
+
+use crate::{
+    condition::attribute_type::Type,
+    path::{Element, Name, Path, ProjectionParseError},
+};
+
+use super::{List, Map, Value, ValuePathSegment};
+
+/// Converts an item, as returned by DynamoDB, into a [`Map`], noting where any
+/// attribute of a type this crate doesn't know about is.
+pub(crate) fn item_to_map(item: HashMap<String, AttributeValue>) -> Result<Map, DecodeError> {
+    item.into_iter()
+        .map(|(name, value)| {
+            attribute_to_value(value)
+                .map(|value| (Name::from(name.clone()), value))
+                .map_err(|err| err.within(ValuePathSegment::Key(name)))
+        })
+        .collect::<Result<_, _>>()
+        .map(|map| Map { map })
+}
+
+fn attribute_to_value(value: AttributeValue) -> Result<Value, DecodeError> {
+    match value {
+        AttributeValue::M(map) => item_to_map(map).map(Value::Map),
+        AttributeValue::L(list) => list
+            .into_iter()
+            .enumerate()
+            .map(|(i, value)| {
+                attribute_to_value(value).map_err(|err| err.within(ValuePathSegment::Index(i)))
+            })
+            .collect::<Result<_, _>>()
+            .map(|list| Value::List(List { list })),
+        value => Value::try_from(value).map_err(|_| DecodeErrorKind::UnknownType.into()),
+    }
+}
+
+#[cfg(feature = "items")]
+/// Like [`item_to_map`], but leaves out attributes of types this crate doesn't
+/// know about, and returns their paths.
+pub(crate) fn item_to_map_lossy(item: HashMap<String, AttributeValue>) -> (Map, Vec<Path>) {
+    let mut unknown = Vec::new();
+    let map = map_lossy(item, &mut Vec::new(), &mut unknown);
+
+    (map, unknown)
+}
+
+#[cfg(feature = "items")]
+fn map_lossy(
+    map: HashMap<String, AttributeValue>,
+    at: &mut Vec<ValuePathSegment>,
+    unknown: &mut Vec<Path>,
+) -> Map {
+    let map = map
+        .into_iter()
+        .filter_map(|(name, value)| {
+            at.push(ValuePathSegment::Key(name.clone()));
+            let value = attribute_to_value_lossy(value, at, unknown);
+            at.pop();
+
+            value.map(|value| (Name::from(name), value))
+        })
+        .collect();
+
+    Map { map }
+}
+
+#[cfg(feature = "items")]
+fn attribute_to_value_lossy(
+    value: AttributeValue,
+    at: &mut Vec<ValuePathSegment>,
+    unknown: &mut Vec<Path>,
+) -> Option<Value> {
+    match value {
+        AttributeValue::M(map) => Some(Value::Map(map_lossy(map, at, unknown))),
+        AttributeValue::L(list) => {
+            let list = list
+                .into_iter()
+                .enumerate()
+                .filter_map(|(i, value)| {
+                    at.push(ValuePathSegment::Index(i));
+                    let value = attribute_to_value_lossy(value, at, unknown);
+                    at.pop();
+
+                    value
+                })
+                .collect();
+
+            Some(Value::List(List { list }))
+        }
+        value => match Value::try_from(value) {
+            Ok(value) => Some(value),
+            Err(_) => {
+                unknown.push(DecodeError::at(at.clone(), DecodeErrorKind::UnknownType).path());
+                None
+            }
+        },
+    }
+}
+
+/// The [`Type`] of an attribute value, or `None` for one this crate doesn't
+/// know about.
+pub(crate) fn attribute_type(value: &AttributeValue) -> Option<Type> {
+    Some(match value {
+        AttributeValue::S(_) => Type::String,
+        AttributeValue::Ss(_) => Type::StringSet,
+        AttributeValue::N(_) => Type::Number,
+        AttributeValue::Ns(_) => Type::NumberSet,
+        AttributeValue::B(_) => Type::Binary,
+        AttributeValue::Bs(_) => Type::BinarySet,
+        AttributeValue::Bool(_) => Type::Boolean,
+        AttributeValue::Null(_) => Type::Null,
+        AttributeValue::L(_) => Type::List,
+        AttributeValue::M(_) => Type::Map,
+        _ => return None,
+    })
+}
+
+/// An error decoding an item returned by DynamoDB.
+///
+/// See [`Expression::decode_item`] and [`Projection::check`].
+///
+/// [`Expression::decode_item`]: crate::Expression::decode_item
+/// [`Projection::check`]: crate::path::Projection::check
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodeError {
+    path: Vec<ValuePathSegment>,
+    kind: DecodeErrorKind,
+}
+
+impl DecodeError {
+    /// Where in the item the problem is. Empty if it's the item itself.
+    pub fn path(&self) -> Path {
+        let mut elements: Vec<Element> = Vec::with_capacity(self.path.len());
+        for segment in &self.path {
+            match segment {
+                ValuePathSegment::Key(name) => elements.push(Element::new_name(name.as_str())),
+                ValuePathSegment::Index(i) => match elements.pop() {
+                    Some(Element::Name(name)) => {
+                        elements.push(Element::new_indexed_field(name, *i))
+                    }
+                    Some(Element::IndexedField(mut field)) => {
+                        field.indexes.push(*i);
+                        elements.push(field.into());
+                    }
+                    // An item is always a map, so its path never starts with an index.
+                    None => {}
+                },
+            }
+        }
+
+        Path { elements }
+    }
+
+    /// What the problem is.
+    pub fn kind(&self) -> &DecodeErrorKind {
+        &self.kind
+    }
+
+    /// Marks this error as being within the given map key or list index.
+    pub(crate) fn within(mut self, segment: ValuePathSegment) -> Self {
+        self.path.insert(0, segment);
+        self
+    }
+
+    pub(crate) fn at<I>(path: I, kind: DecodeErrorKind) -> Self
+    where
+        I: IntoIterator<Item = ValuePathSegment>,
+    {
+        Self {
+            path: path.into_iter().collect(),
+            kind,
+        }
+    }
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.path.is_empty() {
+            write!(f, "{}: ", self.path())?;
+        }
+
+        self.kind.fmt(f)
+    }
+}
+
+impl Error for DecodeError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match &self.kind {
+            DecodeErrorKind::Projection(err) => Some(err),
+            #[cfg(feature = "serde")]
+            DecodeErrorKind::Deserialize(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<DecodeErrorKind> for DecodeError {
+    fn from(kind: DecodeErrorKind) -> Self {
+        Self::at([], kind)
+    }
+}
+
+impl From<ProjectionParseError> for DecodeError {
+    fn from(err: ProjectionParseError) -> Self {
+        DecodeErrorKind::Projection(err).into()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<super::serde::SerdeError> for DecodeError {
+    fn from(err: super::serde::SerdeError) -> Self {
+        Self::at(err.path().to_vec(), DecodeErrorKind::Deserialize(err))
+    }
+}
+
+/// The kind of problem found decoding an item. See [`DecodeError`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DecodeErrorKind {
+    /// A projected attribute isn't in the item.
+    Missing,
+
+    /// An attribute isn't the type needed to reach a projected attribute
+    /// within it. E.g., `a` is a string, but `a.b` is projected.
+    WrongType { expected: Type, found: Type },
+
+    /// An attribute is of a type this crate doesn't know about.
+    UnknownType,
+
+    /// The expression's projection couldn't be parsed.
+    Projection(ProjectionParseError),
+
+    /// The item couldn't be deserialized into the requested type.
+    ///
+    /// Requires the `serde` feature.
+    #[cfg(feature = "serde")]
+    Deserialize(super::serde::SerdeError),
+}
+
+impl fmt::Display for DecodeErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Missing => f.write_str("projected attribute is missing"),
+            Self::WrongType { expected, found } => {
+                write!(f, "expected an attribute of type {expected}, found {found}")
+            }
+            Self::UnknownType => f.write_str("unknown attribute type"),
+            Self::Projection(err) => err.fmt(f),
+            // The path is already included in the `DecodeError`.
+            #[cfg(feature = "serde")]
+            Self::Deserialize(err) => f.write_str(err.message()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use crate::{value::ValuePathSegment, Path};
+
+    use super::{DecodeError, DecodeErrorKind};
+
+    #[test]
+    fn path() {
+        let err = DecodeError::at(
+            [
+                ValuePathSegment::Key("a".into()),
+                ValuePathSegment::Index(2),
+                ValuePathSegment::Index(0),
+                ValuePathSegment::Key("b.c".into()),
+                ValuePathSegment::Key("d".into()),
+                ValuePathSegment::Index(1),
+            ],
+            DecodeErrorKind::Missing,
+        );
+
+        assert_eq!(
+            Path::from_iter([
+                Path::new_indexed_field("a", [2, 0]),
+                Path::new_name("b.c"),
+                Path::new_indexed_field("d", 1),
+            ]),
+            err.path()
+        );
+        assert_eq!(
+            "a[2][0].b.c.d[1]: projected attribute is missing",
+            err.to_string()
+        );
+
+        let err = DecodeError::from(DecodeErrorKind::UnknownType);
+        assert!(err.path().is_empty());
+        assert_eq!("unknown attribute type", err.to_string());
+    }
+
+    #[cfg(feature = "items")]
+    #[test]
+    fn lossy() {
+        use std::collections::HashMap;
+
+        use aws_sdk_dynamodb::types::AttributeValue;
+
+        use crate::value::{item_to_map, item_to_map_lossy};
+
+        // `AttributeValue::Unknown` can't be constructed outside the SDK, so
+        // this only checks that nothing else is left out.
+        let item = HashMap::from([
+            ("id".to_string(), AttributeValue::S("1".into())),
+            (
+                "list".to_string(),
+                AttributeValue::L(vec![
+                    AttributeValue::Null(true),
+                    AttributeValue::M(HashMap::from([
+                        ("a".to_string(), AttributeValue::Bool(true)),
+                        ("b".to_string(), AttributeValue::Ns(vec!["1".into()])),
+                    ])),
+                ]),
+            ),
+        ]);
+
+        let (map, unknown) = item_to_map_lossy(item.clone());
+        assert_eq!(item_to_map(item).unwrap(), map);
+        assert_eq!(Vec::<Path>::new(), unknown);
+    }
+}
@@ -0,0 +1,507 @@
+use core::fmt;
+use std::{collections::HashMap, error::Error};
+
+use base64::{engine::general_purpose, Engine as _};
+use serde_json::{json, value::RawValue, Map as JsonMap, Number};
+
+use super::{base64, BinarySet, List, Map, Num, NumSet, Scalar, Set, StringSet, Value};
+
+/// Converts plain JSON into a [`Value`].
+///
+/// * `null` → null
+/// * Booleans → boolean
+/// * Numbers → number, using the text `serde_json` parsed. Numbers that don't
+///   fit in an `i64` or `u64` have already lost precision as an `f64`, unless
+///   the `arbitrary_precision` feature of `serde_json` is enabled. Use
+///   [`Value::from_json_str`] to keep every number exactly as written.
+/// * Strings → string
+/// * Arrays → list
+/// * Objects → map
+///
+/// This conversion can't fail, so `Value::try_from(json)` works as well.
+///
+/// For JSON in the DynamoDB format (`{"S": "..."}`), see
+/// [`Value::from_dynamodb_json`].
+///
+/// ```
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use dynamodb_expression::{Num, Path, Value};
+/// # use pretty_assertions::assert_eq;
+///
+/// let json: serde_json::Value = serde_json::from_str(r#"{"name": "Widget", "sizes": [1, 2.5]}"#)?;
+/// let value = Value::from(json);
+/// assert_eq!(
+///     Value::new_map([
+///         ("name", Value::from("Widget")),
+///         ("sizes", Value::new_list([Num::new(1), Num::new(2.5)])),
+///     ]),
+///     value,
+/// );
+///
+/// let condition = Path::new_name("details").equal(value);
+/// # _ = condition;
+/// #
+/// # Ok(())
+/// # }
+/// ```
+impl From<serde_json::Value> for Value {
+    fn from(json: serde_json::Value) -> Self {
+        match json {
+            serde_json::Value::Null => Self::new_null(),
+            serde_json::Value::Bool(b) => b.into(),
+            serde_json::Value::Number(n) => Num { n: n.to_string() }.into(),
+            serde_json::Value::String(s) => s.into(),
+            serde_json::Value::Array(array) => {
+                List::from_iter(array.into_iter().map(Self::from)).into()
+            }
+            serde_json::Value::Object(object) => {
+                Map::from_iter(object.into_iter().map(|(k, v)| (k, Self::from(v)))).into()
+            }
+        }
+    }
+}
+
+/// Converts a [`Value`] into plain JSON.
+///
+/// Sets become arrays, and binary data becomes a base64 string. Numbers that
+/// can't be represented by `serde_json` become strings.
+///
+/// For JSON in the DynamoDB format (`{"S": "..."}`), see
+/// [`Value::into_dynamodb_json`].
+impl From<Value> for serde_json::Value {
+    fn from(value: Value) -> Self {
+        match value {
+            Value::Scalar(scalar) => scalar.into(),
+            Value::Set(Set::StringSet(set)) => set.0.into_iter().collect(),
+            Value::Set(Set::NumSet(set)) => set.0.into_iter().map(Self::from).collect(),
+            Value::Set(Set::BinarySet(set)) => set.0.into_iter().map(base64).collect(),
+            Value::Map(map) => Self::Object(
+                map.map
+                    .into_iter()
+                    .map(|(k, v)| (k.name, v.into()))
+                    .collect(),
+            ),
+            Value::List(list) => list.list.into_iter().map(Self::from).collect(),
+        }
+    }
+}
+
+impl From<Scalar> for serde_json::Value {
+    fn from(scalar: Scalar) -> Self {
+        match scalar {
+            Scalar::String(s) => Self::String(s),
+            Scalar::Num(n) => n.into(),
+            Scalar::Bool(b) => Self::Bool(b),
+            Scalar::Binary(b) => Self::String(base64(b)),
+            Scalar::Null => Self::Null,
+        }
+    }
+}
+
+impl From<Num> for serde_json::Value {
+    fn from(num: Num) -> Self {
+        match num.n.parse::<Number>() {
+            Ok(n) => Self::Number(n),
+            Err(_) => Self::String(num.n),
+        }
+    }
+}
+
+impl Value {
+    /// Parses plain JSON text into a [`Value`], converting it the same way as
+    /// `Value::from(json)`, except that numbers are kept exactly as written.
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use dynamodb_expression::{Num, Value};
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let value = Value::from_json_str(r#"{"id": 12345678901234567890123456789}"#)?;
+    /// assert_eq!(
+    ///     Value::new_map([("id", "12345678901234567890123456789".parse::<Num>()?)]),
+    ///     value,
+    /// );
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_json_str(json: &str) -> Result<Self, serde_json::Error> {
+        Self::from_raw_json(serde_json::from_str(json)?)
+    }
+
+    fn from_raw_json(raw: &RawValue) -> Result<Self, serde_json::Error> {
+        let json = raw.get();
+
+        Ok(match json.trim_start().as_bytes().first() {
+            Some(b'{') => Map::from_iter(
+                serde_json::from_str::<HashMap<String, &RawValue>>(json)?
+                    .into_iter()
+                    .map(|(k, v)| Ok((k, Self::from_raw_json(v)?)))
+                    .collect::<Result<Vec<_>, serde_json::Error>>()?,
+            )
+            .into(),
+            Some(b'[') => List::from_iter(
+                serde_json::from_str::<Vec<&RawValue>>(json)?
+                    .into_iter()
+                    .map(Self::from_raw_json)
+                    .collect::<Result<Vec<_>, _>>()?,
+            )
+            .into(),
+            Some(b'-' | b'0'..=b'9') => Num {
+                n: json.trim().to_string(),
+            }
+            .into(),
+            _ => serde_json::from_str::<serde_json::Value>(json)?.into(),
+        })
+    }
+
+    /// Reads a [`Value`] from JSON in the [DynamoDB JSON format][1], such as
+    /// `{"S": "hello"}` or `{"M": {"n": {"N": "42"}}}`.
+    ///
+    /// For plain JSON, use `Value::from(json)`.
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use dynamodb_expression::{Num, Value};
+    /// use serde_json::json;
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let value = Value::from_dynamodb_json(json!({
+    ///     "M": {
+    ///         "name": { "S": "Widget" },
+    ///         "count": { "N": "42" },
+    ///         "tags": { "SS": ["a", "b"] },
+    ///     }
+    /// }))?;
+    /// assert_eq!(
+    ///     Value::new_map([
+    ///         ("name", Value::from("Widget")),
+    ///         ("count", Num::new(42).into()),
+    ///         ("tags", Value::new_string_set(["a", "b"])),
+    ///     ]),
+    ///     value,
+    /// );
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/Programming.LowLevelAPI.html#Programming.LowLevelAPI.DataTypeDescriptors
+    pub fn from_dynamodb_json(json: serde_json::Value) -> Result<Self, DynamoDbJsonError> {
+        let serde_json::Value::Object(object) = json else {
+            return Err(DynamoDbJsonError::new("expected an object", &json));
+        };
+
+        if object.len() != 1 {
+            return Err(DynamoDbJsonError::new(
+                "expected an object with a single type descriptor",
+                &serde_json::Value::Object(object),
+            ));
+        }
+
+        let (type_descriptor, json) = object.into_iter().next().expect("Length was checked");
+
+        Ok(match type_descriptor.as_str() {
+            "S" => as_string(json)?.into(),
+            "N" => as_num(json)?.into(),
+            "B" => as_binary(json)?.into(),
+            "BOOL" => match json {
+                serde_json::Value::Bool(b) => b.into(),
+                json => return Err(DynamoDbJsonError::new("expected a boolean", &json)),
+            },
+            "NULL" => match json {
+                serde_json::Value::Bool(true) => Self::new_null(),
+                json => return Err(DynamoDbJsonError::new("expected `true` for NULL", &json)),
+            },
+            "SS" => StringSet::from_iter(
+                as_array(json)?
+                    .into_iter()
+                    .map(as_string)
+                    .collect::<Result<Vec<_>, _>>()?,
+            )
+            .into(),
+            "NS" => NumSet::from_iter(
+                as_array(json)?
+                    .into_iter()
+                    .map(as_num)
+                    .collect::<Result<Vec<_>, _>>()?,
+            )
+            .into(),
+            "BS" => BinarySet::from_iter(
+                as_array(json)?
+                    .into_iter()
+                    .map(as_binary)
+                    .collect::<Result<Vec<_>, _>>()?,
+            )
+            .into(),
+            "L" => List::from_iter(
+                as_array(json)?
+                    .into_iter()
+                    .map(Self::from_dynamodb_json)
+                    .collect::<Result<Vec<_>, _>>()?,
+            )
+            .into(),
+            "M" => match json {
+                serde_json::Value::Object(object) => Map::from_iter(
+                    object
+                        .into_iter()
+                        .map(|(k, v)| Self::from_dynamodb_json(v).map(|v| (k, v)))
+                        .collect::<Result<Vec<_>, _>>()?,
+                )
+                .into(),
+                json => return Err(DynamoDbJsonError::new("expected an object", &json)),
+            },
+            _ => {
+                return Err(DynamoDbJsonError::new(
+                    "unknown type descriptor",
+                    &serde_json::Value::String(type_descriptor),
+                ))
+            }
+        })
+    }
+
+    /// Converts this [`Value`] into JSON in the [DynamoDB JSON format][1].
+    ///
+    /// For plain JSON, use `serde_json::Value::from(value)`.
+    ///
+    /// ```
+    /// use dynamodb_expression::{Num, Value};
+    /// use serde_json::json;
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let value = Value::new_list([Value::from("a"), Num::new(1).into()]);
+    /// assert_eq!(
+    ///     json!({ "L": [{ "S": "a" }, { "N": "1" }] }),
+    ///     value.into_dynamodb_json(),
+    /// );
+    /// ```
+    ///
+    /// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/Programming.LowLevelAPI.html#Programming.LowLevelAPI.DataTypeDescriptors
+    pub fn into_dynamodb_json(self) -> serde_json::Value {
+        match self {
+            Self::Scalar(Scalar::String(s)) => json!({ "S": s }),
+            Self::Scalar(Scalar::Num(n)) => json!({ "N": n.n }),
+            Self::Scalar(Scalar::Bool(b)) => json!({ "BOOL": b }),
+            Self::Scalar(Scalar::Binary(b)) => json!({ "B": base64(b) }),
+            Self::Scalar(Scalar::Null) => json!({ "NULL": true }),
+            Self::Set(Set::StringSet(set)) => json!({ "SS": set.0 }),
+            Self::Set(Set::NumSet(set)) => {
+                json!({ "NS": set.0.into_iter().map(|n| n.n).collect::<Vec<_>>() })
+            }
+            Self::Set(Set::BinarySet(set)) => {
+                json!({ "BS": set.0.into_iter().map(base64).collect::<Vec<_>>() })
+            }
+            Self::Map(map) => json!({
+                "M": map
+                    .map
+                    .into_iter()
+                    .map(|(k, v)| (k.name, v.into_dynamodb_json()))
+                    .collect::<JsonMap<_, _>>()
+            }),
+            Self::List(list) => json!({
+                "L": list
+                    .list
+                    .into_iter()
+                    .map(Self::into_dynamodb_json)
+                    .collect::<Vec<_>>()
+            }),
+        }
+    }
+}
+
+fn as_string(json: serde_json::Value) -> Result<String, DynamoDbJsonError> {
+    match json {
+        serde_json::Value::String(s) => Ok(s),
+        json => Err(DynamoDbJsonError::new("expected a string", &json)),
+    }
+}
+
+fn as_num(json: serde_json::Value) -> Result<Num, DynamoDbJsonError> {
+    let s = as_string(json)?;
+
+    // Make sure it's a number, but keep the text as-is to avoid losing precision.
+    if s.parse::<f64>().is_ok_and(f64::is_finite) {
+        Ok(Num { n: s })
+    } else {
+        Err(DynamoDbJsonError::new(
+            "expected a number",
+            &serde_json::Value::String(s),
+        ))
+    }
+}
+
+fn as_binary(json: serde_json::Value) -> Result<Vec<u8>, DynamoDbJsonError> {
+    let s = as_string(json)?;
+
+    general_purpose::STANDARD
+        .decode(&s)
+        .map_err(|_| DynamoDbJsonError::new("expected base64", &serde_json::Value::String(s)))
+}
+
+fn as_array(json: serde_json::Value) -> Result<Vec<serde_json::Value>, DynamoDbJsonError> {
+    match json {
+        serde_json::Value::Array(array) => Ok(array),
+        json => Err(DynamoDbJsonError::new("expected an array", &json)),
+    }
+}
+
+/// The JSON passed to [`Value::from_dynamodb_json`] was not valid
+/// [DynamoDB JSON][1].
+///
+/// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/Programming.LowLevelAPI.html#Programming.LowLevelAPI.DataTypeDescriptors
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DynamoDbJsonError {
+    message: String,
+}
+
+impl DynamoDbJsonError {
+    fn new(problem: &str, found: &serde_json::Value) -> Self {
+        Self {
+            message: format!("invalid DynamoDB JSON, {problem}: {found}"),
+        }
+    }
+}
+
+impl fmt::Display for DynamoDbJsonError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl Error for DynamoDbJsonError {}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use crate::value::{Num, Value};
+
+    fn value() -> Value {
+        Value::new_map([
+            ("s", Value::from("text")),
+            ("n", Num::new(-1.5).into()),
+            ("b", Value::new_binary([1, 2, 3])),
+            ("bool", true.into()),
+            ("null", Value::new_null()),
+            ("ss", Value::new_string_set(["a", "b"])),
+            ("ns", Value::new_num_set([1, 2])),
+            ("bs", Value::new_binary_set([[1], [2]])),
+            ("l", Value::new_list([Value::from("x"), Num::new(7).into()])),
+        ])
+    }
+
+    #[test]
+    fn dynamodb_json() {
+        let json = json!({
+            "M": {
+                "s": { "S": "text" },
+                "n": { "N": "-1.5" },
+                "b": { "B": "AQID" },
+                "bool": { "BOOL": true },
+                "null": { "NULL": true },
+                "ss": { "SS": ["a", "b"] },
+                "ns": { "NS": ["1", "2"] },
+                "bs": { "BS": ["AQ==", "Ag=="] },
+                "l": { "L": [{ "S": "x" }, { "N": "7" }] },
+            }
+        });
+
+        assert_eq!(json, value().into_dynamodb_json());
+        assert_eq!(value(), Value::from_dynamodb_json(json).unwrap());
+    }
+
+    #[test]
+    fn dynamodb_json_precision() {
+        let n = "123456789012345678901234567890.123456789";
+        assert_eq!(
+            Value::from(Num { n: n.into() }),
+            Value::from_dynamodb_json(json!({ "N": n })).unwrap()
+        );
+    }
+
+    #[test]
+    fn invalid_dynamodb_json() {
+        for json in [
+            json!("text"),
+            json!({}),
+            json!({ "S": "a", "N": "1" }),
+            json!({ "X": "a" }),
+            json!({ "S": 1 }),
+            json!({ "N": "one" }),
+            json!({ "B": "not base64!" }),
+            json!({ "NULL": false }),
+            json!({ "SS": "a" }),
+            json!({ "L": [{ "S": "a" }, "b"] }),
+        ] {
+            Value::from_dynamodb_json(json.clone())
+                .expect_err(&format!("Should not have parsed: {json}"));
+        }
+    }
+
+    #[test]
+    fn plain_json() {
+        let json = json!({
+            "s": "text",
+            "n": -1.5,
+            "b": "AQID",
+            "bool": true,
+            "null": null,
+            "ss": ["a", "b"],
+            "ns": [1, 2],
+            "bs": ["AQ==", "Ag=="],
+            "l": ["x", 7],
+        });
+
+        assert_eq!(json, serde_json::Value::from(value()));
+
+        assert_eq!(
+            Value::new_map([
+                ("s", Value::from("text")),
+                ("n", Num::new(-1.5).into()),
+                ("bool", true.into()),
+                ("null", Value::new_null()),
+                ("l", Value::new_list([Value::from("x"), Num::new(7).into()])),
+            ]),
+            Value::from(json!({
+                "s": "text",
+                "n": -1.5,
+                "bool": true,
+                "null": null,
+                "l": ["x", 7],
+            }))
+        );
+    }
+
+    #[test]
+    fn plain_json_precision() {
+        let json =
+            r#"{ "n": 12345678901234567890123456789, "l": [-1.25e-3, "x", null, true, {}] }"#;
+
+        assert_eq!(
+            Value::new_map([
+                (
+                    "n",
+                    Value::from(Num {
+                        n: "12345678901234567890123456789".into()
+                    })
+                ),
+                (
+                    "l",
+                    Value::new_list([
+                        Value::from(Num {
+                            n: "-1.25e-3".into()
+                        }),
+                        "x".into(),
+                        Value::new_null(),
+                        true.into(),
+                        Value::new_map(Vec::<(String, Value)>::new()),
+                    ])
+                ),
+            ]),
+            Value::from_json_str(json).unwrap()
+        );
+
+        Value::from_json_str("[1, ").expect_err("Should not have parsed");
+    }
+}
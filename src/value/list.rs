@@ -10,7 +10,7 @@ use super::Value;
 /// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/HowItWorks.NamingRulesDataTypes.html#HowItWorks.DataTypes.Document.List
 #[derive(Clone, PartialEq, Eq, Hash)]
 pub struct List {
-    list: Vec<Value>,
+    pub(crate) list: Vec<Value>,
 }
 
 impl List {
@@ -6,9 +6,14 @@ use core::{
 use aws_sdk_dynamodb::types::AttributeValue;
 use itermap::IterMap;
 
+#[cfg(feature = "items")]
+use std::collections::HashMap;
+
 use crate::path::Name;
 
-use super::Value;
+#[cfg(feature = "items")]
+use super::{item_to_map, item_to_map_lossy, DecodeError};
+use super::{Value, ValueError, ValueErrorKind, ValuePathSegment};
 
 type MapType<K, V> = std::collections::BTreeMap<K, V>;
 // TODO: Allow this to be configured via feature to switch between HashMap and BTreeMap
@@ -20,7 +25,7 @@ type MapType<K, V> = std::collections::BTreeMap<K, V>;
 /// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/HowItWorks.NamingRulesDataTypes.html#HowItWorks.DataTypes.Document.Map
 #[derive(Clone, Default, PartialEq, Eq)]
 pub struct Map {
-    map: MapType<Name, Value>,
+    pub(crate) map: MapType<Name, Value>,
 }
 
 impl Map {
@@ -53,6 +58,127 @@ impl Map {
         map.into()
     }
 
+    /// Like [`Map::new`], but fails if the map would be one DynamoDB won't
+    /// accept: a key appears more than once, or a value isn't valid (see
+    /// [`Value::validate`]).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dynamodb_expression::value::{Map, Num, Value, ValuePathSegment};
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// assert_eq!(
+    ///     Ok(Map::new([("a", Num::new(1)), ("b", Num::new(2))])),
+    ///     Map::try_new([("a", Num::new(1)), ("b", Num::new(2))]),
+    /// );
+    ///
+    /// let err = Map::try_new([
+    ///     ("name", Value::new_string("Widget")),
+    ///     ("price", Value::new_num(f64::INFINITY)),
+    /// ])
+    /// .unwrap_err();
+    /// assert_eq!(&[ValuePathSegment::Key("price".into())], err.path());
+    /// assert_eq!("price: not a number: \"inf\"", err.to_string());
+    /// ```
+    pub fn try_new<I, K, V>(map: I) -> Result<Self, ValueError>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<Name>,
+        V: Into<Value>,
+    {
+        let mut new = MapType::new();
+        for (name, value) in map {
+            let name = name.into();
+            let value = value.into();
+
+            value
+                .validate()
+                .map_err(|err| err.within(ValuePathSegment::Key(name.name.clone())))?;
+
+            if new.contains_key(&name) {
+                return Err(ValueErrorKind::DuplicateMapKey(name.name).into());
+            }
+
+            new.insert(name, value);
+        }
+
+        Ok(Self { map: new })
+    }
+
+    /// Converts an item, as returned by DynamoDB, into a [`Map`].
+    ///
+    /// This only fails for an attribute of a type this crate doesn't know
+    /// about (see [`AttributeValue::Unknown`]), with the [`Path`] to that
+    /// attribute. To keep the rest of the item instead, use
+    /// [`Map::from_item_lossy`]. The other way, a [`Map`] can be converted into
+    /// an item with [`From`]/[`Into`].
+    ///
+    /// Requires the `items` feature.
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use std::collections::HashMap;
+    ///
+    /// use aws_sdk_dynamodb::types::AttributeValue;
+    /// use dynamodb_expression::value::{Map, Value};
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let item = HashMap::from([
+    ///     ("id".to_string(), AttributeValue::S("1".into())),
+    ///     ("count".to_string(), AttributeValue::N("2".into())),
+    /// ]);
+    ///
+    /// let map = Map::try_from_item(item.clone())?;
+    /// assert_eq!(r#"{count: 2, id: "1"}"#, map.to_string());
+    /// assert_eq!(
+    ///     Map::new([("id", Value::from("1")), ("count", Value::new_num(2))]),
+    ///     map
+    /// );
+    ///
+    /// assert_eq!(item, HashMap::from(map));
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`Path`]: crate::Path
+    #[cfg(feature = "items")]
+    pub fn try_from_item(item: HashMap<String, AttributeValue>) -> Result<Self, DecodeError> {
+        item_to_map(item)
+    }
+
+    /// Converts an item, as returned by DynamoDB, into a [`Map`], leaving out
+    /// any attribute of a type this crate doesn't know about (see
+    /// [`AttributeValue::Unknown`]). Returns the [`Path`]s to the attributes
+    /// that were left out, as they were in the item, in no particular order.
+    ///
+    /// Leaving out an element of a list shifts the elements after it down, so
+    /// the [`Map`] isn't suitable for writing back to DynamoDB if any were left
+    /// out.
+    ///
+    /// Requires the `items` feature.
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    ///
+    /// use aws_sdk_dynamodb::types::AttributeValue;
+    /// use dynamodb_expression::value::Map;
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let item = HashMap::from([("id".to_string(), AttributeValue::S("1".into()))]);
+    ///
+    /// let (map, unknown) = Map::from_item_lossy(item);
+    /// assert_eq!(r#"{id: "1"}"#, map.to_string());
+    /// assert!(unknown.is_empty());
+    /// ```
+    ///
+    /// [`Path`]: crate::Path
+    #[cfg(feature = "items")]
+    pub fn from_item_lossy(item: HashMap<String, AttributeValue>) -> (Self, Vec<crate::Path>) {
+        item_to_map_lossy(item)
+    }
+
     // Intentionally not using `impl From<ScalarValue> for AttributeValue` because
     // I don't want to make this a public API people rely on. The purpose of this
     // crate is not to make creating `AttributeValues` easier. They should try
@@ -136,3 +262,17 @@ where
         Self::from_iter(iter)
     }
 }
+
+/// Converts a [`Map`] into an item to send to DynamoDB.
+///
+/// Requires the `items` feature.
+#[cfg(feature = "items")]
+impl From<Map> for HashMap<String, AttributeValue> {
+    fn from(map: Map) -> Self {
+        map.map
+            .into_iter()
+            .map_keys(|name| name.name)
+            .map_values(Value::into_attribute_value)
+            .collect()
+    }
+}
@@ -3,20 +3,34 @@
 //! [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/Expressions.UpdateExpressions.html
 //! [`Update`]: crate::update::Update
 
+mod decode;
+mod json;
 mod list;
 mod map;
 mod num;
 mod scalar;
 mod set;
+mod size;
+mod validate;
 mod value_or_ref;
 
+#[cfg(feature = "serde")]
+pub mod serde;
+
+pub use decode::{DecodeError, DecodeErrorKind};
+pub use json::DynamoDbJsonError;
 pub use list::List;
 pub use map::Map;
-pub use num::Num;
+pub use num::{Num, NumError};
 pub use scalar::Scalar;
 pub use set::{BinarySet, NumSet, Set, StringSet};
+pub use size::{item_size_bytes, MAX_ITEM_SIZE_BYTES};
+pub use validate::{ValueError, ValueErrorKind, ValuePathSegment};
 pub use value_or_ref::{Ref, StringOrRef};
 
+#[cfg(feature = "items")]
+pub(crate) use decode::item_to_map_lossy;
+pub(crate) use decode::{attribute_type, item_to_map};
 pub(crate) use value_or_ref::ValueOrRef;
 
 use core::fmt::{self, LowerExp, UpperExp};
@@ -61,6 +75,33 @@ impl Value {
         Num::new(value).into()
     }
 
+    /// Like [`Value::new_num`], but fails if the number is one DynamoDB won't
+    /// accept, such as `NaN`, infinity, or a number out of range.
+    ///
+    /// See also: [`Num::validate`]
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dynamodb_expression::{value::NumError, Value};
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// assert_eq!(Ok(Value::new_num(2.5)), Value::try_new_num(2.5));
+    /// assert_eq!(
+    ///     Err(NumError::Invalid("NaN".into()).into()),
+    ///     Value::try_new_num(f64::NAN),
+    /// );
+    /// ```
+    pub fn try_new_num<N>(value: N) -> Result<Self, ValueError>
+    where
+        N: ToString + ::num::Num,
+    {
+        let num = Num::new(value);
+        num.validate()?;
+
+        Ok(num.into())
+    }
+
     /// Use when you need a [numeric value][1] for DynamoDB in exponent form
     /// (with a lowercase `e`).
     ///
@@ -182,7 +223,8 @@ impl Value {
     // Intentionally not using `impl From<ScalarValue> for AttributeValue` because
     // I don't want to make this a public API people rely on. The purpose of this
     // crate is not to make creating `AttributeValues` easier. They should try
-    // `serde_dynamo`.
+    // `serde_dynamo`. The exception is the opt-in `items` feature, for working
+    // with whole items.
     pub(crate) fn into_attribute_value(self) -> AttributeValue {
         match self {
             Self::Scalar(value) => value.into_attribute_value(),
@@ -334,6 +376,14 @@ impl TryFrom<AttributeValue> for Value {
     }
 }
 
+/// Requires the `items` feature.
+#[cfg(feature = "items")]
+impl From<Value> for AttributeValue {
+    fn from(value: Value) -> Self {
+        value.into_attribute_value()
+    }
+}
+
 impl fmt::Display for Value {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -397,7 +447,7 @@ mod test {
             Value::new_string_set(["a", "c", "b"]).to_string()
         );
         assert_eq!(
-            r#"[-7, 1e3, 42]"#,
+            r#"[-7, 42, 1e3]"#,
             Value::new_num_set([Num::new_lower_exp(1000), Num::new(42), Num::new(-7)]).to_string()
         );
         assert_eq!(
@@ -488,4 +538,46 @@ mod test {
             .expect("Could not convert AttributeValue to Value"),
         );
     }
+
+    #[cfg(feature = "items")]
+    #[test]
+    fn item_round_trip() {
+        use std::collections::HashMap;
+
+        let item = HashMap::from([
+            ("id".to_string(), AttributeValue::S("1".to_string())),
+            (
+                "nested".to_string(),
+                AttributeValue::M(HashMap::from([
+                    (
+                        "list".to_string(),
+                        AttributeValue::L(vec![
+                            AttributeValue::N("42".to_string()),
+                            AttributeValue::M(HashMap::from([(
+                                "yes".to_string(),
+                                AttributeValue::Bool(true),
+                            )])),
+                        ]),
+                    ),
+                    (
+                        "ns".to_string(),
+                        AttributeValue::Ns(vec!["1".to_string(), "2".to_string()]),
+                    ),
+                    ("null".to_string(), AttributeValue::Null(true)),
+                ])),
+            ),
+        ]);
+
+        let map = Map::try_from_item(item.clone()).expect("All types are known");
+        assert_eq!(
+            r#"{id: "1", nested: {list: [42, {yes: true}], ns: [1, 2], null: NULL}}"#,
+            map.to_string()
+        );
+        assert_eq!(item, HashMap::from(map));
+
+        assert_eq!(
+            AttributeValue::N("7".to_string()),
+            AttributeValue::from(Value::new_num(7))
+        );
+    }
 }
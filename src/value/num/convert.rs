@@ -0,0 +1,214 @@
+//! Checked conversions from a [`Num`] to Rust number types.
+
+use super::{Num, NumError};
+
+/// No integer type here has more digits than this. Larger exponents are
+/// rejected before the number is written out in full.
+const MAX_INTEGER_EXPONENT: i64 = 39;
+
+/// Past this, an `f64` is infinite.
+const MAX_F64_EXPONENT: i64 = 310;
+
+/// Below this, an `f64` rounds to zero.
+const MIN_F64_EXPONENT: i64 = -330;
+
+/// Past these, a [`Decimal`](rust_decimal::Decimal) can't hold the number
+/// exactly.
+#[cfg(feature = "rust_decimal")]
+const DECIMAL_EXPONENTS: core::ops::RangeInclusive<i64> = -28..=29;
+
+impl Num {
+    /// The number as an integer string (no fractional part or exponent),
+    /// for parsing into an integer type.
+    fn integer_string(&self) -> Result<String, NumError> {
+        let parsed = self.parse()?;
+        if !parsed.is_integer() {
+            return Err(NumError::NotAnInteger(self.n.clone()));
+        }
+
+        if parsed.exponent > MAX_INTEGER_EXPONENT {
+            return Err(NumError::Overflow(self.n.clone()));
+        }
+
+        Ok(parsed.to_string())
+    }
+}
+
+macro_rules! try_from_num_int {
+    ($($t:ty),* $(,)?) => {
+        $(
+            /// Fails if the number has a fractional part or is out of range
+            /// for the type.
+            impl TryFrom<&Num> for $t {
+                type Error = NumError;
+
+                fn try_from(num: &Num) -> Result<Self, Self::Error> {
+                    num.integer_string()?
+                        .parse()
+                        .map_err(|_| NumError::Overflow(num.n.clone()))
+                }
+            }
+
+            /// Fails if the number has a fractional part or is out of range
+            /// for the type.
+            impl TryFrom<Num> for $t {
+                type Error = NumError;
+
+                fn try_from(num: Num) -> Result<Self, Self::Error> {
+                    Self::try_from(&num)
+                }
+            }
+        )*
+    };
+}
+
+try_from_num_int!(i64, u64, i128, u128);
+
+/// The result is the nearest `f64`, which may not be exact. Fails if the
+/// number is too large to be represented.
+impl TryFrom<&Num> for f64 {
+    type Error = NumError;
+
+    fn try_from(num: &Num) -> Result<Self, Self::Error> {
+        // Check the syntax first. Rust accepts things like "inf" and "NaN".
+        let parsed = num.parse()?;
+        if parsed.is_zero() || parsed.exponent < MIN_F64_EXPONENT {
+            return Ok(if parsed.negative { -0.0 } else { 0.0 });
+        }
+
+        if parsed.exponent > MAX_F64_EXPONENT {
+            return Err(NumError::Overflow(num.n.clone()));
+        }
+
+        let n: f64 = parsed
+            .to_string()
+            .parse()
+            .map_err(|_| NumError::Invalid(num.n.clone()))?;
+
+        if n.is_finite() {
+            Ok(n)
+        } else {
+            Err(NumError::Overflow(num.n.clone()))
+        }
+    }
+}
+
+/// The result is the nearest `f64`, which may not be exact. Fails if the
+/// number is too large to be represented.
+impl TryFrom<Num> for f64 {
+    type Error = NumError;
+
+    fn try_from(num: Num) -> Result<Self, Self::Error> {
+        Self::try_from(&num)
+    }
+}
+
+/// Fails if the number can't be represented exactly as a
+/// [`Decimal`](rust_decimal::Decimal).
+#[cfg(feature = "rust_decimal")]
+impl TryFrom<&Num> for rust_decimal::Decimal {
+    type Error = NumError;
+
+    fn try_from(num: &Num) -> Result<Self, Self::Error> {
+        let parsed = num.parse()?;
+        if !parsed.is_zero() && !DECIMAL_EXPONENTS.contains(&parsed.exponent) {
+            return Err(NumError::Overflow(num.n.clone()));
+        }
+
+        Self::from_str_exact(&parsed.to_string()).map_err(|_| NumError::Overflow(num.n.clone()))
+    }
+}
+
+/// Fails if the number can't be represented exactly as a
+/// [`Decimal`](rust_decimal::Decimal).
+#[cfg(feature = "rust_decimal")]
+impl TryFrom<Num> for rust_decimal::Decimal {
+    type Error = NumError;
+
+    fn try_from(num: Num) -> Result<Self, Self::Error> {
+        Self::try_from(&num)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::{Num, NumError};
+
+    fn num(s: &str) -> Num {
+        Num { n: s.into() }
+    }
+
+    #[test]
+    fn integers() {
+        assert_eq!(Ok(42), i64::try_from(num("42")));
+        assert_eq!(Ok(-4200), i64::try_from(num("-4.2e3")));
+        assert_eq!(Ok(12), i64::try_from(num("12.000")));
+        assert_eq!(Ok(i64::MIN), i64::try_from(Num::new(i64::MIN)));
+        assert_eq!(
+            Err(NumError::Overflow("9223372036854775808".into())),
+            i64::try_from(num("9223372036854775808"))
+        );
+        assert_eq!(
+            Err(NumError::NotAnInteger("1.5".into())),
+            i64::try_from(num("1.5"))
+        );
+        assert_eq!(
+            Err(NumError::Overflow("-1".into())),
+            u128::try_from(num("-1"))
+        );
+        assert_eq!(Ok(u128::MAX), u128::try_from(Num::new(u128::MAX)));
+        assert_eq!(Ok(10u128.pow(38)), u128::try_from(num("1e38")));
+        assert_eq!(
+            Err(NumError::Invalid("NaN".into())),
+            u64::try_from(num("NaN"))
+        );
+        assert_eq!(
+            Err(NumError::Overflow("1e999999999999".into())),
+            u128::try_from(num("1e999999999999")),
+            "Should fail without writing out the digits"
+        );
+    }
+
+    #[test]
+    fn floats() {
+        assert_eq!(Ok(1.5), f64::try_from(num("1.5")));
+        assert_eq!(Ok(-2600.0), f64::try_from(num("-2.6E3")));
+        assert_eq!(Ok(0.1), f64::try_from(num("0.1")));
+        assert_eq!(
+            Err(NumError::Overflow("1e400".into())),
+            f64::try_from(num("1e400"))
+        );
+        assert_eq!(
+            Err(NumError::Invalid("inf".into())),
+            f64::try_from(num("inf"))
+        );
+        assert_eq!(
+            Err(NumError::Overflow("1e999999999999".into())),
+            f64::try_from(num("1e999999999999"))
+        );
+        assert_eq!(Ok(0.0), f64::try_from(num("1e-999999999999")));
+        assert_eq!(Ok(-0.0), f64::try_from(num("-1e-999999999999")));
+        assert_eq!(Ok(1e-320), f64::try_from(num("1e-320")));
+    }
+
+    #[cfg(feature = "rust_decimal")]
+    #[test]
+    fn decimal() {
+        use rust_decimal::Decimal;
+
+        assert_eq!(
+            Ok(Decimal::new(-12345, 3)),
+            Decimal::try_from(num("-1.2345e1"))
+        );
+        assert_eq!(
+            Err(NumError::Overflow("1e100".into())),
+            Decimal::try_from(num("1e100"))
+        );
+        assert_eq!(
+            Err(NumError::Overflow("1e-999999999999".into())),
+            Decimal::try_from(num("1e-999999999999"))
+        );
+    }
+}
@@ -0,0 +1,464 @@
+mod convert;
+mod parsed;
+
+use core::{
+    cmp::Ordering,
+    fmt::{self, LowerExp, UpperExp},
+    hash::{Hash, Hasher},
+    str::FromStr,
+};
+use std::error::Error;
+
+use aws_sdk_dynamodb::types::AttributeValue;
+
+use self::parsed::{Limit, Parsed};
+
+/// Represents a DynamoDB [numeric value][1].
+///
+/// See also:
+/// * [`Scalar::new_num`]
+/// * [`Value::new_num`]
+/// * [`Scalar::new_num_lower_exp`]
+/// * [`Value::new_num_lower_exp`]
+/// * [`Scalar::new_num_upper_exp`]
+/// * [`Value::new_num_upper_exp`]
+///
+/// # Examples
+///
+/// ```
+/// use dynamodb_expression::value::Num;
+/// # use pretty_assertions::assert_eq;
+///
+/// let value = Num::new(2600);
+/// assert_eq!("2600", value.to_string());
+///
+/// let value = Num::new_lower_exp(2600);
+/// assert_eq!("2.6e3", value.to_string());
+///
+/// let value = Num::new_upper_exp(2600);
+/// assert_eq!("2.6E3", value.to_string());
+///
+/// let value = Num::new(2600.0);
+/// assert_eq!("2600", value.to_string());
+///
+/// let value = Num::new_lower_exp(2600.0);
+/// assert_eq!("2.6e3", value.to_string());
+///
+/// let value = Num::new_upper_exp(2600.0);
+/// assert_eq!("2.6E3", value.to_string());
+/// ```
+///
+/// Equality, ordering, and hashing are numeric, so the same number written
+/// different ways is the same value. The text is kept as it was written.
+///
+/// ```
+/// use dynamodb_expression::value::{Num, NumSet};
+/// # use pretty_assertions::assert_eq;
+///
+/// assert_eq!(Num::new(2600), Num::new_lower_exp(2600));
+/// assert!(Num::new(9.5) < Num::new(10));
+///
+/// let set = NumSet::new([Num::new(1000), Num::new(42), Num::new(1000.0)]);
+/// assert_eq!("[42, 1000]", set.to_string());
+/// ```
+///
+/// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/HowItWorks.NamingRulesDataTypes.html#HowItWorks.DataTypes.Number
+/// [`Scalar::new_num`]: crate::value::Scalar::new_num
+/// [`Scalar::new_num_lower_exp`]: crate::value::Scalar::new_num_lower_exp
+/// [`Scalar::new_num_upper_exp`]: crate::value::Scalar::new_num_upper_exp
+/// [`Value::new_num`]: crate::value::Value::new_num
+/// [`Value::new_num_lower_exp`]: crate::value::Value::new_num_lower_exp
+/// [`Value::new_num_upper_exp`]: crate::value::Value::new_num_upper_exp
+#[derive(Debug, Clone)]
+pub struct Num {
+    pub(crate) n: String,
+}
+
+impl Num {
+    /// Creates a DynamoDB [numeric value][1].
+    ///
+    /// See also: [`Num::new_lower_exp`], [`Num::new_upper_exp`], [`Scalar::new_num`],
+    /// [`Value::new_num`]
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dynamodb_expression::value::Num;
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let value = Num::new(2600);
+    /// assert_eq!("2600", value.to_string());
+    ///
+    /// let value = Num::new(2600.0);
+    /// assert_eq!("2600", value.to_string());
+    /// ```
+    ///
+    /// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/HowItWorks.NamingRulesDataTypes.html#HowItWorks.DataTypes.Number
+    /// [`Scalar::new_num`]: crate::value::Scalar::new_num
+    /// [`Value::new_num`]: crate::value::Value::new_num
+    pub fn new<T>(value: T) -> Self
+    where
+        T: ToString + num::Num,
+    {
+        Self {
+            n: value.to_string(),
+        }
+    }
+
+    /// Creates a DynamoDB [numeric value][1].
+    ///
+    /// See also: [`Num::new`], [`Num::new_upper_exp`], [`Scalar::new_num_lower_exp`],
+    /// [`Value::new_num_lower_exp`]
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dynamodb_expression::value::Num;
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let value = Num::new_lower_exp(2600);
+    /// assert_eq!("2.6e3", value.to_string());
+    ///
+    /// let value = Num::new_lower_exp(2600.0);
+    /// assert_eq!("2.6e3", value.to_string());
+    /// ```
+    ///
+    /// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/HowItWorks.NamingRulesDataTypes.html#HowItWorks.DataTypes.Number
+    /// [`Scalar::new_num_lower_exp`]: crate::value::Scalar::new_num_lower_exp
+    /// [`Value::new_num_lower_exp`]: crate::value::Value::new_num_lower_exp
+    pub fn new_lower_exp<T>(value: T) -> Self
+    where
+        T: LowerExp + num::Num,
+    {
+        Self {
+            n: format!("{value:e}"),
+        }
+    }
+
+    /// Creates a DynamoDB [numeric value][1].
+    ///
+    /// See also: [`Num::new`], [`Num::new_lower_exp`], [`Scalar::new_num_upper_exp`],
+    /// [`Value::new_num_upper_exp`]
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dynamodb_expression::value::Num;
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let value = Num::new_upper_exp(2600);
+    /// assert_eq!("2.6E3", value.to_string());
+    ///
+    /// let value = Num::new_upper_exp(2600.0);
+    /// assert_eq!("2.6E3", value.to_string());
+    /// ```
+    ///
+    /// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/HowItWorks.NamingRulesDataTypes.html#HowItWorks.DataTypes.Number
+    /// [`Scalar::new_num_upper_exp`]: crate::value::Scalar::new_num_upper_exp
+    /// [`Value::new_num_upper_exp`]: crate::value::Value::new_num_upper_exp
+    pub fn new_upper_exp<T>(value: T) -> Self
+    where
+        T: UpperExp + num::Num,
+    {
+        Self {
+            n: format!("{value:E}"),
+        }
+    }
+
+    /// Checks that this is a number DynamoDB will accept: no more than 38
+    /// significant digits, and a magnitude between 1E-130 and
+    /// 9.9999999999999999999999999999999999999E+125 (or zero).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dynamodb_expression::value::{Num, NumError};
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// assert_eq!(Ok(()), Num::new(42).validate());
+    /// assert_eq!(
+    ///     Err(NumError::OutOfRange("1e200".into())),
+    ///     Num::new_lower_exp(1e200).validate(),
+    /// );
+    /// assert_eq!(Err(NumError::Invalid("NaN".into())), Num::new(f64::NAN).validate());
+    /// ```
+    pub fn validate(&self) -> Result<(), NumError> {
+        self.parse_valid().map(drop)
+    }
+
+    /// Returns this number in its normalized form: plain (non-scientific)
+    /// notation with no leading zeros and no trailing fractional zeros.
+    ///
+    /// Returns an error if this is not a number DynamoDB will accept.
+    /// See [`Num::validate`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dynamodb_expression::value::Num;
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let num: Num = "-0012.3400".parse()?;
+    /// assert_eq!("-12.34", num.normalize()?.to_string());
+    ///
+    /// let num = Num::new_lower_exp(2600);
+    /// assert_eq!("2600", num.normalize()?.to_string());
+    /// #
+    /// # Ok::<(), dynamodb_expression::value::NumError>(())
+    /// ```
+    pub fn normalize(&self) -> Result<Self, NumError> {
+        self.parse_valid().map(|parsed| Self {
+            n: parsed.to_string(),
+        })
+    }
+
+    fn parse(&self) -> Result<Parsed, NumError> {
+        Parsed::parse(&self.n).ok_or_else(|| NumError::Invalid(self.n.clone()))
+    }
+
+    fn parse_valid(&self) -> Result<Parsed, NumError> {
+        let parsed = self.parse()?;
+        parsed.check_limits().map_err(|limit| match limit {
+            Limit::TooManyDigits => NumError::TooManyDigits(self.n.clone()),
+            Limit::OutOfRange => NumError::OutOfRange(self.n.clone()),
+        })?;
+
+        Ok(parsed)
+    }
+
+    // Intentionally not using `impl From<Num> for AttributeValue` because
+    // I don't want to make this a public API people rely on. The purpose of this
+    // crate is not to make creating `AttributeValues` easier. They should try
+    // `serde_dynamo`.
+    pub(super) fn into_attribute_value(self) -> AttributeValue {
+        AttributeValue::N(self.n)
+    }
+}
+
+impl fmt::Display for Num {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.n.fmt(f)
+    }
+}
+
+/// Parses and validates a number using DynamoDB's rules. The text is kept as
+/// written. Use [`Num::normalize`] to get the normalized form.
+///
+/// # Examples
+///
+/// ```
+/// use dynamodb_expression::value::{Num, NumError};
+/// # use pretty_assertions::assert_eq;
+///
+/// let num: Num = "1.50".parse()?;
+/// assert_eq!("1.50", num.to_string());
+/// assert_eq!(Num::new(1.5), num);
+///
+/// assert_eq!(
+///     Err(NumError::Invalid("1,000".into())),
+///     "1,000".parse::<Num>(),
+/// );
+/// #
+/// # Ok::<(), NumError>(())
+/// ```
+impl FromStr for Num {
+    type Err = NumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let num = Self { n: s.into() };
+        num.validate()?;
+
+        Ok(num)
+    }
+}
+
+/// The approximate size of a number when stored by DynamoDB, in bytes. Text
+/// that isn't a number is sized by its length.
+pub(in crate::value) fn num_size_bytes(n: &str) -> usize {
+    match Parsed::parse(n) {
+        Some(parsed) if parsed.is_zero() => 1,
+        Some(parsed) => parsed.digits.len().div_ceil(2) + 1,
+        None => n.len(),
+    }
+}
+
+/// The comparison key for a `Num`. Numbers that can't be parsed are sorted
+/// after all valid numbers, by their text.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash)]
+enum Key<'a> {
+    Num(Parsed),
+    Invalid(&'a str),
+}
+
+impl Num {
+    fn key(&self) -> Key<'_> {
+        match Parsed::parse(&self.n) {
+            Some(parsed) => Key::Num(parsed),
+            None => Key::Invalid(&self.n),
+        }
+    }
+}
+
+impl PartialEq for Num {
+    fn eq(&self, other: &Self) -> bool {
+        self.n == other.n || self.key() == other.key()
+    }
+}
+
+impl Eq for Num {}
+
+impl PartialOrd for Num {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Num {
+    fn cmp(&self, other: &Self) -> Ordering {
+        if self.n == other.n {
+            return Ordering::Equal;
+        }
+
+        self.key().cmp(&other.key())
+    }
+}
+
+impl Hash for Num {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key().hash(state);
+    }
+}
+
+impl<T> From<T> for Num
+where
+    T: ToString + num::Num,
+{
+    fn from(num: T) -> Self {
+        Num::new(num)
+    }
+}
+
+impl From<Num> for String {
+    fn from(num: Num) -> Self {
+        num.n
+    }
+}
+
+/// An error parsing, validating, or converting a [`Num`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NumError {
+    /// The text is not a number.
+    Invalid(String),
+    /// The number has more than the 38 significant digits DynamoDB allows.
+    TooManyDigits(String),
+    /// The number's magnitude is outside of what DynamoDB allows.
+    OutOfRange(String),
+    /// The number has a fractional part, but an integer was needed.
+    NotAnInteger(String),
+    /// The number doesn't fit in the type it's being converted to.
+    Overflow(String),
+}
+
+impl fmt::Display for NumError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Invalid(n) => write!(f, "not a number: {n:?}"),
+            Self::TooManyDigits(n) => {
+                write!(f, "more than 38 significant digits: {n}")
+            }
+            Self::OutOfRange(n) => write!(
+                f,
+                "magnitude must be between 1E-130 and \
+                9.9999999999999999999999999999999999999E+125: {n}"
+            ),
+            Self::NotAnInteger(n) => write!(f, "not an integer: {n}"),
+            Self::Overflow(n) => write!(f, "does not fit in the target type: {n}"),
+        }
+    }
+}
+
+impl Error for NumError {}
+
+#[cfg(test)]
+mod test {
+    use std::collections::{BTreeSet, HashSet};
+
+    use pretty_assertions::assert_eq;
+
+    use super::{Num, NumError};
+
+    #[test]
+    fn numeric_equality() {
+        let one: Num = "1".parse().unwrap();
+        for other in ["1.0", "1.000", "01", "+1", "1e0", "10E-1", "0.1e1"] {
+            let other: Num = other.parse().unwrap();
+            assert_eq!(one, other);
+            assert_eq!(one.cmp(&other), core::cmp::Ordering::Equal);
+        }
+
+        assert_ne!(one, Num::new(-1));
+        assert_eq!(Num::new(0), "-0.0".parse::<Num>().unwrap());
+
+        let set: HashSet<Num> = ["1", "1.0", "1e0", "2"]
+            .into_iter()
+            .map(|n| n.parse().unwrap())
+            .collect();
+        assert_eq!(2, set.len());
+
+        let set: BTreeSet<Num> = ["10", "9.5", "1e1", "-3", "100e-1"]
+            .into_iter()
+            .map(|n| n.parse().unwrap())
+            .collect();
+        assert_eq!(
+            vec![Num::new(-3), Num::new(9.5), Num::new(10)],
+            set.into_iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn invalid_text_ordering() {
+        // `Num::new` doesn't validate, so these need a consistent ordering, too.
+        let nan = Num::new(f64::NAN);
+        let inf = Num::new(f64::INFINITY);
+
+        assert_eq!(nan, Num::new(f64::NAN));
+        assert!(Num::new(1e100) < inf);
+        assert!(Num::new(1e100) < nan);
+        assert_ne!(inf, nan);
+    }
+
+    #[test]
+    fn from_str() {
+        assert_eq!(
+            Ok("0012.50"),
+            "0012.50".parse::<Num>().as_ref().map(|n| n.n.as_str())
+        );
+        assert_eq!(Err(NumError::Invalid("".into())), "".parse::<Num>());
+        assert_eq!(Err(NumError::Invalid("1e".into())), "1e".parse::<Num>());
+        assert_eq!(
+            Err(NumError::TooManyDigits(
+                "1234567890.12345678901234567890123456789".into()
+            )),
+            "1234567890.12345678901234567890123456789".parse::<Num>()
+        );
+        assert_eq!(
+            Err(NumError::OutOfRange("1e-131".into())),
+            "1e-131".parse::<Num>()
+        );
+    }
+
+    #[test]
+    fn normalize() {
+        for (input, expected) in [
+            ("0.000", "0"),
+            ("-0", "0"),
+            ("0012.3400", "12.34"),
+            ("1.5e2", "150"),
+            ("-1.5E-2", "-0.015"),
+            ("1000", "1000"),
+        ] {
+            let num: Num = input.parse().unwrap();
+            assert_eq!(expected, num.normalize().unwrap().to_string(), "{input:?}");
+        }
+    }
+}
@@ -0,0 +1,294 @@
+use core::{
+    cmp::Ordering,
+    fmt::{self, Write},
+};
+
+/// The most significant digits DynamoDB will store for a number.
+const MAX_DIGITS: usize = 38;
+
+/// The smallest exponent (in scientific notation) DynamoDB allows.
+const MIN_EXPONENT: i64 = -130;
+
+/// The largest exponent (in scientific notation) DynamoDB allows.
+const MAX_EXPONENT: i64 = 125;
+
+/// Parsed exponents are clamped to this magnitude, so arithmetic on them can't
+/// overflow. Anything this large is far outside of DynamoDB's limits anyway.
+const EXPONENT_CLAMP: i64 = i64::MAX / 4;
+
+/// A parsed decimal number. The value is `0.{digits} * 10^{exponent}`.
+///
+/// `digits` never has leading or trailing zeros. Zero is represented with
+/// empty `digits`, an `exponent` of `0`, and is never negative.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(super) struct Parsed {
+    pub(super) negative: bool,
+    pub(super) digits: String,
+    pub(super) exponent: i64,
+}
+
+/// Why a number is outside of what DynamoDB allows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Limit {
+    TooManyDigits,
+    OutOfRange,
+}
+
+impl Parsed {
+    /// Parses the syntax of a number. Returns `None` if it isn't a number.
+    ///
+    /// Accepts an optional sign, digits with an optional decimal point, and an
+    /// optional exponent (`e` or `E`). This does not check DynamoDB's limits.
+    pub(super) fn parse(s: &str) -> Option<Self> {
+        let (negative, s) = match s.as_bytes().first()? {
+            b'-' => (true, &s[1..]),
+            b'+' => (false, &s[1..]),
+            _ => (false, s),
+        };
+
+        let (mantissa, exponent) = match s.find(['e', 'E']) {
+            Some(i) => (&s[..i], Some(&s[i + 1..])),
+            None => (s, None),
+        };
+
+        let (int, frac) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+        if int.is_empty() && frac.is_empty()
+            || !int.bytes().chain(frac.bytes()).all(|b| b.is_ascii_digit())
+        {
+            return None;
+        }
+
+        let exponent = match exponent {
+            Some(exponent) => parse_exponent(exponent)?,
+            None => 0,
+        };
+
+        let leading_zeros = int
+            .bytes()
+            .chain(frac.bytes())
+            .take_while(|&b| b == b'0')
+            .count();
+
+        let mut digits: String = int
+            .chars()
+            .chain(frac.chars())
+            .skip(leading_zeros)
+            .collect();
+        digits.truncate(digits.trim_end_matches('0').len());
+
+        if digits.is_empty() {
+            return Some(Self::zero());
+        }
+
+        Some(Self {
+            negative,
+            digits,
+            exponent: (int.len() as i64 - leading_zeros as i64)
+                .saturating_add(exponent)
+                .clamp(-EXPONENT_CLAMP, EXPONENT_CLAMP),
+        })
+    }
+
+    fn zero() -> Self {
+        Self {
+            negative: false,
+            digits: String::new(),
+            exponent: 0,
+        }
+    }
+
+    pub(super) fn is_zero(&self) -> bool {
+        self.digits.is_empty()
+    }
+
+    /// Checks the number against DynamoDB's limits on precision and magnitude.
+    pub(super) fn check_limits(&self) -> Result<(), Limit> {
+        if self.digits.len() > MAX_DIGITS {
+            return Err(Limit::TooManyDigits);
+        }
+
+        // In scientific notation (`d.ddd * 10^e`) the exponent is one less.
+        if !self.is_zero()
+            && !(MIN_EXPONENT..=MAX_EXPONENT).contains(&self.exponent.saturating_sub(1))
+        {
+            return Err(Limit::OutOfRange);
+        }
+
+        Ok(())
+    }
+
+    /// Whether the number has no fractional part.
+    pub(super) fn is_integer(&self) -> bool {
+        self.exponent >= self.digits.len() as i64
+    }
+
+    fn cmp_magnitude(&self, other: &Self) -> Ordering {
+        match (self.is_zero(), other.is_zero()) {
+            (true, true) => Ordering::Equal,
+            (true, false) => Ordering::Less,
+            (false, true) => Ordering::Greater,
+            // With no leading or trailing zeros, comparing the digits as
+            // strings works when the exponents are the same.
+            (false, false) => self
+                .exponent
+                .cmp(&other.exponent)
+                .then_with(|| self.digits.cmp(&other.digits)),
+        }
+    }
+}
+
+fn parse_exponent(s: &str) -> Option<i64> {
+    let (negative, digits) = match s.as_bytes().first()? {
+        b'-' => (true, &s[1..]),
+        b'+' => (false, &s[1..]),
+        _ => (false, s),
+    };
+
+    if digits.is_empty() {
+        return None;
+    }
+
+    // Saturate rather than fail. Anything this large is out of range anyway.
+    let mut exponent: i64 = 0;
+    for b in digits.bytes() {
+        if !b.is_ascii_digit() {
+            return None;
+        }
+
+        exponent = exponent
+            .saturating_mul(10)
+            .saturating_add(i64::from(b - b'0'));
+    }
+
+    Some(if negative { -exponent } else { exponent })
+}
+
+impl Ord for Parsed {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, false) => self.cmp_magnitude(other),
+            (true, true) => other.cmp_magnitude(self),
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+        }
+    }
+}
+
+impl PartialOrd for Parsed {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Formats the number in plain (non-scientific) notation, with no leading
+/// zeros and no trailing fractional zeros.
+impl fmt::Display for Parsed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_zero() {
+            return f.write_char('0');
+        }
+
+        if self.negative {
+            f.write_char('-')?;
+        }
+
+        let len = self.digits.len() as i64;
+        if self.exponent <= 0 {
+            f.write_str("0.")?;
+            for _ in self.exponent..0 {
+                f.write_char('0')?;
+            }
+            f.write_str(&self.digits)
+        } else if self.exponent >= len {
+            f.write_str(&self.digits)?;
+            for _ in len..self.exponent {
+                f.write_char('0')?;
+            }
+
+            Ok(())
+        } else {
+            let (int, frac) = self.digits.split_at(self.exponent as usize);
+            write!(f, "{int}.{frac}")
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::{Limit, Parsed};
+
+    #[test]
+    fn parse() {
+        for (input, expected) in [
+            ("0", "0"),
+            ("-0", "0"),
+            ("000.000", "0"),
+            ("0e99999999999999999999", "0"),
+            ("1", "1"),
+            ("+1", "1"),
+            ("007", "7"),
+            ("1.50", "1.5"),
+            ("-1.50", "-1.5"),
+            (".5", "0.5"),
+            ("5.", "5"),
+            ("0.0012", "0.0012"),
+            ("1200", "1200"),
+            ("1.2e3", "1200"),
+            ("12E-4", "0.0012"),
+            ("1.2e+1", "12"),
+        ] {
+            let parsed = Parsed::parse(input).unwrap_or_else(|| panic!("{input:?} should parse"));
+            assert_eq!(expected, parsed.to_string(), "{input:?}");
+        }
+
+        for input in [
+            "", "-", "+", ".", "e5", "1e", "1e+", "1.2.3", "1,000", " 1", "1 ", "--1", "0x10",
+            "NaN", "inf", "1e5.5",
+        ] {
+            assert_eq!(None, Parsed::parse(input), "{input:?}");
+        }
+    }
+
+    #[test]
+    fn limits() {
+        let check = |s: &str| Parsed::parse(s).unwrap().check_limits();
+
+        assert_eq!(Ok(()), check("0"));
+        assert_eq!(Ok(()), check("1e-130"));
+        assert_eq!(Ok(()), check("-1e-130"));
+        assert_eq!(
+            Ok(()),
+            check("9.9999999999999999999999999999999999999E+125")
+        );
+        assert_eq!(Ok(()), check("12345678901234567890123456789012345678000"));
+        assert_eq!(Err(Limit::OutOfRange), check("1e-131"));
+        assert_eq!(Err(Limit::OutOfRange), check("1e126"));
+        assert_eq!(Err(Limit::OutOfRange), check("-1e126"));
+        assert_eq!(
+            Err(Limit::OutOfRange),
+            check("0.0001e-99999999999999999999")
+        );
+        assert_eq!(Err(Limit::OutOfRange), check("1000e99999999999999999999"));
+        assert_eq!(
+            Err(Limit::TooManyDigits),
+            check("123456789012345678901234567890123456789")
+        );
+    }
+
+    #[test]
+    fn ordering() {
+        let ordered = [
+            "-1e125", "-100", "-99.9", "-1", "-0.5", "-1e-130", "0", "1e-130", "0.5", "1", "1.05",
+            "1.5", "10", "99.9", "100", "1e125",
+        ]
+        .map(|s| Parsed::parse(s).unwrap());
+
+        for (i, a) in ordered.iter().enumerate() {
+            for (j, b) in ordered.iter().enumerate() {
+                assert_eq!(i.cmp(&j), a.cmp(b), "{a} vs. {b}");
+            }
+        }
+    }
+}
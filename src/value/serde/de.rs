@@ -0,0 +1,328 @@
+use ::serde::{
+    de::{
+        self, value::StringDeserializer, Deserialize, DeserializeSeed, IntoDeserializer, Visitor,
+    },
+    forward_to_deserialize_any,
+};
+
+use crate::{
+    path::Name,
+    value::{List, Map, Num, Scalar, Set, Value, ValuePathSegment},
+};
+
+use super::SerdeError;
+
+/// A [`serde::Deserializer`] that reads from a [`Value`].
+///
+/// See also: [`from_value`](super::from_value)
+#[derive(Debug, Clone)]
+pub struct Deserializer {
+    value: Value,
+}
+
+impl Deserializer {
+    /// A deserializer that reads from this value.
+    pub fn new(value: Value) -> Self {
+        Self { value }
+    }
+}
+
+impl IntoDeserializer<'_, SerdeError> for Value {
+    type Deserializer = Deserializer;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        Deserializer::new(self)
+    }
+}
+
+fn visit_num<'de, V>(num: Num, visitor: V) -> Result<V::Value, SerdeError>
+where
+    V: Visitor<'de>,
+{
+    let Num { n } = num;
+
+    if let Ok(n) = n.parse::<u64>() {
+        visitor.visit_u64(n)
+    } else if let Ok(n) = n.parse::<i64>() {
+        visitor.visit_i64(n)
+    } else if let Ok(n) = n.parse::<u128>() {
+        visitor.visit_u128(n)
+    } else if let Ok(n) = n.parse::<i128>() {
+        visitor.visit_i128(n)
+    } else if let Ok(n) = n.parse::<f64>() {
+        visitor.visit_f64(n)
+    } else {
+        Err(de::Error::custom(format_args!("invalid number: {n}")))
+    }
+}
+
+fn visit_list<'de, V>(list: Vec<Value>, visitor: V) -> Result<V::Value, SerdeError>
+where
+    V: Visitor<'de>,
+{
+    let len = list.len();
+    let mut seq = SeqAccess {
+        iter: list.into_iter().enumerate(),
+    };
+    let value = visitor.visit_seq(&mut seq)?;
+
+    match seq.iter.len() {
+        0 => Ok(value),
+        remaining => Err(de::Error::invalid_length(
+            len,
+            &format!("{} elements in sequence", len - remaining).as_str(),
+        )),
+    }
+}
+
+/// Reads list elements, noting the index of any element that fails.
+struct SeqAccess {
+    iter: core::iter::Enumerate<std::vec::IntoIter<Value>>,
+}
+
+impl<'de> de::SeqAccess<'de> for SeqAccess {
+    type Error = SerdeError;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        self.iter
+            .next()
+            .map(|(i, value)| {
+                seed.deserialize(Deserializer::new(value))
+                    .map_err(|err| err.within(ValuePathSegment::Index(i)))
+            })
+            .transpose()
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+}
+
+/// Reads map entries, noting the key of any value that fails.
+struct MapAccess<I> {
+    iter: I,
+    value: Option<(String, Value)>,
+}
+
+impl<'de, I> de::MapAccess<'de> for MapAccess<I>
+where
+    I: ExactSizeIterator<Item = (Name, Value)>,
+{
+    type Error = SerdeError;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        let Some((Name { name }, value)) = self.iter.next() else {
+            return Ok(None);
+        };
+
+        self.value = Some((name.clone(), value));
+        seed.deserialize(StringDeserializer::new(name)).map(Some)
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Self::Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let (name, value) = self
+            .value
+            .take()
+            .ok_or_else(|| SerdeError::new("next_value_seed called before next_key_seed"))?;
+
+        seed.deserialize(Deserializer::new(value))
+            .map_err(|err| err.within(ValuePathSegment::Key(name)))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+}
+
+/// Reads an enum variant with content, from a map with a single entry keyed by
+/// the variant name. Notes the variant name for any problem with the content.
+struct EnumAccess {
+    variant: String,
+    value: Value,
+}
+
+impl<'de> de::EnumAccess<'de> for EnumAccess {
+    type Error = SerdeError;
+    type Variant = VariantAccess;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self::Variant), Self::Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let Self { variant, value } = self;
+        let name = seed.deserialize(StringDeserializer::<SerdeError>::new(variant.clone()))?;
+
+        Ok((name, VariantAccess { variant, value }))
+    }
+}
+
+/// The content of an enum variant. See [`EnumAccess`].
+struct VariantAccess {
+    variant: String,
+    value: Value,
+}
+
+impl VariantAccess {
+    fn within(variant: String) -> impl FnOnce(SerdeError) -> SerdeError {
+        move |err| err.within(ValuePathSegment::Key(variant))
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for VariantAccess {
+    type Error = SerdeError;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        <()>::deserialize(Deserializer::new(self.value)).map_err(Self::within(self.variant))
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, Self::Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        seed.deserialize(Deserializer::new(self.value))
+            .map_err(Self::within(self.variant))
+    }
+
+    fn tuple_variant<V>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        de::Deserializer::deserialize_tuple(Deserializer::new(self.value), len, visitor)
+            .map_err(Self::within(self.variant))
+    }
+
+    fn struct_variant<V>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        de::Deserializer::deserialize_struct(Deserializer::new(self.value), "", fields, visitor)
+            .map_err(Self::within(self.variant))
+    }
+}
+
+impl<'de> de::Deserializer<'de> for Deserializer {
+    type Error = SerdeError;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.value {
+            Value::Scalar(Scalar::String(s)) => visitor.visit_string(s),
+            Value::Scalar(Scalar::Num(n)) => visit_num(n, visitor),
+            Value::Scalar(Scalar::Bool(b)) => visitor.visit_bool(b),
+            Value::Scalar(Scalar::Binary(b)) => visitor.visit_byte_buf(b),
+            Value::Scalar(Scalar::Null) => visitor.visit_unit(),
+            Value::Set(Set::StringSet(set)) => {
+                visit_list(set.0.into_iter().map(Value::from).collect(), visitor)
+            }
+            Value::Set(Set::NumSet(set)) => {
+                visit_list(set.0.into_iter().map(Value::from).collect(), visitor)
+            }
+            Value::Set(Set::BinarySet(set)) => {
+                visit_list(set.0.into_iter().map(Value::from).collect(), visitor)
+            }
+            Value::List(List { list }) => visit_list(list, visitor),
+            Value::Map(Map { map }) => visitor.visit_map(MapAccess {
+                iter: map.into_iter(),
+                value: None,
+            }),
+        }
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.value {
+            Value::Scalar(Scalar::Null) => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_unit_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_any(visitor)
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    /// Numbers can also be read as strings, which allows for number types
+    /// that don't fit in the primitive types.
+    fn deserialize_str<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.value {
+            Value::Scalar(Scalar::Num(Num { n })) => visitor.visit_string(n),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_string<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.value {
+            Value::Scalar(Scalar::String(variant)) => {
+                visitor.visit_enum(StringDeserializer::<SerdeError>::new(variant))
+            }
+            Value::Map(Map { map }) if map.len() == 1 => {
+                let (Name { name: variant }, value) =
+                    map.into_iter().next().expect("The map has one entry");
+
+                visitor.visit_enum(EnumAccess { variant, value })
+            }
+            value => Err(de::Error::invalid_type(
+                de::Unexpected::Other(&value.to_string()),
+                &"a string or a map with a single entry",
+            )),
+        }
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char
+        bytes byte_buf unit seq tuple tuple_struct map struct identifier
+        ignored_any
+    }
+}
@@ -0,0 +1,562 @@
+//! Converts between [`Value`] and any type that implements
+//! [`serde::Serialize`] or [`serde::Deserialize`].
+//!
+//! Requires the `serde` feature.
+//!
+//! Rust types map to DynamoDB values like this:
+//! * `bool` → boolean
+//! * Integers and floats → number. Floats must be finite.
+//! * `char`, `&str`, `String` → string
+//! * `Option::None`, `()`, unit structs → null
+//! * Sequences, tuples → list
+//! * Maps, structs → map. Map keys must be strings, characters, or integers.
+//! * Unit enum variants → string of the variant name
+//! * Other enum variants → map with a single entry, keyed by the variant name
+//!
+//! DynamoDB sets and binary values don't have a natural serde equivalent. Use
+//! the [`string_set`], [`number_set`], [`binary_set`], and [`binary`] modules
+//! with `#[serde(with = "...")]` to get those. DynamoDB doesn't allow empty
+//! sets, so serializing an empty one is an error. Skip the field when it's
+//! empty, with `#[serde(default, skip_serializing_if = "...")]`.
+//!
+//! ```
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! use std::collections::BTreeSet;
+//!
+//! use dynamodb_expression::{
+//!     value::{self, Value},
+//!     Path,
+//! };
+//! use serde::{Deserialize, Serialize};
+//! # use pretty_assertions::assert_eq;
+//!
+//! #[derive(Debug, PartialEq, Serialize, Deserialize)]
+//! struct Address {
+//!     city: String,
+//!     zip: Option<String>,
+//!     #[serde(
+//!         with = "value::serde::string_set",
+//!         default,
+//!         skip_serializing_if = "BTreeSet::is_empty"
+//!     )]
+//!     tags: BTreeSet<String>,
+//! }
+//!
+//! let address = Address {
+//!     city: String::from("Seattle"),
+//!     zip: None,
+//!     tags: BTreeSet::from([String::from("home")]),
+//! };
+//!
+//! let value = value::serde::to_value(&address)?;
+//! assert_eq!(
+//!     Value::new_map([
+//!         ("city", Value::from("Seattle")),
+//!         ("zip", Value::new_null()),
+//!         ("tags", Value::new_string_set(["home"])),
+//!     ]),
+//!     value,
+//! );
+//!
+//! let update = Path::new_name("address").set(value.clone());
+//! # _ = update;
+//!
+//! let decoded: Address = value::serde::from_value(value)?;
+//! assert_eq!(address, decoded);
+//! #
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`Value`]: crate::value::Value
+
+mod de;
+mod ser;
+
+pub use de::Deserializer;
+pub use ser::Serializer;
+
+use core::fmt;
+use std::error::Error;
+
+use ::serde::{de::DeserializeOwned, Serialize};
+
+use super::{validate::write_path_prefix, Value, ValuePathSegment};
+
+/// Serializes any [`Serialize`] type into a [`Value`].
+///
+/// See the [module documentation](self) for how types are mapped.
+pub fn to_value<T>(value: &T) -> Result<Value, SerdeError>
+where
+    T: ?Sized + Serialize,
+{
+    value.serialize(Serializer)
+}
+
+/// Deserializes a [`Value`] into any [`DeserializeOwned`] type.
+///
+/// See the [module documentation](self) for how types are mapped.
+pub fn from_value<T>(value: Value) -> Result<T, SerdeError>
+where
+    T: DeserializeOwned,
+{
+    T::deserialize(Deserializer::new(value))
+}
+
+/// An error that occurred while converting between a [`Value`] and a Rust type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SerdeError {
+    path: Vec<ValuePathSegment>,
+    message: String,
+}
+
+impl SerdeError {
+    pub(crate) fn new<T>(message: T) -> Self
+    where
+        T: fmt::Display,
+    {
+        Self {
+            path: Vec::new(),
+            message: message.to_string(),
+        }
+    }
+
+    /// Where in the value the problem is, when deserializing. Empty if it's
+    /// the value itself.
+    pub fn path(&self) -> &[ValuePathSegment] {
+        &self.path
+    }
+
+    /// The message, without the path.
+    pub(crate) fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Marks this error as being within the given map key or list index.
+    pub(super) fn within(mut self, segment: ValuePathSegment) -> Self {
+        self.path.insert(0, segment);
+        self
+    }
+}
+
+impl fmt::Display for SerdeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_path_prefix(f, &self.path)?;
+        f.write_str(&self.message)
+    }
+}
+
+impl Error for SerdeError {}
+
+impl ::serde::ser::Error for SerdeError {
+    fn custom<T>(msg: T) -> Self
+    where
+        T: fmt::Display,
+    {
+        Self::new(msg)
+    }
+}
+
+impl ::serde::de::Error for SerdeError {
+    fn custom<T>(msg: T) -> Self
+    where
+        T: fmt::Display,
+    {
+        Self::new(msg)
+    }
+}
+
+// Newtype struct names the `Serializer` recognizes as sets. Other serializers
+// will see a newtype wrapping a sequence, which they handle as usual.
+const STRING_SET: &str = "\0dynamodb_expression::StringSet";
+const NUMBER_SET: &str = "\0dynamodb_expression::NumSet";
+const BINARY_SET: &str = "\0dynamodb_expression::BinarySet";
+
+/// Use with `#[serde(with = "dynamodb_expression::value::serde::string_set")]`
+/// on a collection of strings to serialize it as a [DynamoDB string set][1].
+///
+/// Fails for an empty collection, since DynamoDB sets can't be empty.
+///
+/// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/HowItWorks.NamingRulesDataTypes.html#HowItWorks.DataTypes.SetTypes
+pub mod string_set {
+    use ::serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: ?Sized + Serialize,
+        S: Serializer,
+    {
+        serializer.serialize_newtype_struct(super::STRING_SET, value)
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        T::deserialize(deserializer)
+    }
+}
+
+/// Use with `#[serde(with = "dynamodb_expression::value::serde::number_set")]`
+/// on a collection of numbers to serialize it as a [DynamoDB number set][1].
+///
+/// Fails for an empty collection, since DynamoDB sets can't be empty.
+///
+/// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/HowItWorks.NamingRulesDataTypes.html#HowItWorks.DataTypes.SetTypes
+pub mod number_set {
+    use ::serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: ?Sized + Serialize,
+        S: Serializer,
+    {
+        serializer.serialize_newtype_struct(super::NUMBER_SET, value)
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        T::deserialize(deserializer)
+    }
+}
+
+/// Use with `#[serde(with = "dynamodb_expression::value::serde::binary_set")]`
+/// on a collection of byte buffers to serialize it as a
+/// [DynamoDB binary set][1].
+///
+/// Fails for an empty collection, since DynamoDB sets can't be empty.
+///
+/// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/HowItWorks.NamingRulesDataTypes.html#HowItWorks.DataTypes.SetTypes
+pub mod binary_set {
+    use ::serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<T, I, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        for<'a> &'a T: IntoIterator<Item = &'a I>,
+        T: ?Sized,
+        I: AsRef<[u8]>,
+        S: Serializer,
+    {
+        serializer.serialize_newtype_struct(super::BINARY_SET, &Bytes(value))
+    }
+
+    pub fn deserialize<'de, T, I, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: FromIterator<I>,
+        I: From<Vec<u8>>,
+        D: Deserializer<'de>,
+    {
+        Ok(Vec::<super::binary::ByteBuf>::deserialize(deserializer)?
+            .into_iter()
+            .map(|bytes| I::from(bytes.0))
+            .collect())
+    }
+
+    /// Serializes each item with `serialize_bytes`, rather than as a
+    /// sequence of `u8`.
+    struct Bytes<'a, T: ?Sized>(&'a T);
+
+    impl<T, I> Serialize for Bytes<'_, T>
+    where
+        for<'a> &'a T: IntoIterator<Item = &'a I>,
+        T: ?Sized,
+        I: AsRef<[u8]>,
+    {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            serializer.collect_seq(self.0.into_iter().map(|bytes| ByteSlice(bytes.as_ref())))
+        }
+    }
+
+    struct ByteSlice<'a>(&'a [u8]);
+
+    impl Serialize for ByteSlice<'_> {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            serializer.serialize_bytes(self.0)
+        }
+    }
+}
+
+/// Use with `#[serde(with = "dynamodb_expression::value::serde::binary")]` on
+/// a byte buffer (such as `Vec<u8>`) to serialize it as a
+/// [DynamoDB binary value][1], rather than a list of numbers.
+///
+/// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/HowItWorks.NamingRulesDataTypes.html#HowItWorks.DataTypes
+pub mod binary {
+    use core::fmt;
+
+    use ::serde::{
+        de::{self, SeqAccess, Visitor},
+        Deserialize, Deserializer, Serializer,
+    };
+
+    pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: ?Sized + AsRef<[u8]>,
+        S: Serializer,
+    {
+        serializer.serialize_bytes(value.as_ref())
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: From<Vec<u8>>,
+        D: Deserializer<'de>,
+    {
+        ByteBuf::deserialize(deserializer).map(|bytes| T::from(bytes.0))
+    }
+
+    /// Deserializes from bytes, or from a sequence of `u8` for formats that
+    /// don't have bytes.
+    pub(super) struct ByteBuf(pub(super) Vec<u8>);
+
+    impl<'de> Deserialize<'de> for ByteBuf {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserializer.deserialize_byte_buf(ByteBufVisitor)
+        }
+    }
+
+    struct ByteBufVisitor;
+
+    impl<'de> Visitor<'de> for ByteBufVisitor {
+        type Value = ByteBuf;
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("binary data")
+        }
+
+        fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            Ok(ByteBuf(v.to_vec()))
+        }
+
+        fn visit_byte_buf<E>(self, v: Vec<u8>) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            Ok(ByteBuf(v))
+        }
+
+        fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+        where
+            A: SeqAccess<'de>,
+        {
+            let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+            while let Some(byte) = seq.next_element()? {
+                bytes.push(byte);
+            }
+
+            Ok(ByteBuf(bytes))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+    use ::serde::{Deserialize, Serialize};
+    use pretty_assertions::assert_eq;
+
+    use crate::value::{Num, Value, ValuePathSegment};
+
+    use super::{from_value, to_value};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    enum Shape {
+        Point,
+        Circle(f64),
+        Rect { w: u32, h: u32 },
+        Pair(i8, i8),
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Item {
+        id: String,
+        count: u64,
+        delta: i32,
+        ratio: f64,
+        active: bool,
+        missing: Option<String>,
+        shapes: Vec<Shape>,
+        attributes: BTreeMap<String, String>,
+        #[serde(with = "super::string_set")]
+        tags: BTreeSet<String>,
+        #[serde(with = "super::number_set")]
+        scores: Vec<u16>,
+        #[serde(with = "super::binary_set")]
+        blobs: HashSet<Vec<u8>>,
+        #[serde(with = "super::binary")]
+        data: Vec<u8>,
+        initial: char,
+        unit: (),
+    }
+
+    fn item() -> Item {
+        Item {
+            id: String::from("item-1"),
+            count: 42,
+            delta: -7,
+            ratio: 0.5,
+            active: true,
+            missing: None,
+            shapes: vec![
+                Shape::Point,
+                Shape::Circle(1.5),
+                Shape::Rect { w: 2, h: 3 },
+                Shape::Pair(-1, 1),
+            ],
+            attributes: BTreeMap::from([(String::from("color"), String::from("red"))]),
+            tags: BTreeSet::from([String::from("a"), String::from("b")]),
+            scores: vec![1, 2, 3],
+            blobs: HashSet::from([vec![1, 2], vec![3]]),
+            data: vec![0, 255],
+            initial: 'x',
+            unit: (),
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let value = to_value(&item()).unwrap();
+
+        assert_eq!(
+            Value::new_map([
+                ("id", Value::from("item-1")),
+                ("count", Num::new(42).into()),
+                ("delta", Num::new(-7).into()),
+                ("ratio", Num::new(0.5).into()),
+                ("active", true.into()),
+                ("missing", Value::new_null()),
+                (
+                    "shapes",
+                    Value::new_list([
+                        Value::from("Point"),
+                        Value::new_map([("Circle", Num::new(1.5))]),
+                        Value::new_map([(
+                            "Rect",
+                            Value::new_map([("w", Num::new(2)), ("h", Num::new(3))]),
+                        )]),
+                        Value::new_map([("Pair", Value::new_list([Num::new(-1), Num::new(1)]),)]),
+                    ]),
+                ),
+                ("attributes", Value::new_map([("color", "red")])),
+                ("tags", Value::new_string_set(["a", "b"])),
+                ("scores", Value::new_num_set([1, 2, 3])),
+                ("blobs", Value::new_binary_set([vec![1, 2], vec![3]])),
+                ("data", Value::new_binary([0, 255])),
+                ("initial", "x".into()),
+                ("unit", Value::new_null()),
+            ]),
+            value,
+        );
+
+        assert_eq!(item(), from_value(value).unwrap());
+    }
+
+    #[test]
+    fn errors() {
+        to_value(&f64::NAN).expect_err("NaN isn't a valid DynamoDB number");
+        to_value(&BTreeMap::from([((1, 2), "a")])).expect_err("Map keys must be strings");
+
+        #[derive(Serialize)]
+        struct Mixed(#[serde(with = "super::string_set")] Vec<u8>);
+        to_value(&Mixed(vec![1])).expect_err("A string set must contain strings");
+
+        #[derive(Serialize)]
+        struct Sets {
+            #[serde(with = "super::string_set")]
+            strings: Vec<String>,
+            #[serde(with = "super::number_set")]
+            numbers: Vec<u8>,
+            #[serde(with = "super::binary_set")]
+            blobs: Vec<Vec<u8>>,
+        }
+        let sets = || Sets {
+            strings: vec![String::from("a")],
+            numbers: vec![1],
+            blobs: vec![vec![1]],
+        };
+        to_value(&sets()).expect("None of the sets are empty");
+        assert_eq!(
+            "StringSet can't be empty; skip the field when it is",
+            to_value(&Sets {
+                strings: Vec::new(),
+                ..sets()
+            })
+            .unwrap_err()
+            .to_string()
+        );
+        to_value(&Sets {
+            numbers: Vec::new(),
+            ..sets()
+        })
+        .expect_err("A number set can't be empty");
+        to_value(&Sets {
+            blobs: Vec::new(),
+            ..sets()
+        })
+        .expect_err("A binary set can't be empty");
+
+        from_value::<u8>(Num::new(256).into()).expect_err("Out of range");
+        from_value::<String>(Num::new(1).into()).expect("Numbers can be read as strings");
+        from_value::<bool>("true".into()).expect_err("Not a boolean");
+    }
+
+    #[test]
+    fn error_path() {
+        let value = Value::new_map([(
+            "shapes",
+            Value::new_list([
+                Value::from("Point"),
+                Value::new_map([("Rect", Value::new_map([("w", Value::from("wide"))]))]),
+            ]),
+        )]);
+
+        #[derive(Debug, Deserialize)]
+        #[expect(dead_code, reason = "Only deserialized to check errors")]
+        struct Shapes {
+            shapes: Vec<Shape>,
+        }
+
+        let err = from_value::<Shapes>(value).unwrap_err();
+        assert_eq!(
+            &[
+                ValuePathSegment::Key("shapes".into()),
+                ValuePathSegment::Index(1),
+                ValuePathSegment::Key("Rect".into()),
+                ValuePathSegment::Key("w".into()),
+            ],
+            err.path()
+        );
+        assert!(err.to_string().starts_with("shapes[1].Rect.w: "), "{err}");
+
+        let value = Value::new_map([(
+            "shapes",
+            Value::new_list([Value::new_map([(
+                "Pair",
+                Value::new_list([Value::new_num(1), Value::from("2")]),
+            )])]),
+        )]);
+        let err = from_value::<Shapes>(value).unwrap_err();
+        assert!(err.to_string().starts_with("shapes[0].Pair[1]: "), "{err}");
+
+        let err = from_value::<Vec<u8>>(Value::new_list([Value::new_num(1), Value::from("2")]))
+            .unwrap_err();
+        assert_eq!(&[ValuePathSegment::Index(1)], err.path());
+    }
+}
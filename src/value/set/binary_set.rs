@@ -3,13 +3,16 @@ use std::collections::BTreeSet;
 
 use aws_sdk_dynamodb::{primitives::Blob, types::AttributeValue};
 
-use super::base64;
+use super::{
+    super::validate::{try_collect_set, ValueError},
+    base64,
+};
 
 /// Represents a [DynamoDB binary set][1].
 ///
 /// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/HowItWorks.NamingRulesDataTypes.html#HowItWorks.DataTypes.SetTypes
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct BinarySet(BTreeSet<Vec<u8>>);
+pub struct BinarySet(pub(crate) BTreeSet<Vec<u8>>);
 
 impl BinarySet {
     /// Creates a value to use as a [DynamoDB binary set][1].
@@ -70,6 +73,29 @@ impl BinarySet {
         set.into()
     }
 
+    /// Like [`BinarySet::new`], but fails if the set would be one DynamoDB
+    /// won't accept: it's empty or has duplicate members.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dynamodb_expression::value::BinarySet;
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// assert_eq!(Ok(BinarySet::new(["a", "b"])), BinarySet::try_new(["a", "b"]));
+    ///
+    /// // Binary members are shown base64 encoded.
+    /// let err = BinarySet::try_new(["a", "a"]).unwrap_err();
+    /// assert_eq!("duplicate set member: YQ==", err.to_string());
+    /// ```
+    pub fn try_new<I, T>(set: I) -> Result<Self, ValueError>
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<Vec<u8>>,
+    {
+        try_collect_set(set.into_iter().map(|b| Ok(b.into())), |b| base64(b)).map(Self)
+    }
+
     // Intentionally not using `impl From<BinarySet> for AttributeValue` because
     // I don't want to make this a public API people rely on. The purpose of this
     // crate is not to make creating `AttributeValues` easier. They should try
@@ -151,16 +151,16 @@ mod test {
             "[\
                 -3.4028235e38, \
                 0, \
+                9.2, \
                 1000, \
-                3.4028235E38, \
-                9.2\
+                3.4028235E38\
             ]",
             set.to_string()
         );
 
         let deserialized: Vec<f32> =
             serde_json::from_str(&set.to_string()).expect("Must be valid JSON");
-        assert_eq!(vec![f32::MIN, 0.0, 1000.0, f32::MAX, 9.2], deserialized);
+        assert_eq!(vec![f32::MIN, 0.0, 9.2, 1000.0, f32::MAX], deserialized);
     }
 
     #[test]
@@ -3,13 +3,15 @@ use std::collections::BTreeSet;
 
 use aws_sdk_dynamodb::types::AttributeValue;
 
+use crate::value::validate::{try_collect_set, ValueError};
+
 use crate::Num;
 
 /// Represents a [DynamoDB number set][1].
 ///
 /// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/HowItWorks.NamingRulesDataTypes.html#HowItWorks.DataTypes.SetTypes
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct NumSet(BTreeSet<Num>);
+pub struct NumSet(pub(crate) BTreeSet<Num>);
 
 impl NumSet {
     /// Creates a value to use as a [DynamoDB number set][1].
@@ -39,6 +41,42 @@ impl NumSet {
         set.into()
     }
 
+    /// Like [`NumSet::new`], but fails if the set would be one DynamoDB won't
+    /// accept: it's empty, has a number that isn't valid (see
+    /// [`Num::validate`]), or has members that are numerically the same
+    /// (e.g., `1` and `1.0`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dynamodb_expression::value::{Num, NumSet, ValueErrorKind};
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// assert_eq!(Ok(NumSet::new([1, 2, 3])), NumSet::try_new([1, 2, 3]));
+    ///
+    /// let err = NumSet::try_new([Num::new(1000), Num::new_lower_exp(1000)]).unwrap_err();
+    /// assert_eq!("duplicate set member: 1e3 (same as 1000)", err.to_string());
+    ///
+    /// let err = NumSet::try_new(Vec::<Num>::new()).unwrap_err();
+    /// assert_eq!(&ValueErrorKind::EmptySet, err.kind());
+    /// ```
+    pub fn try_new<I, T>(set: I) -> Result<Self, ValueError>
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<Num>,
+    {
+        try_collect_set(
+            set.into_iter().map(|num| {
+                let num = num.into();
+                num.validate()?;
+
+                Ok(num)
+            }),
+            Num::to_string,
+        )
+        .map(Self)
+    }
+
     // Intentionally not using `impl From<NumSet> for AttributeValue` because
     // I don't want to make this a public API people rely on. The purpose of this
     // crate is not to make creating `AttributeValues` easier. They should try
@@ -3,11 +3,13 @@ use std::collections::BTreeSet;
 
 use aws_sdk_dynamodb::types::AttributeValue;
 
+use crate::value::validate::{try_collect_set, ValueError};
+
 /// Represents a [DynamoDB string set][1].
 ///
 /// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/HowItWorks.NamingRulesDataTypes.html#HowItWorks.DataTypes.SetTypes
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct StringSet(BTreeSet<String>);
+pub struct StringSet(pub(crate) BTreeSet<String>);
 
 impl StringSet {
     /// Creates a value to use as a [DynamoDB string set][1]. A collection of unique strings.
@@ -37,6 +39,28 @@ impl StringSet {
         set.into()
     }
 
+    /// Like [`StringSet::new`], but fails if the set would be one DynamoDB
+    /// won't accept: it's empty or has duplicate members.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dynamodb_expression::value::StringSet;
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// assert_eq!(Ok(StringSet::new(["a", "b"])), StringSet::try_new(["a", "b"]));
+    ///
+    /// let err = StringSet::try_new(["a", "b", "a"]).unwrap_err();
+    /// assert_eq!("duplicate set member: a", err.to_string());
+    /// ```
+    pub fn try_new<I, T>(set: I) -> Result<Self, ValueError>
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        try_collect_set(set.into_iter().map(|s| Ok(s.into())), String::clone).map(Self)
+    }
+
     // Intentionally not using `impl From<StringSet> for AttributeValue` because
     // I don't want to make this a public API people rely on. The purpose of this
     // crate is not to make creating `AttributeValues` easier. They should try
@@ -0,0 +1,283 @@
+//! Item size estimation, following [AWS's published rules][1].
+//!
+//! [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/CapacityUnitCalculations.html
+
+use std::collections::HashMap;
+
+use aws_sdk_dynamodb::types::AttributeValue;
+
+use super::{num::num_size_bytes, List, Map, Num, Scalar, Set, Value};
+
+/// The largest item DynamoDB allows, in bytes (400 KB).
+pub const MAX_ITEM_SIZE_BYTES: usize = 400 * 1024;
+
+/// The overhead of a list or map, regardless of its contents.
+const DOCUMENT_OVERHEAD: usize = 3;
+
+/// The overhead of each element in a list or map.
+const ELEMENT_OVERHEAD: usize = 1;
+
+impl Value {
+    /// The approximate size of this value when stored by DynamoDB, in bytes,
+    /// per [AWS's published rules][1]. This does not include the length of the
+    /// attribute name it's stored under.
+    ///
+    /// * Strings and binary: their length in bytes (UTF-8 for strings).
+    /// * Numbers: one byte per two significant digits, plus one byte.
+    /// * Booleans and null: one byte.
+    /// * Sets: the sum of their members' sizes.
+    /// * Lists and maps: three bytes, plus one byte per element, plus the size
+    ///   of each element. Map elements include the length of their key.
+    ///
+    /// See also: [`Map::item_size_bytes`], [`item_size_bytes`]
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dynamodb_expression::Value;
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// assert_eq!(5, Value::new_string("hello").size_bytes());
+    /// assert_eq!(3, Value::new_num(123).size_bytes());
+    /// assert_eq!(1, Value::new_bool(true).size_bytes());
+    ///
+    /// // 3 bytes of overhead, 1 byte per element, plus each element.
+    /// assert_eq!(3 + (1 + 1) + (1 + 2), Value::new_list([Value::new_null(), Value::new_num(7)]).size_bytes());
+    /// ```
+    ///
+    /// [1]: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/CapacityUnitCalculations.html
+    /// [`item_size_bytes`]: crate::value::item_size_bytes
+    pub fn size_bytes(&self) -> usize {
+        match self {
+            Self::Scalar(scalar) => scalar.size_bytes(),
+            Self::Set(Set::StringSet(set)) => set.0.iter().map(String::len).sum(),
+            Self::Set(Set::NumSet(set)) => set.0.iter().map(Num::size_bytes).sum(),
+            Self::Set(Set::BinarySet(set)) => set.0.iter().map(Vec::len).sum(),
+            Self::List(List { list }) => {
+                DOCUMENT_OVERHEAD
+                    + list
+                        .iter()
+                        .map(|value| ELEMENT_OVERHEAD + value.size_bytes())
+                        .sum::<usize>()
+            }
+            Self::Map(map) => DOCUMENT_OVERHEAD + map.elements_size_bytes(ELEMENT_OVERHEAD),
+        }
+    }
+}
+
+impl Scalar {
+    /// The approximate size of this value when stored by DynamoDB, in bytes.
+    ///
+    /// See [`Value::size_bytes`] for details.
+    pub fn size_bytes(&self) -> usize {
+        match self {
+            Self::String(s) => s.len(),
+            Self::Num(num) => num.size_bytes(),
+            Self::Bool(_) | Self::Null => 1,
+            Self::Binary(b) => b.len(),
+        }
+    }
+}
+
+impl Num {
+    /// The approximate size of this number when stored by DynamoDB, in bytes:
+    /// one byte per two significant digits, plus one byte. Leading and
+    /// trailing zeros aren't counted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dynamodb_expression::Num;
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// assert_eq!(4, Num::new(12345).size_bytes());
+    /// assert_eq!(2, Num::new(1_000_000).size_bytes());
+    /// assert_eq!(2, Num::new(0.05).size_bytes());
+    /// ```
+    pub fn size_bytes(&self) -> usize {
+        num_size_bytes(&self.n)
+    }
+}
+
+impl Map {
+    /// The approximate size of this map when stored by DynamoDB as an item
+    /// (a row in a table), in bytes. That's the sum of the length of each
+    /// attribute name and the size of its value. Unlike [`Value::size_bytes`],
+    /// there is no overhead for the map itself.
+    ///
+    /// Compare with [`MAX_ITEM_SIZE_BYTES`] to catch items that are too large.
+    ///
+    /// See also: [`item_size_bytes`]
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dynamodb_expression::value::{Map, MAX_ITEM_SIZE_BYTES};
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let item = Map::new([("id", "abc123"), ("name", "Widget")]);
+    /// assert_eq!((2 + 6) + (4 + 6), item.item_size_bytes());
+    /// assert!(item.item_size_bytes() <= MAX_ITEM_SIZE_BYTES);
+    /// ```
+    ///
+    /// [`MAX_ITEM_SIZE_BYTES`]: crate::value::MAX_ITEM_SIZE_BYTES
+    /// [`item_size_bytes`]: crate::value::item_size_bytes
+    pub fn item_size_bytes(&self) -> usize {
+        self.elements_size_bytes(0)
+    }
+
+    fn elements_size_bytes(&self, overhead: usize) -> usize {
+        self.map
+            .iter()
+            .map(|(name, value)| overhead + name.name.len() + value.size_bytes())
+            .sum()
+    }
+}
+
+/// The approximate size of an item (a row in a table) when stored by
+/// DynamoDB, in bytes. That's the sum of the length of each attribute name and
+/// the size of its value. See [`Value::size_bytes`] for how values are sized.
+///
+/// Compare with [`MAX_ITEM_SIZE_BYTES`] to catch items that are too large.
+///
+/// An [`AttributeValue::Unknown`] (a type added to DynamoDB after this version
+/// of the AWS SDK) can't be sized, so it counts as zero bytes. An item with
+/// one may be larger than this returns.
+///
+/// See also: [`Map::item_size_bytes`]
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// use aws_sdk_dynamodb::types::AttributeValue;
+/// use dynamodb_expression::value::item_size_bytes;
+/// # use pretty_assertions::assert_eq;
+///
+/// let item = HashMap::from([
+///     ("id".to_string(), AttributeValue::S("abc123".into())),
+///     ("count".to_string(), AttributeValue::N("42".into())),
+/// ]);
+/// assert_eq!((2 + 6) + (5 + 2), item_size_bytes(&item));
+/// ```
+pub fn item_size_bytes(item: &HashMap<String, AttributeValue>) -> usize {
+    item.iter()
+        .map(|(name, value)| name.len() + attribute_value_size_bytes(value))
+        .sum()
+}
+
+fn attribute_value_size_bytes(value: &AttributeValue) -> usize {
+    match value {
+        AttributeValue::S(s) => s.len(),
+        AttributeValue::N(n) => num_size_bytes(n),
+        AttributeValue::B(b) => b.as_ref().len(),
+        AttributeValue::Bool(_) | AttributeValue::Null(_) => 1,
+        AttributeValue::Ss(ss) => ss.iter().map(String::len).sum(),
+        AttributeValue::Ns(ns) => ns.iter().map(|n| num_size_bytes(n)).sum(),
+        AttributeValue::Bs(bs) => bs.iter().map(|b| b.as_ref().len()).sum(),
+        AttributeValue::L(list) => {
+            DOCUMENT_OVERHEAD
+                + list
+                    .iter()
+                    .map(|value| ELEMENT_OVERHEAD + attribute_value_size_bytes(value))
+                    .sum::<usize>()
+        }
+        AttributeValue::M(map) => {
+            DOCUMENT_OVERHEAD
+                + map
+                    .iter()
+                    .map(|(name, value)| {
+                        ELEMENT_OVERHEAD + name.len() + attribute_value_size_bytes(value)
+                    })
+                    .sum::<usize>()
+        }
+        // Unknown to this version of the SDK, so there's nothing to size.
+        _ => 0,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use aws_sdk_dynamodb::{primitives::Blob, types::AttributeValue};
+    use pretty_assertions::assert_eq;
+
+    use super::item_size_bytes;
+    use crate::value::{Map, Num, Value};
+
+    #[test]
+    fn num() {
+        for (expected, n) in [
+            (1, "0"),
+            (2, "1"),
+            (2, "-1"),
+            (2, "10"),
+            (2, "12"),
+            (3, "123"),
+            (2, "0.0001"),
+            (3, "1.25"),
+            (2, "1e100"),
+            (20, "12345678901234567890123456789012345678"),
+        ] {
+            assert_eq!(expected, Num { n: n.into() }.size_bytes(), "{n}");
+        }
+    }
+
+    #[test]
+    fn value_and_attribute_value_agree() {
+        let value = Value::new_map([
+            ("s", Value::new_string("héllo")),
+            ("n", Value::new_num(-12.5)),
+            ("b", Value::new_binary([1, 2, 3])),
+            ("bool", Value::new_bool(false)),
+            ("null", Value::new_null()),
+            ("ss", Value::new_string_set(["a", "bb"])),
+            ("ns", Value::new_num_set([1, 100])),
+            ("bs", Value::new_binary_set([b"abc"])),
+            (
+                "l",
+                Value::new_list([Value::new_list(Vec::<Value>::new()), Value::new_num(1)]),
+            ),
+            ("m", Value::new_map([("k", "v")])),
+        ]);
+
+        let expected = 3 // map overhead
+            + (1 + 1 + 6) // "s": "héllo" (é is 2 bytes)
+            + (1 + 1 + 3) // "n": -12.5
+            + (1 + 1 + 3) // "b"
+            + (1 + 4 + 1) // "bool"
+            + (1 + 4 + 1) // "null"
+            + (1 + 2 + 3) // "ss"
+            + (1 + 2 + (2 + 2)) // "ns"
+            + (1 + 2 + 3) // "bs"
+            + (1 + 1 + 3 + (1 + 3) + (1 + 2)) // "l"
+            + (1 + 1 + 3 + (1 + 1 + 1)); // "m"
+        assert_eq!(expected, value.size_bytes());
+
+        let Value::Map(map) = value.clone() else {
+            unreachable!()
+        };
+        assert_eq!(expected - 3 - 10, map.item_size_bytes());
+
+        let AttributeValue::M(item) = value.into_attribute_value() else {
+            unreachable!()
+        };
+        assert_eq!(expected - 3 - 10, item_size_bytes(&item));
+    }
+
+    #[test]
+    fn empty_item() {
+        assert_eq!(0, Map::default().item_size_bytes());
+        assert_eq!(3, Value::new_map(Map::default()).size_bytes());
+        assert_eq!(0, item_size_bytes(&HashMap::new()));
+        assert_eq!(
+            4,
+            item_size_bytes(&HashMap::from([(
+                "b".into(),
+                AttributeValue::B(Blob::new(vec![1, 2, 3]))
+            )]))
+        );
+    }
+}
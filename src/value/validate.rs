@@ -0,0 +1,353 @@
+use core::fmt;
+use std::{collections::BTreeSet, error::Error};
+
+use super::{List, Map, NumError, Scalar, Set, Value};
+
+impl Value {
+    /// Checks that this is a value DynamoDB will accept. That is, all numbers
+    /// are valid (see [`Num::validate`]) and no sets are empty. Nested lists
+    /// and maps are checked, too.
+    ///
+    /// Sets can't hold duplicates once they're built, so duplicate members are
+    /// only caught by the `try_new` constructors. E.g., [`NumSet::try_new`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dynamodb_expression::value::{Num, Value, ValueErrorKind, ValuePathSegment};
+    /// # use pretty_assertions::assert_eq;
+    ///
+    /// let value = Value::new_map([
+    ///     ("name", Value::new_string("Widget")),
+    ///     ("sizes", Value::new_list([Value::new_num(1), Value::new_num(f64::NAN)])),
+    /// ]);
+    ///
+    /// let error = value.validate().unwrap_err();
+    /// assert_eq!(
+    ///     &[ValuePathSegment::Key("sizes".into()), ValuePathSegment::Index(1)],
+    ///     error.path()
+    /// );
+    /// assert!(matches!(error.kind(), ValueErrorKind::InvalidNum(_)));
+    /// assert_eq!("sizes[1]: not a number: \"NaN\"", error.to_string());
+    ///
+    /// assert!(Value::new_num_set(Vec::<Num>::new()).validate().is_err());
+    /// ```
+    ///
+    /// [`Num::validate`]: crate::value::Num::validate
+    /// [`NumSet::try_new`]: crate::value::NumSet::try_new
+    pub fn validate(&self) -> Result<(), ValueError> {
+        match self {
+            Self::Scalar(Scalar::Num(num)) => num.validate().map_err(ValueError::from),
+            Self::Scalar(_) => Ok(()),
+            Self::Set(set) => {
+                let is_empty = match set {
+                    Set::StringSet(set) => set.0.is_empty(),
+                    Set::NumSet(set) => set.0.is_empty(),
+                    Set::BinarySet(set) => set.0.is_empty(),
+                };
+
+                if is_empty {
+                    return Err(ValueErrorKind::EmptySet.into());
+                }
+
+                if let Set::NumSet(set) = set {
+                    set.0.iter().try_for_each(|num| num.validate())?;
+                }
+
+                Ok(())
+            }
+            Self::List(List { list }) => list.iter().enumerate().try_for_each(|(i, value)| {
+                value
+                    .validate()
+                    .map_err(|err| err.within(ValuePathSegment::Index(i)))
+            }),
+            Self::Map(Map { map }) => map.iter().try_for_each(|(name, value)| {
+                value
+                    .validate()
+                    .map_err(|err| err.within(ValuePathSegment::Key(name.name.clone())))
+            }),
+        }
+    }
+}
+
+/// Collects set members, failing if the set would be empty or if any members
+/// are duplicates of each other. `describe` renders a member for the error.
+pub(super) fn try_collect_set<I, T, F>(iter: I, describe: F) -> Result<BTreeSet<T>, ValueError>
+where
+    I: IntoIterator<Item = Result<T, ValueError>>,
+    T: Ord,
+    F: Fn(&T) -> String,
+{
+    let mut set = BTreeSet::new();
+    for member in iter {
+        let member = member?;
+        if let Some(existing) = set.get(&member) {
+            return Err(ValueErrorKind::DuplicateSetMember {
+                member: describe(&member),
+                existing: describe(existing),
+            }
+            .into());
+        }
+
+        set.insert(member);
+    }
+
+    if set.is_empty() {
+        return Err(ValueErrorKind::EmptySet.into());
+    }
+
+    Ok(set)
+}
+
+/// An error for a value DynamoDB would reject.
+///
+/// Returned by [`Value::validate`] and the `try_new` constructors, such as
+/// [`NumSet::try_new`] and [`Map::try_new`].
+///
+/// [`NumSet::try_new`]: crate::value::NumSet::try_new
+/// [`Map::try_new`]: crate::value::Map::try_new
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValueError {
+    path: Vec<ValuePathSegment>,
+    kind: ValueErrorKind,
+}
+
+impl ValueError {
+    /// Where in the value the problem is. Empty if it's the value itself.
+    pub fn path(&self) -> &[ValuePathSegment] {
+        &self.path
+    }
+
+    /// What the problem is.
+    pub fn kind(&self) -> &ValueErrorKind {
+        &self.kind
+    }
+
+    /// Marks this error as being within the given map key or list index.
+    pub(super) fn within(mut self, segment: ValuePathSegment) -> Self {
+        self.path.insert(0, segment);
+        self
+    }
+}
+
+impl fmt::Display for ValueError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_path_prefix(f, &self.path)?;
+        self.kind.fmt(f)
+    }
+}
+
+/// Writes the path (e.g., `foo.bar[2]`) followed by `: `, if there is a path.
+pub(super) fn write_path_prefix(
+    f: &mut fmt::Formatter<'_>,
+    path: &[ValuePathSegment],
+) -> fmt::Result {
+    if path.is_empty() {
+        return Ok(());
+    }
+
+    let mut first = true;
+    for segment in path {
+        match segment {
+            ValuePathSegment::Key(key) if first => f.write_str(key)?,
+            ValuePathSegment::Key(key) => write!(f, ".{key}")?,
+            ValuePathSegment::Index(i) => write!(f, "[{i}]")?,
+        }
+
+        first = false;
+    }
+
+    f.write_str(": ")
+}
+
+impl Error for ValueError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match &self.kind {
+            ValueErrorKind::InvalidNum(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<ValueErrorKind> for ValueError {
+    fn from(kind: ValueErrorKind) -> Self {
+        Self {
+            path: Vec::new(),
+            kind,
+        }
+    }
+}
+
+impl From<NumError> for ValueError {
+    fn from(err: NumError) -> Self {
+        ValueErrorKind::InvalidNum(err).into()
+    }
+}
+
+/// The kind of problem found with a value. See [`ValueError`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValueErrorKind {
+    /// A number DynamoDB won't accept. E.g., `NaN`, infinity, or out of range.
+    InvalidNum(NumError),
+    /// A set with no members. DynamoDB doesn't allow empty sets.
+    EmptySet,
+    /// Two set members that are the same. Numbers are compared numerically,
+    /// so `1` and `1.0` are duplicates. Binary members are base64 encoded.
+    DuplicateSetMember { member: String, existing: String },
+    /// A key that appears more than once when building a map.
+    DuplicateMapKey(String),
+}
+
+impl fmt::Display for ValueErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidNum(err) => err.fmt(f),
+            Self::EmptySet => f.write_str("sets cannot be empty"),
+            Self::DuplicateSetMember { member, existing } if member == existing => {
+                write!(f, "duplicate set member: {member}")
+            }
+            Self::DuplicateSetMember { member, existing } => {
+                write!(f, "duplicate set member: {member} (same as {existing})")
+            }
+            Self::DuplicateMapKey(key) => write!(f, "duplicate map key: {key}"),
+        }
+    }
+}
+
+/// A step into a [`Value`], used to say where a [`ValueError`] is.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ValuePathSegment {
+    /// A key in a map.
+    Key(String),
+    /// An index in a list.
+    Index(usize),
+}
+
+impl fmt::Display for ValuePathSegment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Key(key) => f.write_str(key),
+            Self::Index(i) => write!(f, "[{i}]"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use crate::value::{
+        BinarySet, Map, Num, NumError, NumSet, StringSet, Value, ValueError, ValueErrorKind,
+        ValuePathSegment,
+    };
+
+    #[test]
+    fn num_set() {
+        assert_eq!(
+            Ok(NumSet::new([1, 2, 3])),
+            NumSet::try_new([Num::new(3), Num::new(1), Num::new(2)])
+        );
+
+        assert_eq!(
+            Err(ValueError::from(ValueErrorKind::EmptySet)),
+            NumSet::try_new(Vec::<Num>::new())
+        );
+
+        let err =
+            NumSet::try_new(["1".parse::<Num>().unwrap(), "1.0".parse().unwrap()]).unwrap_err();
+        assert_eq!(
+            &ValueErrorKind::DuplicateSetMember {
+                member: "1.0".into(),
+                existing: "1".into(),
+            },
+            err.kind()
+        );
+        assert_eq!("duplicate set member: 1.0 (same as 1)", err.to_string());
+
+        assert_eq!(
+            Err(NumError::Invalid("inf".into()).into()),
+            NumSet::try_new([1.0, f64::INFINITY])
+        );
+        assert_eq!(
+            Err(NumError::OutOfRange("1e200".into()).into()),
+            NumSet::try_new([Num::new(1), Num::new_lower_exp(1e200)])
+        );
+    }
+
+    #[test]
+    fn string_and_binary_sets() {
+        assert_eq!(
+            Ok(StringSet::new(["a", "b"])),
+            StringSet::try_new(["b", "a"])
+        );
+        assert_eq!(
+            "duplicate set member: a",
+            StringSet::try_new(["a", "b", "a"]).unwrap_err().to_string()
+        );
+        assert_eq!(
+            Err(ValueErrorKind::EmptySet.into()),
+            StringSet::try_new(Vec::<String>::new())
+        );
+
+        assert_eq!(
+            Ok(BinarySet::new(["a", "b"])),
+            BinarySet::try_new(["a", "b"])
+        );
+        assert_eq!(
+            "duplicate set member: YQ==",
+            BinarySet::try_new(["a", "a"]).unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn map() {
+        assert_eq!(
+            Ok(Map::new([("a", Num::new(1)), ("b", Num::new(2))])),
+            Map::try_new([("a", Num::new(1)), ("b", Num::new(2))])
+        );
+
+        assert_eq!(
+            "duplicate map key: a",
+            Map::try_new([("a", "x"), ("b", "y"), ("a", "z")])
+                .unwrap_err()
+                .to_string()
+        );
+
+        let err = Map::try_new([
+            ("ok", Value::new_num(1)),
+            (
+                "nested",
+                Value::new_map([(
+                    "list",
+                    Value::new_list([
+                        Value::new_string("x"),
+                        Value::new_num_set(Vec::<Num>::new()),
+                    ]),
+                )]),
+            ),
+        ])
+        .unwrap_err();
+        assert_eq!(
+            vec![
+                ValuePathSegment::Key("nested".into()),
+                ValuePathSegment::Key("list".into()),
+                ValuePathSegment::Index(1),
+            ],
+            err.path()
+        );
+        assert_eq!("nested.list[1]: sets cannot be empty", err.to_string());
+    }
+
+    #[test]
+    fn num() {
+        assert_eq!(Ok(Value::new_num(42)), Value::try_new_num(42));
+        assert_eq!(
+            Err(NumError::Invalid("NaN".into()).into()),
+            Value::try_new_num(f64::NAN)
+        );
+        assert_eq!(
+            Err(NumError::Invalid("-inf".into()).into()),
+            Value::try_new_num(f64::NEG_INFINITY)
+        );
+    }
+}